@@ -0,0 +1,64 @@
+//! Prints statistics about a Model
+//!
+//! ```sh
+//! cargo run --example info -- model.glb
+//! ```
+
+use modelz::{Image, Model3D};
+
+fn main() {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("Usage: cargo run --example info -- <model>");
+        std::process::exit(2);
+    };
+    let model = match Model3D::load(&path) {
+        Ok(model) => model,
+        Err(e) => {
+            eprintln!("Failed to load {path}: {e:?}");
+            std::process::exit(1);
+        }
+    };
+
+    println!("{path}");
+    println!("Meshes: {}", model.meshes.len());
+    for (i, mesh) in model.meshes.iter().enumerate() {
+        println!(
+            "  [{i}] {}: {} vertices, {} triangles, {:?}, material {:?}",
+            mesh.name.as_deref().unwrap_or("<unnamed>"),
+            mesh.vertices.len(),
+            mesh.triangle_count(),
+            mesh.mode,
+            mesh.material_index,
+        );
+    }
+
+    println!("Materials: {}", model.materials.len());
+    for (i, material) in model.materials.iter().enumerate() {
+        println!(
+            "  [{i}] {}",
+            material.name.as_deref().unwrap_or("<unnamed>")
+        );
+        let textures = [
+            ("diffuse", &material.diffuse_texture),
+            ("normal", &material.normal_texture),
+        ];
+        for (slot, texture) in textures {
+            let Some(texture) = texture else {
+                continue;
+            };
+            match &texture.image {
+                Image::Path { path, .. } => println!("      {slot}: {}", path.display()),
+                Image::Memory { data, mime_type } => println!(
+                    "      {slot}: embedded {} ({} bytes)",
+                    mime_type.as_deref().unwrap_or("unknown"),
+                    data.len()
+                ),
+            }
+        }
+    }
+
+    match model.aabb() {
+        Some((min, max)) => println!("AABB: {min:?} .. {max:?}"),
+        None => println!("AABB: empty"),
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use crate::{normals::position_key, Mesh};
+
+impl Mesh {
+    /// Returns an index buffer with adjacency for `GL_TRIANGLES_ADJACENCY` or `vk::PrimitiveTopology::TRIANGLE_LIST_WITH_ADJACENCY`
+    ///
+    /// Every Triangle `[a, b, c]` becomes 6 indices `[a, ab, b, bc, c, ca]`, Where `ab` is the Vertex of the neighbouring
+    /// Triangle opposite of the edge `a b`. Edges are matched by Vertex position, So neighbours are found across UV and
+    /// normal seams. Boundary edges have no neighbour and use the opposite Vertex of the Triangle itself.
+    /// Strips and fans are read as their Triangles, Meshes which are not made of Triangles return an empty Vec
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.obj").expect("Failed to load");
+    /// let adjacency = model.meshes[0].to_adjacency_indices();
+    /// assert_eq!(adjacency.len(), model.meshes[0].triangle_count() * 6);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    #[must_use]
+    pub fn to_adjacency_indices(&self) -> Vec<u32> {
+        let key = |index: usize| position_key(self.vertices[index].position);
+        // Directed edge to the Vertex opposite of it, The neighbour sees the same edge in reverse
+        let mut opposite = HashMap::new();
+        for [a, b, c] in self.triangles() {
+            for (from, to, across) in [(a, b, c), (b, c, a), (c, a, b)] {
+                opposite.entry((key(from), key(to))).or_insert(across);
+            }
+        }
+        let index = |i: usize| u32::try_from(i).expect("Vertex index does not fit into u32");
+        self.triangles()
+            .flat_map(|[a, b, c]| {
+                let neighbour = |from: usize, to: usize, own: usize| {
+                    opposite.get(&(key(to), key(from))).copied().unwrap_or(own)
+                };
+                [
+                    a,
+                    neighbour(a, b, c),
+                    b,
+                    neighbour(b, c, a),
+                    c,
+                    neighbour(c, a, b),
+                ]
+                .map(index)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{Mesh, Vertex};
+
+/// An optional attribute of a [`Vertex`], The position is always present
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VertexAttr {
+    /// `Vertex::color`
+    Color,
+    /// `Vertex::tex_coord`
+    TexCoord,
+    /// `Vertex::normal`
+    Normal,
+    /// `Vertex::tangent`
+    Tangent,
+}
+
+impl VertexAttr {
+    /// All optional attributes
+    pub const ALL: [Self; 4] = [Self::Color, Self::TexCoord, Self::Normal, Self::Tangent];
+
+    /// Whether the Vertex has this attribute
+    const fn is_present(self, vertex: &Vertex) -> bool {
+        match self {
+            Self::Color => vertex.color.is_some(),
+            Self::TexCoord => vertex.tex_coord.is_some(),
+            Self::Normal => vertex.normal.is_some(),
+            Self::Tangent => vertex.tangent.is_some(),
+        }
+    }
+}
+
+impl Mesh {
+    /// Returns every attribute at least one Vertex of the Mesh has, In the order of [`VertexAttr::ALL`]
+    #[must_use]
+    pub fn available_attributes(&self) -> Vec<VertexAttr> {
+        VertexAttr::ALL
+            .into_iter()
+            .filter(|attr| self.vertices.iter().any(|vertex| attr.is_present(vertex)))
+            .collect()
+    }
+
+    /// Removes an attribute from every Vertex, e.g. to save memory when the renderer does not use colors
+    ///
+    /// Dropping normals or tangents also drops their Morph Target displacements
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{Model3D, VertexAttr};
+    ///
+    /// let mut model = Model3D::load("model.gltf").expect("Failed to load");
+    /// for mesh in &mut model.meshes {
+    ///     mesh.drop_attribute(VertexAttr::Color);
+    /// }
+    /// ```
+    pub fn drop_attribute(&mut self, attr: VertexAttr) {
+        for vertex in &mut self.vertices {
+            match attr {
+                VertexAttr::Color => vertex.color = None,
+                VertexAttr::TexCoord => vertex.tex_coord = None,
+                VertexAttr::Normal => vertex.normal = None,
+                VertexAttr::Tangent => vertex.tangent = None,
+            }
+        }
+        for target in &mut self.morph_targets {
+            match attr {
+                VertexAttr::Normal => target.normals = None,
+                VertexAttr::Tangent => target.tangents = None,
+                VertexAttr::Color | VertexAttr::TexCoord => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{math, Mesh, Model3D};
+
+impl Mesh {
+    /// Axis aligned bounding box of all Vertex positions as `(min, max)`, `None` if the Mesh has no Vertices
+    ///
+    /// Uses the precomputed `bounds` when present, Otherwise all Vertices are scanned
+    #[must_use]
+    pub fn aabb(&self) -> Option<([f32; 3], [f32; 3])> {
+        if self.vertices.is_empty() {
+            return None;
+        }
+        self.bounds
+            .or_else(|| aabb(self.vertices.iter().map(|v| v.position)))
+    }
+
+    /// Number of Triangles the Mesh draws, See [`Mesh::triangles`]
+    #[must_use]
+    pub fn triangle_count(&self) -> usize {
+        self.triangles().count()
+    }
+
+    /// Bounding sphere of all Vertex positions as `(center, radius)`, `None` if the Mesh has no Vertices
+    ///
+    /// Uses Ritter's algorithm, The sphere is not the smallest possible one but at most ~5% larger
+    #[must_use]
+    pub fn bounding_sphere(&self) -> Option<([f32; 3], f32)> {
+        ritter(self.vertices.iter().map(|v| v.position))
+    }
+
+    /// Average of all Vertex positions, `None` if the Mesh has no Vertices
+    ///
+    /// Cheap reference point for sorting transparent Meshes back to front. Dense areas pull the centroid towards them,
+    /// Use the center of [`Mesh::aabb`] for the geometric center
+    #[must_use]
+    pub fn centroid(&self) -> Option<[f32; 3]> {
+        centroid(self.vertices.iter().map(|v| v.position))
+    }
+}
+
+impl Model3D {
+    /// Axis aligned bounding box of the Vertex positions of all Meshes, See [`Mesh::aabb`]
+    ///
+    /// Mesh positions are used as they are, Node transforms are not applied
+    #[must_use]
+    pub fn aabb(&self) -> Option<([f32; 3], [f32; 3])> {
+        aabb(
+            self.meshes
+                .iter()
+                .filter_map(Mesh::aabb)
+                .flat_map(<[[f32; 3]; 2]>::from),
+        )
+    }
+
+    /// Bounding sphere of the Vertex positions of all Meshes, See [`Mesh::bounding_sphere`]
+    ///
+    /// Mesh positions are used as they are, Node transforms are not applied
+    #[must_use]
+    pub fn bounding_sphere(&self) -> Option<([f32; 3], f32)> {
+        ritter(
+            self.meshes
+                .iter()
+                .flat_map(|mesh| mesh.vertices.iter().map(|v| v.position)),
+        )
+    }
+
+    /// Average of the Vertex positions of all Meshes, See [`Mesh::centroid`]
+    ///
+    /// Every Vertex has the same weight, So Meshes with more Vertices pull the centroid towards them. Node transforms are not applied
+    #[must_use]
+    pub fn centroid(&self) -> Option<[f32; 3]> {
+        centroid(
+            self.meshes
+                .iter()
+                .flat_map(|mesh| mesh.vertices.iter().map(|v| v.position)),
+        )
+    }
+}
+
+fn centroid(points: impl Iterator<Item = [f32; 3]>) -> Option<[f32; 3]> {
+    // Summed in f64, So large Meshes do not lose precision
+    let (sum, count) = points.fold(([0.0_f64; 3], 0_u32), |(sum, count), p| {
+        (std::array::from_fn(|k| sum[k] + f64::from(p[k])), count + 1)
+    });
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "The average of f32 positions fits into f32"
+    )]
+    (count > 0).then(|| sum.map(|value| (value / f64::from(count)) as f32))
+}
+
+fn aabb(mut points: impl Iterator<Item = [f32; 3]>) -> Option<([f32; 3], [f32; 3])> {
+    let first = points.next()?;
+    Some(points.fold((first, first), |(min, max), p| {
+        (
+            std::array::from_fn(|k| min[k].min(p[k])),
+            std::array::from_fn(|k| max[k].max(p[k])),
+        )
+    }))
+}
+
+fn ritter(points: impl Iterator<Item = [f32; 3]> + Clone) -> Option<([f32; 3], f32)> {
+    let farthest_from = |from: [f32; 3]| {
+        points
+            .clone()
+            .max_by(|a, b| distance(*a, from).total_cmp(&distance(*b, from)))
+    };
+    let x = points.clone().next()?;
+    let y = farthest_from(x)?;
+    let z = farthest_from(y)?;
+
+    let mut center = math::scale(math::add(y, z), 0.5);
+    let mut radius = distance(y, z) * 0.5;
+    for point in points {
+        let d = distance(point, center);
+        if d > radius {
+            // Grow the sphere just enough to touch the point on the far side
+            let new_radius = (radius + d) * 0.5;
+            center = math::add(
+                center,
+                math::scale(math::sub(point, center), (new_radius - radius) / d),
+            );
+            radius = new_radius;
+        }
+    }
+    Some((center, radius))
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    math::length(math::sub(a, b))
+}
@@ -0,0 +1,217 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+    math,
+    query::{closest_point_on_triangle, ray_triangle},
+    ClosestPoint, Mesh, RayHit,
+};
+
+/// Maximum number of Triangles in a leaf node
+const LEAF_SIZE: usize = 4;
+
+/// Bounding volume hierarchy over the Triangles of a [`Mesh`], Built with [`Mesh::build_bvh`]
+///
+/// The BVH borrows the Mesh instead of copying its Triangles. This keeps the memory overhead at the tree itself
+/// and lets queries report interpolated attributes, but the Mesh can not be modified while the BVH is alive.
+/// After modifying a Mesh the BVH has to be rebuilt
+pub struct MeshBvh<'a> {
+    mesh: &'a Mesh,
+    /// Resolved Vertex indices of every Triangle, Together with its index in [`Mesh::triangles`]
+    triangles: Vec<(usize, [usize; 3])>,
+    nodes: Vec<Node>,
+    triangle_tests: AtomicUsize,
+}
+
+struct Node {
+    min: [f32; 3],
+    max: [f32; 3],
+    kind: NodeKind,
+}
+
+enum NodeKind {
+    /// Range into `MeshBvh::triangles`
+    Leaf {
+        start: usize,
+        end: usize,
+    },
+    Inner {
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Mesh {
+    /// Builds a [`MeshBvh`] to speed up repeated [`MeshBvh::raycast`] and [`MeshBvh::closest_point`] queries
+    #[must_use]
+    pub fn build_bvh(&self) -> MeshBvh<'_> {
+        let mut bvh = MeshBvh {
+            mesh: self,
+            triangles: self.triangles().enumerate().collect(),
+            nodes: Vec::new(),
+            triangle_tests: AtomicUsize::new(0),
+        };
+        if !bvh.triangles.is_empty() {
+            bvh.build(0, bvh.triangles.len());
+        }
+        bvh
+    }
+}
+
+impl MeshBvh<'_> {
+    /// Same as [`Mesh::raycast`] but only tests Triangles whose bounds are hit by the ray
+    #[must_use]
+    pub fn raycast(&self, origin: [f32; 3], dir: [f32; 3]) -> Option<RayHit> {
+        let dir = math::normalize(dir);
+        let inv_dir = dir.map(|d| 1.0 / d);
+        let mut nearest: Option<(usize, f32, [f32; 3])> = None;
+        let mut stack = self.root();
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            let Some(entry) = ray_aabb(origin, inv_dir, node.min, node.max) else {
+                continue;
+            };
+            if nearest.is_some_and(|(_, t, _)| entry > t) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for i in start..end {
+                        let triangle = self.positions(i);
+                        self.triangle_tests.fetch_add(1, Ordering::Relaxed);
+                        if let Some((t, barycentric)) = ray_triangle(origin, dir, &triangle) {
+                            if nearest.is_none_or(|(_, nearest_t, _)| t < nearest_t) {
+                                nearest = Some((i, t, barycentric));
+                            }
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => stack.extend([left, right]),
+            }
+        }
+        nearest.map(|(i, t, barycentric)| {
+            self.mesh
+                .ray_hit(origin, dir, self.triangles[i], t, barycentric)
+        })
+    }
+
+    /// Same as [`Mesh::closest_point`] but skips Triangles whose bounds are further away than the current best
+    #[must_use]
+    pub fn closest_point(&self, point: [f32; 3]) -> Option<ClosestPoint> {
+        let mut nearest: Option<ClosestPoint> = None;
+        let mut stack = self.root();
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if nearest
+                .is_some_and(|nearest| aabb_distance(point, node.min, node.max) > nearest.distance)
+            {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf { start, end } => {
+                    for i in start..end {
+                        self.triangle_tests.fetch_add(1, Ordering::Relaxed);
+                        let candidate = closest_point_on_triangle(point, &self.positions(i));
+                        let distance = math::length(math::sub(point, candidate));
+                        if nearest.is_none_or(|nearest| distance < nearest.distance) {
+                            nearest = Some(ClosestPoint {
+                                point: candidate,
+                                distance,
+                                triangle: self.triangles[i].0,
+                            });
+                        }
+                    }
+                }
+                NodeKind::Inner { left, right } => stack.extend([left, right]),
+            }
+        }
+        nearest
+    }
+
+    /// Total number of Triangle tests done by all queries so far, Useful for profiling
+    #[must_use]
+    pub fn triangle_tests(&self) -> usize {
+        self.triangle_tests.load(Ordering::Relaxed)
+    }
+
+    fn root(&self) -> Vec<usize> {
+        if self.nodes.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        }
+    }
+
+    fn positions(&self, i: usize) -> [[f32; 3]; 3] {
+        self.triangles[i]
+            .1
+            .map(|corner| self.mesh.vertices[corner].position)
+    }
+
+    fn centroid(&self, i: usize) -> [f32; 3] {
+        let [a, b, c] = self.positions(i);
+        math::scale(math::add(math::add(a, b), c), 1.0 / 3.0)
+    }
+
+    /// Builds the node for the Given Triangle range and returns its index
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let (min, max) = (start..end).flat_map(|i| self.positions(i)).fold(
+            ([f32::MAX; 3], [f32::MIN; 3]),
+            |(min, max), p| {
+                (
+                    std::array::from_fn(|k| min[k].min(p[k])),
+                    std::array::from_fn(|k| max[k].max(p[k])),
+                )
+            },
+        );
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            min,
+            max,
+            kind: NodeKind::Leaf { start, end },
+        });
+        if end - start <= LEAF_SIZE {
+            return index;
+        }
+
+        // Median split along the longest axis
+        let extent = math::sub(max, min);
+        let axis = (0..3)
+            .max_by(|&a, &b| extent[a].total_cmp(&extent[b]))
+            .unwrap_or(0);
+        let mut range = self.triangles[start..end]
+            .iter()
+            .enumerate()
+            .map(|(i, triangle)| (self.centroid(start + i)[axis], *triangle))
+            .collect::<Vec<_>>();
+        range.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for (i, (_, triangle)) in range.into_iter().enumerate() {
+            self.triangles[start + i] = triangle;
+        }
+
+        let middle = start + (end - start) / 2;
+        let left = self.build(start, middle);
+        let right = self.build(middle, end);
+        self.nodes[index].kind = NodeKind::Inner { left, right };
+        index
+    }
+}
+
+/// Slab test, Returns the ray parameter where the ray enters the box
+fn ray_aabb(origin: [f32; 3], inv_dir: [f32; 3], min: [f32; 3], max: [f32; 3]) -> Option<f32> {
+    let mut t_min = 0.0_f32;
+    let mut t_max = f32::INFINITY;
+    for axis in 0..3 {
+        let t1 = (min[axis] - origin[axis]) * inv_dir[axis];
+        let t2 = (max[axis] - origin[axis]) * inv_dir[axis];
+        // NaN (origin on a slab with a zero direction) is ignored by min/max
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+    }
+    (t_min <= t_max).then_some(t_min)
+}
+
+fn aabb_distance(point: [f32; 3], min: [f32; 3], max: [f32; 3]) -> f32 {
+    let outside: [f32; 3] =
+        std::array::from_fn(|k| (min[k] - point[k]).max(point[k] - max[k]).max(0.0));
+    math::length(outside)
+}
@@ -0,0 +1,118 @@
+use crate::ModelFormat;
+
+/// What a [`ModelFormat`] can store, See [`ModelFormat::capabilities`]
+///
+/// These describe the File format itself. Some data (e.g. animations) is not loaded by modelz yet
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "Every capability is an independent yes/no fact"
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormatCapabilities {
+    /// Surface Materials like a base color
+    pub supports_materials: bool,
+    /// Image Textures referenced by Materials
+    pub supports_textures: bool,
+    /// More than one Mesh per File
+    pub supports_multiple_meshes: bool,
+    /// Normals, Per Vertex or per face
+    pub supports_normals: bool,
+    /// Texture coordinates
+    pub supports_tex_coords: bool,
+    /// Per Vertex colors
+    pub supports_vertex_colors: bool,
+    /// Animations of nodes or Morph Target weights
+    pub supports_animation: bool,
+}
+
+impl ModelFormat {
+    /// Returns what the Format can store, Useful to decide on a Format before loading or exporting
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modelz::ModelFormat;
+    ///
+    /// assert!(!ModelFormat::STL.capabilities().supports_materials);
+    /// ```
+    #[must_use]
+    pub const fn capabilities(&self) -> FormatCapabilities {
+        match self {
+            #[cfg(feature = "obj")]
+            Self::OBJ => FormatCapabilities {
+                supports_materials: true,
+                supports_textures: true,
+                supports_multiple_meshes: true,
+                supports_normals: true,
+                supports_tex_coords: true,
+                // Vertex colors are a common extension after the position
+                supports_vertex_colors: true,
+                supports_animation: false,
+            },
+            #[cfg(feature = "gltf")]
+            Self::GLTF => FormatCapabilities {
+                supports_materials: true,
+                supports_textures: true,
+                supports_multiple_meshes: true,
+                supports_normals: true,
+                supports_tex_coords: true,
+                supports_vertex_colors: true,
+                supports_animation: true,
+            },
+            #[cfg(feature = "stl")]
+            Self::STL => FormatCapabilities {
+                supports_materials: false,
+                supports_textures: false,
+                supports_multiple_meshes: false,
+                // Every Triangle has a face normal
+                supports_normals: true,
+                supports_tex_coords: false,
+                supports_vertex_colors: false,
+                supports_animation: false,
+            },
+            #[cfg(feature = "ply")]
+            Self::PLY => FormatCapabilities {
+                // Only a `material_index`, The Materials themselves are not stored
+                supports_materials: false,
+                supports_textures: false,
+                // The loader splits faces into one Mesh per `material_index`
+                supports_multiple_meshes: true,
+                supports_normals: true,
+                supports_tex_coords: true,
+                supports_vertex_colors: true,
+                supports_animation: false,
+            },
+            #[cfg(feature = "dxf")]
+            Self::DXF => FormatCapabilities {
+                supports_materials: false,
+                supports_textures: false,
+                // One Mesh per layer
+                supports_multiple_meshes: true,
+                supports_normals: false,
+                supports_tex_coords: false,
+                supports_vertex_colors: false,
+                supports_animation: false,
+            },
+            #[cfg(feature = "x3d")]
+            Self::X3D => FormatCapabilities {
+                supports_materials: true,
+                supports_textures: true,
+                supports_multiple_meshes: true,
+                supports_normals: true,
+                supports_tex_coords: true,
+                supports_vertex_colors: true,
+                supports_animation: true,
+            },
+            #[cfg(feature = "usd")]
+            Self::USD => FormatCapabilities {
+                supports_materials: true,
+                supports_textures: true,
+                supports_multiple_meshes: true,
+                supports_normals: true,
+                supports_tex_coords: true,
+                supports_vertex_colors: true,
+                supports_animation: true,
+            },
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{Material, Vertex};
+
+impl Vertex {
+    /// The Vertex color quantized to 8 bit per channel, Values are clamped to 0.0..=1.0 first
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modelz::Vertex;
+    ///
+    /// let mut vertex = Vertex::default();
+    /// vertex.color = Some([1.0, 0.5, 0.0, 1.0]);
+    /// assert_eq!(vertex.color_u8(), Some([255, 128, 0, 255]));
+    /// ```
+    #[must_use]
+    pub fn color_u8(&self) -> Option<[u8; 4]> {
+        self.color.map(to_u8)
+    }
+
+    /// Sets the Vertex color from 8 bit channels, 255 becomes 1.0
+    pub fn set_color_u8(&mut self, color: [u8; 4]) {
+        self.color = Some(from_u8(color));
+    }
+}
+
+impl Material {
+    /// The base color quantized to 8 bit per channel, Values are clamped to 0.0..=1.0 first
+    #[must_use]
+    pub fn base_color_u8(&self) -> Option<[u8; 4]> {
+        self.base_color.map(to_u8)
+    }
+
+    /// Sets the base color from 8 bit channels, 255 becomes 1.0
+    pub fn set_base_color_u8(&mut self, color: [u8; 4]) {
+        self.base_color = Some(from_u8(color));
+    }
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "The channels are clamped to 0.0..=255.0"
+)]
+fn to_u8(color: [f32; 4]) -> [u8; 4] {
+    color.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+fn from_u8(color: [u8; 4]) -> [f32; 4] {
+    color.map(|channel| f32::from(channel) / 255.0)
+}
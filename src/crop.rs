@@ -0,0 +1,186 @@
+use crate::{math, Indices, Mesh, RenderMode, Vertex};
+
+/// How [`Mesh::crop_to_aabb_with`] treats Triangles which cross the box boundary
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CropMode {
+    /// Keep crossing Triangles as they are, Only Triangles fully outside are removed
+    #[default]
+    Keep,
+    /// Cut crossing Triangles at the box faces, New Vertices get interpolated attributes
+    Clip,
+}
+
+/// A polygon corner while clipping, Stored as barycentric weights of the source Triangle
+#[derive(Clone, Copy)]
+struct Corner {
+    /// Which Triangle corner this is, `None` for points created by clipping
+    source: Option<usize>,
+    barycentric: [f32; 3],
+    position: [f32; 3],
+}
+
+impl Mesh {
+    /// Removes all Triangles which lie fully outside the box, Same as `crop_to_aabb_with(min, max, CropMode::Keep)`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let mut model = Model3D::load("scan.ply").expect("Failed to load");
+    /// model.meshes[0].crop_to_aabb([0.0, 0.0, 0.0], [10.0, 10.0, 10.0]);
+    /// ```
+    pub fn crop_to_aabb(&mut self, min: [f32; 3], max: [f32; 3]) {
+        self.crop_to_aabb_with(min, max, CropMode::Keep);
+    }
+
+    /// Removes all Triangles which lie fully outside the box, Triangles crossing the box boundary are handled by `mode`
+    ///
+    /// The Mesh is converted into an indexed `Triangles` list and unused Vertices are removed afterwards.
+    /// Meshes which are not made of Triangles stay untouched
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    pub fn crop_to_aabb_with(&mut self, min: [f32; 3], max: [f32; 3], mode: CropMode) {
+        if !self.is_triangle_mesh() {
+            return;
+        }
+        // Collected up front, Clipping adds Vertices while iterating
+        let triangles = self.triangles().collect::<Vec<_>>();
+        let mut indices = Vec::new();
+        for corners in triangles {
+            let positions = corners.map(|i| self.vertices[i].position);
+            let inside = |p: [f32; 3]| (0..3).all(|k| min[k] <= p[k] && p[k] <= max[k]);
+            let disjoint = (0..3).any(|k| {
+                positions.iter().all(|p| p[k] < min[k]) || positions.iter().all(|p| p[k] > max[k])
+            });
+            if disjoint {
+                continue;
+            }
+            if mode == CropMode::Keep || positions.into_iter().all(inside) {
+                indices.extend(corners);
+                continue;
+            }
+
+            let polygon = clip_triangle(positions, min, max);
+            let polygon = polygon
+                .iter()
+                .map(|corner| {
+                    corner.source.map_or_else(
+                        || self.blend_vertex(corners, corner.barycentric),
+                        |k| corners[k],
+                    )
+                })
+                .collect::<Vec<_>>();
+            // The clipped polygon is convex, So a fan keeps the winding order
+            for i in 1..polygon.len().saturating_sub(1) {
+                indices.extend([polygon[0], polygon[i], polygon[i + 1]]);
+            }
+        }
+
+        let indices = indices
+            .into_iter()
+            .map(|i| u32::try_from(i).expect("Vertex index does not fit into u32"))
+            .collect();
+        self.indices = Some(Indices::U32(indices));
+        self.mode = RenderMode::Triangles;
+        self.remove_unused_vertices();
+    }
+
+    /// Adds a new Vertex at the Given barycentric weights of a Triangle and returns its index
+    fn blend_vertex(&mut self, corners: [usize; 3], barycentric: [f32; 3]) -> usize {
+        let [a, b, c] = corners.map(|i| &self.vertices[i]);
+        let tangent = match [a.tangent, b.tangent, c.tangent] {
+            [Some(ta), Some(tb), Some(tc)] => {
+                let xyz = math::normalize(math::interpolate(
+                    [ta, tb, tc].map(|t| [t[0], t[1], t[2]]),
+                    barycentric,
+                ));
+                Some([xyz[0], xyz[1], xyz[2], ta[3]])
+            }
+            _ => None,
+        };
+        let vertex = Vertex {
+            position: math::interpolate([a.position, b.position, c.position], barycentric),
+            color: blend([a.color, b.color, c.color], barycentric),
+            tex_coord: blend([a.tex_coord, b.tex_coord, c.tex_coord], barycentric),
+            normal: blend([a.normal, b.normal, c.normal], barycentric).map(math::normalize),
+            tangent,
+        };
+        self.vertices.push(vertex);
+        self.original_indices = None;
+        for target in &mut self.morph_targets {
+            for values in [
+                &mut target.positions,
+                &mut target.normals,
+                &mut target.tangents,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let displacement = math::interpolate(corners.map(|i| values[i]), barycentric);
+                values.push(displacement);
+            }
+        }
+        self.vertices.len() - 1
+    }
+}
+
+/// Interpolates an optional attribute, `None` if any corner is missing it
+fn blend<const N: usize>(values: [Option<[f32; N]>; 3], barycentric: [f32; 3]) -> Option<[f32; N]> {
+    match values {
+        [Some(a), Some(b), Some(c)] => Some(math::interpolate([a, b, c], barycentric)),
+        _ => None,
+    }
+}
+
+/// Sutherland–Hodgman clipping of a Triangle against all six box faces
+fn clip_triangle(positions: [[f32; 3]; 3], min: [f32; 3], max: [f32; 3]) -> Vec<Corner> {
+    let mut polygon = vec![
+        Corner {
+            source: Some(0),
+            barycentric: [1.0, 0.0, 0.0],
+            position: positions[0],
+        },
+        Corner {
+            source: Some(1),
+            barycentric: [0.0, 1.0, 0.0],
+            position: positions[1],
+        },
+        Corner {
+            source: Some(2),
+            barycentric: [0.0, 0.0, 1.0],
+            position: positions[2],
+        },
+    ];
+    for axis in 0..3 {
+        // Signed distance to the face, Positive is inside
+        polygon = clip_polygon(&polygon, |p| p[axis] - min[axis]);
+        polygon = clip_polygon(&polygon, |p| max[axis] - p[axis]);
+    }
+    polygon
+}
+
+fn clip_polygon(polygon: &[Corner], distance: impl Fn([f32; 3]) -> f32) -> Vec<Corner> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let (d_current, d_next) = (distance(current.position), distance(next.position));
+        if d_current >= 0.0 {
+            clipped.push(current);
+        }
+        if (d_current >= 0.0) != (d_next >= 0.0) {
+            let t = d_current / (d_current - d_next);
+            let lerp = |a: f32, b: f32| (b - a).mul_add(t, a);
+            clipped.push(Corner {
+                source: None,
+                barycentric: std::array::from_fn(|k| {
+                    lerp(current.barycentric[k], next.barycentric[k])
+                }),
+                position: std::array::from_fn(|k| lerp(current.position[k], next.position[k])),
+            });
+        }
+    }
+    clipped
+}
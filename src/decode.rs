@@ -0,0 +1,136 @@
+use std::io::Cursor;
+
+use crate::{Image, MinFilter, ModelError, Texture};
+
+impl Texture {
+    /// Decodes the Image into tightly packed RGBA8 pixels, Returns the pixels, width and height
+    ///
+    /// Grayscale and RGB Images are expanded to RGBA. Images stored as a path are read from disk.
+    /// PNG, JPEG, Radiance HDR (`.hdr`) and EXR (`.exr`) are supported, HDR values above 1.0 are clamped. See [`Texture::to_rgba32f`]
+    /// to keep them. GPU compressed Images (See [`Image::is_gpu_compressed`]) can not be decoded
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.gltf").expect("Failed to load");
+    /// if let Some(texture) = &model.materials[0].diffuse_texture {
+    ///     let (pixels, width, height) = texture.to_rgba8().expect("Failed to decode");
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::MaterialLoad`] if the Image could not be read or decoded
+    pub fn to_rgba8(&self) -> Result<(Vec<u8>, u32, u32), ModelError> {
+        let rgba = self.decode()?.into_rgba8();
+        let (width, height) = rgba.dimensions();
+        Ok((rgba.into_raw(), width, height))
+    }
+
+    /// Decodes the Image into tightly packed RGBA32F pixels, Returns the pixels, width and height
+    ///
+    /// Unlike [`Texture::to_rgba8`] HDR values are kept as they are, Which is needed for environment maps and lightmaps.
+    /// 8 bit Images are converted to 0.0..=1.0, The color space is not converted
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{ColorSpace, Image, Sampler, Texture};
+    ///
+    /// let texture = Texture {
+    ///     image: Image::Path { path: "sky.hdr".into(), mime_type: None },
+    ///     sampler: Sampler::default(),
+    ///     name: None,
+    ///     color_space: ColorSpace::Linear,
+    ///     tex_coord_set: 0,
+    /// };
+    /// let (pixels, width, height) = texture.to_rgba32f().expect("Failed to decode");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::MaterialLoad`] if the Image could not be read or decoded
+    pub fn to_rgba32f(&self) -> Result<(Vec<f32>, u32, u32), ModelError> {
+        let rgba = self.decode()?.into_rgba32f();
+        let (width, height) = rgba.dimensions();
+        Ok((rgba.into_raw(), width, height))
+    }
+
+    /// Decodes the Image into an [`image::DynamicImage`], Keeping its pixel format
+    ///
+    /// The `image` crate is re-exported as `modelz::image`, Use it instead of your own dependency to always get the same version
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modelz::{image::DynamicImage, ColorSpace, Image, Sampler, Texture};
+    ///
+    /// let texture = Texture {
+    ///     image: Image::Path {
+    ///         path: concat!(env!("CARGO_MANIFEST_DIR"), "/tests/checker_256x128.png").into(),
+    ///         mime_type: None,
+    ///     },
+    ///     sampler: Sampler::default(),
+    ///     name: None,
+    ///     color_space: ColorSpace::Srgb,
+    ///     tex_coord_set: 0,
+    /// };
+    /// let image: DynamicImage = texture.decode().expect("Failed to decode");
+    /// assert_eq!((image.width(), image.height()), (256, 128));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::MaterialLoad`] if the Image could not be read or decoded
+    pub fn decode(&self) -> Result<image::DynamicImage, ModelError> {
+        if self.image.is_gpu_compressed() {
+            return Err(ModelError::MaterialLoad(
+                "KTX2 and Basis Universal Images can not be decoded, They have to be transcoded"
+                    .to_string(),
+            ));
+        }
+        match &self.image {
+            Image::Memory { data, mime_type } => mime_type
+                .as_deref()
+                .and_then(image::ImageFormat::from_mime_type)
+                .map_or_else(
+                    || image::load_from_memory(data),
+                    |format| image::load_from_memory_with_format(data, format),
+                ),
+            Image::Path { path, .. } => image::open(path),
+        }
+        .map_err(|e| ModelError::MaterialLoad(format!("Failed to decode Image, {e}")))
+    }
+
+    /// Number of mip levels of a full mip chain, `floor(log2(max(width, height))) + 1`
+    ///
+    /// Only the Image header is read, The pixels are not decoded. Returns 1 if the sampler uses a `MinFilter`
+    /// without mipmaps (`Nearest` or `Linear`), Since the other levels would never be sampled
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::MaterialLoad`] if the Image header could not be read
+    pub fn mip_level_count(&self) -> Result<u32, ModelError> {
+        if matches!(
+            self.sampler.min_filter,
+            Some(MinFilter::Nearest | MinFilter::Linear)
+        ) {
+            return Ok(1);
+        }
+        let dimensions = match &self.image {
+            Image::Memory { data, .. } => image::ImageReader::new(Cursor::new(data))
+                .with_guessed_format()
+                .map_err(image::ImageError::IoError)
+                .and_then(image::ImageReader::into_dimensions),
+            Image::Path { path, .. } => image::ImageReader::open(path)
+                .and_then(image::ImageReader::with_guessed_format)
+                .map_err(image::ImageError::IoError)
+                .and_then(image::ImageReader::into_dimensions),
+        };
+        let (width, height) = dimensions
+            .map_err(|e| ModelError::MaterialLoad(format!("Failed to read Image header, {e}")))?;
+        Ok(width.max(height).max(1).ilog2() + 1)
+    }
+}
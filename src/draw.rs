@@ -0,0 +1,48 @@
+use crate::{Indices, Material, Model3D, RenderMode, Vertex};
+
+/// Everything a single draw (e.g. `vkCmdDrawIndexed`) needs, See [`Model3D::draw_calls`]
+#[derive(Clone, Copy)]
+pub struct DrawCall<'a> {
+    /// Vertices to upload into the vertex buffer
+    pub vertices: &'a [Vertex],
+    /// Indices to upload into the index buffer, `None` for non indexed draws
+    pub indices: Option<&'a Indices>,
+    /// The resolved Material, `None` if the Mesh has no Material or its index is out of range
+    pub material: Option<&'a Material>,
+    /// The primitive topology
+    pub mode: RenderMode,
+    /// World transform as column major 4x4 Matrix, Usually pushed as push constant or instance data
+    pub transform: [f32; 16],
+}
+
+impl Model3D {
+    /// Returns one [`DrawCall`] for every placed Mesh, See [`Model3D::instances`]
+    ///
+    /// Meshes instanced by multiple nodes get one draw call per instance, All of them borrow the same Vertices
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.gltf").expect("Failed to load");
+    /// for draw in model.draw_calls() {
+    ///     // Upload `draw.vertices` and `draw.indices`, Bind `draw.material` and record the draw
+    ///     println!("{} Vertices using {:?}", draw.vertices.len(), draw.mode);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn draw_calls(&self) -> Vec<DrawCall<'_>> {
+        self.instances()
+            .map(|(mesh, transform)| DrawCall {
+                vertices: &mesh.vertices,
+                indices: mesh.indices.as_ref(),
+                material: mesh
+                    .material_index
+                    .and_then(|index| self.materials.get(index)),
+                mode: mesh.mode,
+                transform,
+            })
+            .collect()
+    }
+}
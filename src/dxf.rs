@@ -0,0 +1,203 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::{options::LoadContext, Model3D, ModelError, RenderMode, Vertex};
+
+/// Polyline flag: The Polyline is a polyface Mesh
+const POLYFACE_MESH: i32 = 64;
+/// Vertex flag: The Vertex is a polyface Mesh Vertex (or face record without `POLYFACE_POSITION`)
+const POLYFACE_VERTEX: i32 = 128;
+/// Vertex flag: Together with `POLYFACE_VERTEX` marks a position, Otherwise the Vertex is a face record
+const POLYFACE_POSITION: i32 = 64;
+
+/// A DXF entity, Its type and all group code/value pairs
+struct Entity {
+    kind: String,
+    groups: Vec<(i32, String)>,
+}
+
+impl Entity {
+    fn value(&self, code: i32) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn float(&self, code: i32) -> Result<f32, ModelError> {
+        self.value(code).map_or(Ok(0.0), |value| {
+            value.parse().map_err(|_| {
+                ModelError::ModelParsing(format!("Invalid number {value} for group code {code}"))
+            })
+        })
+    }
+
+    fn int(&self, code: i32) -> i32 {
+        self.value(code).and_then(|v| v.parse().ok()).unwrap_or(0)
+    }
+
+    /// Point stored in the group codes `x`, `x + 10` and `x + 20`
+    fn point(&self, x: i32) -> Result<[f32; 3], ModelError> {
+        Ok([self.float(x)?, self.float(x + 10)?, self.float(x + 20)?])
+    }
+
+    fn layer(&self) -> String {
+        self.value(8).unwrap_or("0").to_string()
+    }
+}
+
+/// Geometry collected for one layer
+#[derive(Default)]
+struct Layer {
+    triangles: Vec<[f32; 3]>,
+    lines: Vec<[f32; 3]>,
+}
+
+/// Loads an ASCII DXF File
+///
+/// `3DFACE` entities and polyface `POLYLINE`s become Triangles, `LINE` entities become Lines.
+/// Every layer becomes its own Mesh named after the layer. Everything else (arcs, text, blocks...) is ignored
+pub fn load(path: &Path, options: &LoadContext) -> Result<Model3D, ModelError> {
+    let content = crate::source::read_to_string(path, options)?;
+    let entities = parse_entities(&content)?;
+
+    let mut layers: BTreeMap<String, Layer> = BTreeMap::new();
+    let mut i = 0;
+    while i < entities.len() {
+        let entity = &entities[i];
+        i += 1;
+        match entity.kind.as_str() {
+            "3DFACE" => {
+                let corners = [
+                    entity.point(10)?,
+                    entity.point(11)?,
+                    entity.point(12)?,
+                    entity.point(13)?,
+                ];
+                let layer = layers.entry(entity.layer()).or_default();
+                layer.triangles.extend([corners[0], corners[1], corners[2]]);
+                // The fourth corner equals the third one for Triangles
+                if corners[3].map(f32::to_bits) != corners[2].map(f32::to_bits) {
+                    layer.triangles.extend([corners[0], corners[2], corners[3]]);
+                }
+            }
+            "LINE" => {
+                let layer = layers.entry(entity.layer()).or_default();
+                layer.lines.extend([entity.point(10)?, entity.point(11)?]);
+            }
+            "POLYLINE" => {
+                let end = entities[i..]
+                    .iter()
+                    .position(|e| e.kind != "VERTEX")
+                    .map_or(entities.len(), |end| i + end);
+                if entity.int(70) & POLYFACE_MESH != 0 {
+                    let triangles = load_polyface(&entities[i..end])?;
+                    let layer = layers.entry(entity.layer()).or_default();
+                    layer.triangles.extend(triangles);
+                }
+                // Skip the Vertices and the SEQEND
+                i = end + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let mut meshes = Vec::new();
+    for (name, layer) in layers {
+        for (positions, mode) in [
+            (layer.triangles, RenderMode::Triangles),
+            (layer.lines, RenderMode::Lines),
+        ] {
+            if positions.is_empty() {
+                continue;
+            }
+            let vertices = positions
+                .into_iter()
+                .map(|position| Vertex {
+                    position,
+                    ..Default::default()
+                })
+                .collect();
+            let mut mesh = crate::Mesh::new(vertices, None, mode);
+            mesh.name = Some(name.clone());
+            meshes.push(mesh);
+        }
+    }
+    Ok(Model3D::new(meshes, vec![], crate::ModelFormat::DXF))
+}
+
+/// Triangulates the face records of a polyface Mesh
+fn load_polyface(vertices: &[Entity]) -> Result<Vec<[f32; 3]>, ModelError> {
+    let mut positions = Vec::new();
+    let mut triangles = Vec::new();
+    for vertex in vertices {
+        let flags = vertex.int(70);
+        if flags & POLYFACE_VERTEX == 0 {
+            continue;
+        }
+        if flags & POLYFACE_POSITION != 0 {
+            positions.push(vertex.point(10)?);
+            continue;
+        }
+        // Face record, 1 based indices which are negative for invisible edges and 0 when unused
+        let corners = [71, 72, 73, 74]
+            .into_iter()
+            .map(|code| vertex.int(code).unsigned_abs() as usize)
+            .take_while(|&index| index != 0)
+            .map(|index| {
+                positions.get(index - 1).copied().ok_or_else(|| {
+                    ModelError::ModelParsing(format!("Polyface index {index} is out of range"))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for k in 1..corners.len().saturating_sub(1) {
+            triangles.extend([corners[0], corners[k], corners[k + 1]]);
+        }
+    }
+    Ok(triangles)
+}
+
+/// Reads all entities of the `ENTITIES` section
+fn parse_entities(content: &str) -> Result<Vec<Entity>, ModelError> {
+    let mut lines = content.lines();
+    let mut groups = Vec::new();
+    while let Some(code) = lines.next() {
+        let code = code.trim();
+        if code.is_empty() {
+            continue;
+        }
+        let code = code.parse::<i32>().map_err(|_| {
+            ModelError::ModelParsing(format!(
+                "Invalid group code {code}, Only ASCII DXF is supported"
+            ))
+        })?;
+        let value = lines
+            .next()
+            .ok_or_else(|| ModelError::ModelParsing("Missing group value".to_string()))?;
+        groups.push((code, value.trim().to_string()));
+    }
+
+    let mut entities = Vec::new();
+    let mut in_entities = false;
+    let mut iter = groups.into_iter().peekable();
+    while let Some((code, value)) = iter.next() {
+        if code != 0 {
+            continue;
+        }
+        match value.as_str() {
+            "SECTION" => in_entities = iter.peek().is_some_and(|(_, name)| name == "ENTITIES"),
+            "ENDSEC" => in_entities = false,
+            _ if in_entities => {
+                let mut entity = Entity {
+                    kind: value,
+                    groups: Vec::new(),
+                };
+                while let Some(group) = iter.next_if(|(code, _)| *code != 0) {
+                    entity.groups.push(group);
+                }
+                entities.push(entity);
+            }
+            _ => {}
+        }
+    }
+    Ok(entities)
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::HashSet,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::Path,
+};
+
+use crate::{Image, Material, Model3D, ModelError, Texture};
+
+impl Model3D {
+    /// Writes every embedded texture ([`Image::Memory`]) into `dir` and replaces it with an [`Image::Path`]
+    ///
+    /// Files are named after the texture, Or a hash of the image data when the texture has no (unique) name.
+    /// The extension is taken from the mime type. Useful to hand texture paths to libraries which can not read from memory
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let mut model = Model3D::load("model.glb").expect("Failed to load");
+    /// model
+    ///     .extract_textures_to_dir("textures".as_ref())
+    ///     .expect("Failed to write textures");
+    /// ```
+    /// # Errors
+    ///
+    /// Returns `ModelError::OpenFile` if `dir` can not be created or a texture can not be written
+    pub fn extract_textures_to_dir(&mut self, dir: &Path) -> Result<(), ModelError> {
+        std::fs::create_dir_all(dir).map_err(|e| ModelError::OpenFile(e.to_string()))?;
+        let mut used = HashSet::new();
+        for material in &mut self.materials {
+            for texture in material.textures_mut().into_iter().flatten() {
+                let Image::Memory { data, mime_type } = &texture.image else {
+                    continue;
+                };
+                let extension = mime_type.as_deref().map_or("bin", mime_extension);
+                let file_name = texture
+                    .name
+                    .as_deref()
+                    .map(|name| format!("{}.{extension}", sanitize(name)))
+                    .filter(|file_name| !used.contains(file_name))
+                    .unwrap_or_else(|| {
+                        let mut hasher = DefaultHasher::new();
+                        data.hash(&mut hasher);
+                        format!("{:016x}.{extension}", hasher.finish())
+                    });
+                let path = dir.join(&file_name);
+                std::fs::write(&path, data).map_err(|e| ModelError::OpenFile(e.to_string()))?;
+                texture.image = Image::Path {
+                    path,
+                    mime_type: mime_type.clone(),
+                };
+                used.insert(file_name);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Material {
+    /// All texture slots of the Material
+    pub(crate) const fn textures(&self) -> [&Option<Texture>; 8] {
+        [
+            &self.diffuse_texture,
+            &self.normal_texture,
+            &self.transmission_texture,
+            &self.clearcoat_texture,
+            &self.clearcoat_normal_texture,
+            &self.specular_glossiness_texture,
+            &self.displacement_texture,
+            &self.reflection_texture,
+        ]
+    }
+
+    /// All texture slots of the Material
+    pub(crate) const fn textures_mut(&mut self) -> [&mut Option<Texture>; 8] {
+        [
+            &mut self.diffuse_texture,
+            &mut self.normal_texture,
+            &mut self.transmission_texture,
+            &mut self.clearcoat_texture,
+            &mut self.clearcoat_normal_texture,
+            &mut self.specular_glossiness_texture,
+            &mut self.displacement_texture,
+            &mut self.reflection_texture,
+        ]
+    }
+}
+
+fn mime_extension(mime_type: &str) -> &str {
+    match mime_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/webp" => "webp",
+        "image/ktx2" => "ktx2",
+        "image/basis" => "basis",
+        "image/vnd.radiance" => "hdr",
+        "image/x-exr" => "exr",
+        _ => "bin",
+    }
+}
+
+/// Keeps texture names from escaping the directory or using characters File systems reject
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,32 @@
+//! Entry point for `cargo fuzz`, See [`fuzz_load`]
+
+use crate::{LoadOptions, Model3D, ModelError};
+
+/// File extension of every Format a tag can select, Tags wrap around so every byte picks one
+const EXTENSIONS: [&str; 10] = [
+    "obj", "gltf", "glb", "stl", "ply", "dxf", "wrl", "x3d", "usda", "usdz",
+];
+
+/// Loads `data` as the Format selected by `format`, Meant as a `cargo fuzz` target
+///
+/// The tag indexes a fixed list of File extensions (modulo its length), So every tag selects a Format. Formats whose
+/// feature is disabled return [`ModelError::FeatureDisabled`]. `data` is loaded from memory through
+/// [`Model3D::from_bytes_with_options`], So no other File (MTL Files, glTF Buffers) is ever read
+///
+/// # Examples
+///
+/// ```ignore
+/// #![no_main]
+/// libfuzzer_sys::fuzz_target!(|input: (u8, &[u8])| {
+///     let _ = modelz::fuzz_load(input.0, input.1);
+/// });
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of the loader
+pub fn fuzz_load(format: u8, data: &[u8]) -> Result<Model3D, ModelError> {
+    let extension = EXTENSIONS[usize::from(format) % EXTENSIONS.len()];
+    let format = crate::format_from_extension(std::path::Path::new(&format!("fuzz.{extension}")))?;
+    Model3D::from_bytes_with_options(data, &format, &LoadOptions::default())
+}
@@ -1,22 +1,37 @@
-use std::{
-    fs::{self},
-    path::Path,
-};
+use std::{collections::HashMap, ops::Range, path::Path};
 
 use gltf::Mesh;
 
-use crate::{Indices, Model3D, ModelError, Vertex};
+use crate::{
+    math, mesh::validate_indices, options::LoadContext, Indices, Model3D, ModelError, Vertex,
+};
 
-pub fn load(path: &Path) -> Result<Model3D, ModelError> {
-    let gltf = gltf::Gltf::from_reader(
-        fs::File::open(path).map_err(|e| ModelError::OpenFile(e.to_string()))?,
-    )
-    .map_err(|e| ModelError::ModelParsing(e.to_string()))?;
+/// Loads a glTF File
+///
+/// The result only depends on the File: Meshes follow `document.meshes()` with one Mesh per primitive in primitive order,
+/// Vertices and Indices keep the accessor order and Materials keep the document order. Instances, Cameras and Lights follow
+/// a depth first walk of the Scene. Loading the same File twice yields identical Models, So they can be diffed.
+///
+/// Every glTF Mesh is loaded exactly once, No matter how many scenes or nodes reference it. Only the default scene is walked,
+/// Files without one use their first scene. Files without any scene have no instances, Cameras and Lights
+pub fn load(path: &Path, options: &LoadContext) -> Result<Model3D, ModelError> {
+    let gltf =
+        gltf::Gltf::from_reader_without_validation(crate::source::open_model(path, options)?)
+            .map_err(|e| ModelError::ModelParsing(e.to_string()))?;
+    check_position_accessors(gltf.document.as_json())?;
+    let gltf = gltf::Gltf {
+        document: gltf::Document::from_json(gltf.document.into_json())
+            .map_err(|e| ModelError::ModelParsing(e.to_string()))?,
+        blob: gltf.blob,
+    };
 
-    let path = path.parent().unwrap_or_else(|| Path::new("./"));
+    let model_dir = options.model_dir(path);
+    let path = model_dir.unwrap_or_else(|| Path::new("./"));
 
-    let buffer_data = gltf::import_buffers(&gltf.document, Some(path), gltf.blob)
-        .expect("Failed to Load glTF Buffers");
+    // Relative buffer URIs (including `../` and `%20` escapes) are resolved against the directory of the File,
+    // Models loaded from memory can only use embedded Buffers
+    let buffer_data = gltf::import_buffers(&gltf.document, model_dir, gltf.blob)
+        .map_err(|e| ModelError::ModelParsing(format!("Failed to load glTF Buffers, {e}")))?;
 
     let mut materials = Vec::new();
     let len = gltf.document.materials().len();
@@ -27,74 +42,415 @@ pub fn load(path: &Path) -> Result<Model3D, ModelError> {
             i + 1,
             len,
         );
-        materials.push(load_material(path, &material, &buffer_data));
+        materials.push(load_material(
+            path,
+            &gltf.document,
+            &material,
+            &buffer_data,
+            options,
+        )?);
     }
 
+    // Without a default scene the first one is shown, Like most viewers do
+    if gltf.document.default_scene().is_none() && gltf.document.scenes().len() > 1 {
+        log::debug!(
+            "No default scene, Using the first of {} scenes",
+            gltf.document.scenes().len()
+        );
+    }
+    let scene = gltf
+        .document
+        .default_scene()
+        .or_else(|| gltf.document.scenes().next());
+
+    #[cfg(feature = "extras")]
+    let hidden = match &scene {
+        Some(scene) if options.respect_hidden_extras => hidden_meshes(scene),
+        _ => std::collections::HashSet::new(),
+    };
+
+    let variants = gltf
+        .document
+        .variants()
+        .map(|variants| {
+            variants
+                .map(|variant| variant.name().to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
     let mut meshes = Vec::new();
+    let mut walk = NodeWalk::default();
+    #[cfg(feature = "extras")]
+    {
+        walk.skip_hidden = options.respect_hidden_extras;
+    }
     for mesh in gltf.document.meshes() {
-        meshes.append(&mut load_mesh(&mesh, &buffer_data));
+        #[cfg(feature = "extras")]
+        if hidden.contains(&mesh.index()) {
+            continue;
+        }
+        if let Some(filter) = &options.mesh_filter {
+            if !mesh
+                .name()
+                .is_some_and(|name| filter.iter().any(|wanted| wanted == name))
+            {
+                continue;
+            }
+        }
+        let start = meshes.len();
+        meshes.append(&mut load_mesh(&mesh, &buffer_data, &variants, options)?);
+        walk.mesh_ranges.insert(mesh.index(), start..meshes.len());
     }
 
-    Ok(Model3D {
-        meshes,
-        materials,
-        format: crate::ModelFormat::GLTF,
-    })
+    if let Some(scene) = scene {
+        for node in scene.nodes() {
+            walk.visit(&node, &math::IDENTITY);
+        }
+    }
+
+    let mut model = Model3D::new(meshes, materials, crate::ModelFormat::GLTF);
+    model.cameras = walk.cameras;
+    model.lights = walk.lights;
+    model.mesh_instances = walk.mesh_instances;
+    model.variants = variants;
+    model.gltf_document = options.retain_source.then_some(gltf.document);
+    Ok(model)
 }
 
 fn load_material<'a>(
     model_dir: &'a Path,
+    document: &'a gltf::Document,
     material: &gltf::Material<'a>,
     buffer_data: &'a [gltf::buffer::Data],
-) -> crate::Material {
+    options: &LoadContext,
+) -> Result<crate::Material, ModelError> {
     let pbr = material.pbr_metallic_roughness();
 
-    let diffuse_texture = pbr.base_color_texture().map(|ref texture| {
-        let texture = texture.texture();
-        match texture.source().source() {
-            gltf::image::Source::View { view, mime_type } => {
-                let parent_buffer_data = &buffer_data[view.buffer().index()].0;
-                let begin = view.offset();
-                let end = begin + view.length();
-                let encoded_image = &parent_buffer_data[begin..end];
-
-                let sampler = texture.sampler();
-                let image = crate::Image::Memory {
-                    data: encoded_image.to_vec(), // idk
-                    mime_type: Some(mime_type.to_string()),
-                };
-                crate::Texture {
-                    image,
-                    sampler: convert_sampler(&sampler),
-                    name: texture.name().map(std::string::ToString::to_string),
-                }
-            }
-            gltf::image::Source::Uri { uri, mime_type } => {
-                let sampler = texture.sampler();
-
-                let image = crate::Image::Path {
-                    path: model_dir.join(uri),
-                    mime_type: mime_type.map(std::string::ToString::to_string),
-                };
-
-                crate::Texture {
-                    image,
-                    sampler: convert_sampler(&sampler),
-                    name: texture.name().map(std::string::ToString::to_string),
-                }
-            }
-        }
-    });
+    let texture = |texture: &gltf::Texture<'a>, tex_coord_set, color_space| {
+        load_texture(
+            model_dir,
+            document,
+            texture,
+            buffer_data,
+            options,
+            color_space,
+        )
+        .map(|texture| crate::Texture {
+            tex_coord_set,
+            ..texture
+        })
+    };
+
+    let json_texture = |extension: Option<&gltf::json::Value>, key: &str, color_space| {
+        json_texture_info(document, extension, key)?
+            .map(|(json_texture, tex_coord_set)| texture(&json_texture, tex_coord_set, color_space))
+            .transpose()
+    };
+
+    // The deprecated specular glossiness workflow replaces metallic roughness, Its diffuse is the base color
+    let spec_gloss = material.extension_value("KHR_materials_pbrSpecularGlossiness");
+    let diffuse_texture = if spec_gloss.is_some() {
+        json_texture(spec_gloss, "diffuseTexture", crate::ColorSpace::Srgb)?
+    } else {
+        pbr.base_color_texture()
+            .map(|info| texture(&info.texture(), info.tex_coord(), crate::ColorSpace::Srgb))
+            .transpose()?
+    };
+    let base_color = spec_gloss.map_or_else(
+        || pbr.base_color_factor(),
+        |spec_gloss| json_color(spec_gloss, "diffuseFactor", 1.0),
+    );
+    let normal_texture = material
+        .normal_texture()
+        .map(|normal| {
+            texture(
+                &normal.texture(),
+                normal.tex_coord(),
+                crate::ColorSpace::Linear,
+            )
+        })
+        .transpose()?;
+    let transmission = material.transmission();
+    let transmission_texture = transmission
+        .as_ref()
+        .and_then(gltf::material::Transmission::transmission_texture)
+        .map(|info| texture(&info.texture(), info.tex_coord(), crate::ColorSpace::Linear))
+        .transpose()?;
+    let volume = material.volume();
+    // The gltf crate has no typed support for clear coat, So the extension is read from the raw JSON
+    let clearcoat = material.extension_value("KHR_materials_clearcoat");
+    // Sheen has no typed support either
+    let sheen = material.extension_value("KHR_materials_sheen");
     let alpha_mode = convert_alpha_mode(material.alpha_mode());
 
-    crate::Material {
+    Ok(crate::Material {
         diffuse_texture,
+        normal_texture,
         alpha_mode,
         double_sided: material.double_sided(),
         name: material.name().map(std::string::ToString::to_string),
-        base_color: Some(pbr.base_color_factor()),
+        base_color: Some(base_color),
         alpha_cutoff: material.alpha_cutoff(),
+        transmission_factor: transmission
+            .as_ref()
+            .map(gltf::material::Transmission::transmission_factor),
+        transmission_texture,
+        thickness_factor: volume
+            .as_ref()
+            .map(gltf::material::Volume::thickness_factor),
+        attenuation_color: volume
+            .as_ref()
+            .map(gltf::material::Volume::attenuation_color),
+        attenuation_distance: volume
+            .as_ref()
+            .map(gltf::material::Volume::attenuation_distance),
+        clearcoat_factor: clearcoat.map(|clearcoat| json_factor(clearcoat, "clearcoatFactor", 0.0)),
+        clearcoat_roughness_factor: clearcoat
+            .map(|clearcoat| json_factor(clearcoat, "clearcoatRoughnessFactor", 0.0)),
+        clearcoat_texture: json_texture(clearcoat, "clearcoatTexture", crate::ColorSpace::Linear)?,
+        clearcoat_normal_texture: json_texture(
+            clearcoat,
+            "clearcoatNormalTexture",
+            crate::ColorSpace::Linear,
+        )?,
+        ior: material.ior(),
+        sheen_color_factor: sheen.map(|sheen| json_color(sheen, "sheenColorFactor", 0.0)),
+        sheen_roughness_factor: sheen.map(|sheen| json_factor(sheen, "sheenRoughnessFactor", 0.0)),
+        specular_factor: spec_gloss.map(|spec_gloss| json_color(spec_gloss, "specularFactor", 1.0)),
+        glossiness_factor: spec_gloss
+            .map(|spec_gloss| json_factor(spec_gloss, "glossinessFactor", 1.0)),
+        // The RGB specular color is sRGB, The glossiness in alpha is linear
+        specular_glossiness_texture: json_texture(
+            spec_gloss,
+            "specularGlossinessTexture",
+            crate::ColorSpace::Srgb,
+        )?,
+        displacement_texture: None,
+        reflection_texture: None,
+        #[cfg(feature = "extras")]
+        extras: convert_extras(material.extras()),
+    })
+}
+
+fn load_texture<'a>(
+    model_dir: &'a Path,
+    document: &'a gltf::Document,
+    texture: &gltf::Texture<'a>,
+    buffer_data: &'a [gltf::buffer::Data],
+    options: &LoadContext,
+    color_space: crate::ColorSpace,
+) -> Result<crate::Texture, ModelError> {
+    // `KHR_texture_basisu` stores a KTX2 Image, `source` is an optional fallback. KTX2 can not be decoded,
+    // So the fallback is preferred and the KTX2 Image is only used if there is no fallback
+    let basisu = texture
+        .extension_value("KHR_texture_basisu")
+        .and_then(|basisu| basisu.get("source")?.as_u64())
+        .and_then(|index| document.images().nth(usize::try_from(index).ok()?));
+    let source = texture.source().or(basisu).ok_or_else(|| {
+        ModelError::MaterialLoad(format!("Texture {} has no Image", texture.index()))
+    })?;
+    // The gltf crate panics on Images without data and on embedded Images without a MIME type, So the JSON is read directly
+    let json = &document.as_json().images[source.index()];
+    let mime_type = json
+        .mime_type
+        .as_ref()
+        .map(|mime_type| mime_type.0.as_str());
+    let image = match (&json.buffer_view, &json.uri) {
+        (Some(view), _) => {
+            let view = document.views().nth(view.value()).ok_or_else(|| {
+                ModelError::ModelParsing(format!(
+                    "Image bufferView {} does not exist",
+                    view.value()
+                ))
+            })?;
+            options.check_texture_bytes(view.length())?;
+            let parent_buffer_data = &buffer_data[view.buffer().index()].0;
+            let begin = view.offset();
+            let encoded_image = begin
+                .checked_add(view.length())
+                .and_then(|end| parent_buffer_data.get(begin..end))
+                .ok_or_else(|| {
+                    ModelError::ModelParsing(format!(
+                        "Image bufferView {} runs past the end of its Buffer",
+                        view.index()
+                    ))
+                })?;
+
+            let mime_type = match mime_type {
+                Some(mime_type) if !mime_type.is_empty() => Some(mime_type.to_string()),
+                _ => crate::sniff::sniff_mime_type(encoded_image),
+            };
+            crate::Image::Memory {
+                data: encoded_image.to_vec(), // idk
+                mime_type,
+            }
+        }
+        (None, Some(uri)) => {
+            let uri = decode_uri(uri);
+            options
+                .resolve_texture(&uri)?
+                .unwrap_or_else(|| crate::Image::Path {
+                    path: model_dir.join(&uri),
+                    mime_type: mime_type
+                        .map(std::string::ToString::to_string)
+                        .or_else(|| extension_mime_type(&uri)),
+                })
+        }
+        (None, None) => {
+            return Err(ModelError::ModelParsing(format!(
+                "Image {} has neither a uri nor a bufferView",
+                source.index()
+            )))
+        }
+    };
+    Ok(crate::Texture {
+        image,
+        sampler: convert_sampler(&texture.sampler()),
+        name: texture.name().map(std::string::ToString::to_string),
+        color_space,
+        tex_coord_set: 0,
+    })
+}
+
+/// Decodes `%XX` escapes of a relative URI, e.g. `%20` into a space
+///
+/// The gltf crate decodes buffer URIs itself, Image URIs are resolved here and have to be decoded before they are joined
+/// to the model directory. Invalid escapes are kept as they are
+fn decode_uri(uri: &str) -> String {
+    const fn hex(byte: u8) -> Option<u8> {
+        match byte {
+            b'0'..=b'9' => Some(byte - b'0'),
+            b'a'..=b'f' => Some(byte - b'a' + 10),
+            b'A'..=b'F' => Some(byte - b'A' + 10),
+            _ => None,
+        }
     }
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| Some((hex(*bytes.get(i + 1)?)?, hex(*bytes.get(i + 2)?)?)))
+            .flatten();
+        if let Some((high, low)) = escape {
+            decoded.push(high << 4 | low);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Guesses the mime type of an Image from its URI extension, Used when the glTF does not specify one
+fn extension_mime_type(uri: &str) -> Option<String> {
+    let extension = Path::new(uri).extension()?.to_ascii_lowercase();
+    let mime_type = match extension.to_str()? {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "ktx2" => "image/ktx2",
+        "basis" => "image/basis",
+        "hdr" => "image/vnd.radiance",
+        "exr" => "image/x-exr",
+        _ => return None,
+    };
+    Some(mime_type.to_string())
+}
+
+/// Reads a texture info of an extension which the gltf crate does not support, Returns the Texture and its UV set
+fn json_texture_info<'a>(
+    document: &'a gltf::Document,
+    extension: Option<&gltf::json::Value>,
+    key: &str,
+) -> Result<Option<(gltf::Texture<'a>, u32)>, ModelError> {
+    let info = extension.and_then(|extension| extension.get(key));
+    let Some(index) = info.and_then(|info| info.get("index")?.as_u64()) else {
+        return Ok(None);
+    };
+    let texture = usize::try_from(index)
+        .ok()
+        .and_then(|index| document.textures().nth(index))
+        .ok_or_else(|| ModelError::MaterialLoad(format!("{key} {index} does not exist")))?;
+    let tex_coord_set = info
+        .and_then(|info| info.get("texCoord")?.as_u64())
+        .and_then(|set| u32::try_from(set).ok())
+        .unwrap_or(0);
+    Ok(Some((texture, tex_coord_set)))
+}
+
+/// Reads a factor of an extension which the gltf crate does not support, Missing factors use the default of the extension
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "glTF factors are single precision"
+)]
+fn json_factor(extension: &gltf::json::Value, key: &str, default: f32) -> f32 {
+    extension
+        .get(key)
+        .and_then(gltf::json::Value::as_f64)
+        .map_or(default, |factor| factor as f32)
+}
+
+/// Reads an RGB or RGBA color of an extension, Missing channels use the default of the extension
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "glTF colors are single precision"
+)]
+fn json_color<const N: usize>(extension: &gltf::json::Value, key: &str, default: f32) -> [f32; N] {
+    let channels = extension.get(key).and_then(gltf::json::Value::as_array);
+    std::array::from_fn(|i| {
+        channels
+            .and_then(|channels| channels.get(i)?.as_f64())
+            .map_or(default, |channel| channel as f32)
+    })
+}
+
+#[cfg(feature = "extras")]
+fn convert_extras(extras: &gltf::json::Extras) -> Option<serde_json::Value> {
+    extras
+        .as_ref()
+        .and_then(|raw| serde_json::from_str(raw.get()).ok())
+}
+
+/// Whether the `extras` of a node contain `"hidden": true`
+#[cfg(feature = "extras")]
+fn is_hidden(node: &gltf::Node<'_>) -> bool {
+    convert_extras(node.extras())
+        .and_then(|extras| extras.get("hidden")?.as_bool())
+        .unwrap_or(false)
+}
+
+/// Returns the glTF Meshes which are only referenced by hidden nodes (or their children) of a Scene
+#[cfg(feature = "extras")]
+fn hidden_meshes(scene: &gltf::Scene<'_>) -> std::collections::HashSet<usize> {
+    fn collect(
+        node: &gltf::Node<'_>,
+        hidden_parent: bool,
+        hidden: &mut std::collections::HashSet<usize>,
+        visible: &mut std::collections::HashSet<usize>,
+    ) {
+        let is_hidden = hidden_parent || is_hidden(node);
+        if let Some(mesh) = node.mesh() {
+            if is_hidden {
+                &mut *hidden
+            } else {
+                &mut *visible
+            }
+            .insert(mesh.index());
+        }
+        for child in node.children() {
+            collect(&child, is_hidden, hidden, visible);
+        }
+    }
+    let mut hidden = std::collections::HashSet::new();
+    let mut visible = std::collections::HashSet::new();
+    for node in scene.nodes() {
+        collect(&node, false, &mut hidden, &mut visible);
+    }
+    hidden.retain(|mesh| !visible.contains(mesh));
+    hidden
 }
 
 fn convert_sampler<'a>(sampler: &'a gltf::texture::Sampler<'a>) -> crate::Sampler {
@@ -155,25 +511,98 @@ const fn convert_mode(mode: gltf::mesh::Mode) -> crate::RenderMode {
     }
 }
 
-// fn load_node(
-//     node: gltf::Node<'_>,
-//     buffer_data: &[gltf::buffer::Data],
-// ) {
-//     if let Some(mesh) = node.mesh() {
-//         load_mesh(
-//             mesh,
-//             buffer_data,
-//         );
-//     }
-//     node.children().enumerate().for_each(|(i, child)| {
-//         load_node(
-//             child,
-//             buffer_data,
-//         )
-//     });
-// }
-
-fn load_mesh(mesh: &Mesh, buffer_data: &[gltf::buffer::Data]) -> Vec<crate::Mesh> {
+/// Walks the node hierarchy of a Scene and collects everything which depends on the world transform
+#[derive(Default)]
+struct NodeWalk {
+    cameras: Vec<crate::Camera>,
+    lights: Vec<crate::Light>,
+    mesh_instances: Vec<crate::MeshInstance>,
+    /// Loaded Meshes (one per primitive) of every glTF Mesh, Filtered Meshes are missing
+    mesh_ranges: HashMap<usize, Range<usize>>,
+    /// Skip nodes marked as hidden and their children, See [`LoadOptions::respect_hidden_extras`]
+    #[cfg(feature = "extras")]
+    skip_hidden: bool,
+}
+
+impl NodeWalk {
+    fn visit(&mut self, node: &gltf::Node<'_>, parent_transform: &[f32; 16]) {
+        #[cfg(feature = "extras")]
+        if self.skip_hidden && is_hidden(node) {
+            return;
+        }
+        let local = math::from_columns(node.transform().matrix());
+        let transform = math::mat4_mul(parent_transform, &local);
+
+        if let Some(range) = node
+            .mesh()
+            .and_then(|mesh| self.mesh_ranges.get(&mesh.index()))
+        {
+            self.mesh_instances
+                .extend(range.clone().map(|mesh_index| crate::MeshInstance {
+                    mesh_index,
+                    transform,
+                }));
+        }
+        if let Some(camera) = node.camera() {
+            self.cameras.push(crate::Camera {
+                projection: convert_projection(&camera),
+                transform,
+                name: camera.name().map(std::string::ToString::to_string),
+            });
+        }
+        if let Some(light) = node.light() {
+            self.lights.push(crate::Light {
+                kind: convert_light_kind(&light.kind()),
+                color: light.color(),
+                intensity: light.intensity(),
+                range: light.range(),
+                transform,
+                name: light.name().map(std::string::ToString::to_string),
+            });
+        }
+        for child in node.children() {
+            self.visit(&child, &transform);
+        }
+    }
+}
+
+const fn convert_light_kind(kind: &gltf::khr_lights_punctual::Kind) -> crate::LightKind {
+    match kind {
+        gltf::khr_lights_punctual::Kind::Directional => crate::LightKind::Directional,
+        gltf::khr_lights_punctual::Kind::Point => crate::LightKind::Point,
+        gltf::khr_lights_punctual::Kind::Spot {
+            inner_cone_angle,
+            outer_cone_angle,
+        } => crate::LightKind::Spot {
+            inner_cone_angle: *inner_cone_angle,
+            outer_cone_angle: *outer_cone_angle,
+        },
+    }
+}
+
+fn convert_projection(camera: &gltf::Camera<'_>) -> crate::Projection {
+    match camera.projection() {
+        gltf::camera::Projection::Perspective(perspective) => crate::Projection::Perspective {
+            yfov: perspective.yfov(),
+            aspect_ratio: perspective.aspect_ratio(),
+            znear: perspective.znear(),
+            zfar: perspective.zfar(),
+        },
+        gltf::camera::Projection::Orthographic(orthographic) => crate::Projection::Orthographic {
+            xmag: orthographic.xmag(),
+            ymag: orthographic.ymag(),
+            znear: orthographic.znear(),
+            zfar: orthographic.zfar(),
+        },
+    }
+}
+
+fn load_mesh(
+    mesh: &Mesh,
+    buffer_data: &[gltf::buffer::Data],
+    variants: &[String],
+    options: &LoadContext,
+) -> Result<Vec<crate::Mesh>, ModelError> {
     let mut meshes = Vec::new();
     for (i, primitive) in mesh.primitives().enumerate() {
         log::debug!(
@@ -181,38 +610,158 @@ fn load_mesh(mesh: &Mesh, buffer_data: &[gltf::buffer::Data]) -> Vec<crate::Mesh
             i + 1,
             mesh.primitives().len()
         );
-        let (vertices, indices) = load_primitive(buffer_data, &primitive);
+        let (vertices, indices) = load_primitive(buffer_data, &primitive, options)?;
+        if vertices.is_empty() && !options.keep_empty_primitives {
+            log::debug!("         Skipping empty Mesh Primitive {}", i + 1);
+            continue;
+        }
+        let morph_targets = if vertices.is_empty() {
+            Vec::new()
+        } else {
+            load_morph_targets(buffer_data, &primitive)
+        };
+        // Weights default to zero when the mesh does not specify them
+        let morph_weights = mesh
+            .weights()
+            .map_or_else(|| vec![0.0; morph_targets.len()], <[f32]>::to_vec);
         meshes.push(crate::Mesh {
             vertices,
             indices,
             mode: convert_mode(primitive.mode()),
             material_index: primitive.material().index(),
             name: mesh.name().map(std::string::ToString::to_string),
+            morph_targets,
+            morph_weights,
+            bounds: declared_bounds(&primitive),
+            original_indices: None,
+            source_mesh: Some(mesh.index()),
+            material_variants: load_material_variants(&primitive, variants),
+            #[cfg(feature = "extras")]
+            extras: convert_extras(mesh.extras()),
         });
     }
-    meshes
+    Ok(meshes)
+}
+
+/// Checks that the POSITION accessor of every primitive exists
+///
+/// The validation of the `gltf` crate reads the accessor before checking its index, So it would panic instead
+fn check_position_accessors(root: &gltf::json::Root) -> Result<(), ModelError> {
+    let position = gltf::json::validation::Checked::Valid(gltf::json::mesh::Semantic::Positions);
+    let missing = root
+        .meshes
+        .iter()
+        .flat_map(|mesh| &mesh.primitives)
+        .filter_map(|primitive| primitive.attributes.get(&position))
+        .find(|index| index.value() >= root.accessors.len());
+    missing.map_or(Ok(()), |index| {
+        Err(ModelError::ModelParsing(format!(
+            "POSITION accessor {} does not exist",
+            index.value()
+        )))
+    })
+}
+
+/// Reads the `KHR_materials_variants` mappings of a primitive, `None` if it has none
+fn load_material_variants(
+    primitive: &gltf::Primitive,
+    variants: &[String],
+) -> Option<crate::MaterialVariants> {
+    let mut mappings = primitive.mappings().peekable();
+    mappings.peek()?;
+    let mut materials = HashMap::new();
+    for mapping in mappings {
+        let Some(material) = mapping.material().index() else {
+            continue;
+        };
+        // Mappings pointing at unknown variants are ignored
+        let names = mapping
+            .variants()
+            .iter()
+            .filter_map(|&variant| variants.get(usize::try_from(variant).ok()?));
+        for name in names {
+            materials.insert(name.clone(), material);
+        }
+    }
+    Some(crate::MaterialVariants {
+        base: primitive.material().index(),
+        materials,
+    })
+}
+
+/// Reads the bounding box declared by the POSITION accessor, glTF requires `min` and `max` on it
+///
+/// Quantized positions (`KHR_mesh_quantization`) declare their bounds before dequantization, So only float positions are used
+fn declared_bounds(primitive: &gltf::Primitive) -> Option<([f32; 3], [f32; 3])> {
+    let positions = primitive.get(&gltf::Semantic::Positions)?;
+    if positions.count() == 0
+        || positions.data_type() != gltf::accessor::DataType::F32
+        || positions.normalized()
+    {
+        return None;
+    }
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "The bounds were written from f32 positions"
+    )]
+    let read = |value: Option<gltf::json::Value>| -> Option<[f32; 3]> {
+        match value?.as_array()?.as_slice() {
+            [x, y, z] => Some([x.as_f64()? as f32, y.as_f64()? as f32, z.as_f64()? as f32]),
+            _ => None,
+        }
+    };
+    Some((read(positions.min())?, read(positions.max())?))
 }
 
 fn load_primitive<'a>(
     buffer_data: &'a [gltf::buffer::Data],
     primitive: &gltf::Primitive<'a>,
-) -> (Vec<Vertex>, Option<Indices>) {
+    options: &LoadContext,
+) -> Result<(Vec<Vertex>, Option<Indices>), ModelError> {
+    // Check the declared counts before anything gets allocated
+    let positions = primitive.get(&gltf::Semantic::Positions);
+    let vertex_count = positions.as_ref().map_or(0, gltf::Accessor::count);
+    options.check_vertices(vertex_count)?;
+    // The gltf crate can not read empty accessors, And without Vertices there is nothing to read
+    let Some(positions) = positions.filter(|_| vertex_count > 0) else {
+        return Ok((Vec::new(), None));
+    };
+    if let Some(indices) = primitive.indices() {
+        options.check_indices(indices.count())?;
+    }
+
+    // The reader resolves `byteStride`, So interleaved and separate attribute buffers are read the same way.
+    // Every attribute yields one value per Vertex in the same order
     let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
 
+    // Only primitives without Vertices are skipped, Vertices which can not be read are an error
     let mut vertices: Vec<Vertex> = reader
         .read_positions()
-        .unwrap()
+        .ok_or_else(|| {
+            ModelError::ModelParsing(format!(
+                "POSITION accessor {} can not be read",
+                positions.index()
+            ))
+        })?
         .map(|position| Vertex {
             position,
             color: None,
             tex_coord: None,
             normal: None,
+            tangent: None,
         })
         .collect();
 
     if let Some(normal_attribute) = reader.read_normals() {
-        for (normal_index, normal) in normal_attribute.enumerate() {
-            vertices[normal_index].normal = Some(normal);
+        for (vertex, normal) in vertices.iter_mut().zip(normal_attribute) {
+            vertex.normal = Some(normal);
+        }
+    }
+
+    // Authored tangents are kept as they are, Including the handedness in w
+    if let Some(tangent_attribute) = reader.read_tangents() {
+        for (vertex, tangent) in vertices.iter_mut().zip(tangent_attribute) {
+            vertex.tangent = Some(tangent);
         }
     }
 
@@ -220,8 +769,8 @@ fn load_primitive<'a>(
         .read_colors(0)
         .map(gltf::mesh::util::ReadColors::into_rgba_f32)
     {
-        for (color_index, color) in color_attribute.enumerate() {
-            vertices[color_index].color = Some(color);
+        for (vertex, color) in vertices.iter_mut().zip(color_attribute) {
+            vertex.color = Some(color);
         }
     }
 
@@ -229,8 +778,8 @@ fn load_primitive<'a>(
         .read_tex_coords(0)
         .map(gltf::mesh::util::ReadTexCoords::into_f32)
     {
-        for (tex_coord_index, tex_coord) in tex_coord_attribute.enumerate() {
-            vertices[tex_coord_index].tex_coord = Some(tex_coord);
+        for (vertex, tex_coord) in vertices.iter_mut().zip(tex_coord_attribute) {
+            vertex.tex_coord = Some(tex_coord);
         }
     }
 
@@ -239,6 +788,24 @@ fn load_primitive<'a>(
         gltf::mesh::util::ReadIndices::U16(indices) => Indices::U16(indices.collect::<Vec<_>>()),
         gltf::mesh::util::ReadIndices::U32(indices) => Indices::U32(indices.collect::<Vec<_>>()),
     });
+    if let Some(indices) = &indices {
+        validate_indices(vertices.len(), indices)?;
+    }
 
-    (vertices, indices)
+    Ok((vertices, indices))
+}
+
+fn load_morph_targets<'a>(
+    buffer_data: &'a [gltf::buffer::Data],
+    primitive: &gltf::Primitive<'a>,
+) -> Vec<crate::MorphTarget> {
+    let reader = primitive.reader(|buffer| Some(&buffer_data[buffer.index()]));
+    reader
+        .read_morph_targets()
+        .map(|(positions, normals, tangents)| crate::MorphTarget {
+            positions: positions.map(Iterator::collect),
+            normals: normals.map(Iterator::collect),
+            tangents: tangents.map(Iterator::collect),
+        })
+        .collect()
 }
@@ -0,0 +1,50 @@
+use half::f16;
+
+use crate::{Mesh, Vertex};
+
+impl Mesh {
+    /// Packs the positions, normals and texture coordinates into one interleaved buffer of half precision floats
+    ///
+    /// Every Vertex has its position (x, y, z), Followed by its normal (x, y, z) and texture coordinate (u, v).
+    /// Normals and texture coordinates are only included when every Vertex has them, So a Vertex takes 3, 5, 6 or 8 values
+    /// and `packed.len() / vertices.len()` tells which. The buffer can be uploaded as `vk::Format::R16G16B16_SFLOAT`
+    /// (or the matching GL format) attributes as it is.
+    ///
+    /// Halves the size of these attributes for mobile and other bandwidth constrained renderers, At the cost of precision:
+    /// f16 has 11 significant bits, So values keep about 3 decimal digits (a relative error of up to 2^-11).
+    /// Positions suffer the most, Coordinates around 1000 are only accurate to 0.5 units and everything beyond 65504
+    /// becomes infinite. Center and scale large Models into a small range first or keep their positions in f32.
+    /// Normals and texture coordinates in the usual 0..1 or -1..1 ranges are fine.
+    ///
+    /// Values are rounded to the nearest f16 (ties to even)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.gltf").expect("Failed to load");
+    /// let mesh = &model.meshes[0];
+    /// let packed = mesh.to_packed_f16();
+    /// let stride = packed.len() / mesh.vertices.len();
+    /// println!("{} bytes per Vertex", stride * 2);
+    /// ```
+    #[must_use]
+    pub fn to_packed_f16(&self) -> Vec<f16> {
+        let every = |present: fn(&Vertex) -> bool| self.vertices.iter().all(present);
+        let normals = every(|vertex| vertex.normal.is_some());
+        let tex_coords = every(|vertex| vertex.tex_coord.is_some());
+        let stride = 3 + if normals { 3 } else { 0 } + if tex_coords { 2 } else { 0 };
+        let mut packed = Vec::with_capacity(self.vertices.len() * stride);
+        for vertex in &self.vertices {
+            packed.extend(vertex.position.map(f16::from_f32));
+            if normals {
+                packed.extend(vertex.normal.unwrap_or_default().map(f16::from_f32));
+            }
+            if tex_coords {
+                packed.extend(vertex.tex_coord.unwrap_or_default().map(f16::from_f32));
+            }
+        }
+        packed
+    }
+}
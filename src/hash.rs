@@ -0,0 +1,152 @@
+use std::fmt::Write;
+
+use crate::{Indices, Mesh, Model3D};
+
+impl Mesh {
+    /// Returns a stable hash of the geometry, Which can be used as a cache key in asset pipelines
+    ///
+    /// Vertices, Indices, `RenderMode`, the Material index and Morph Targets are hashed, The `name` is not.
+    /// Float bit patterns are hashed directly, So `0.0` and `-0.0` hash differently and NaNs only match the same NaN.
+    /// Index values are hashed independent of their width, A `U16` and a `U32` buffer with the same values hash the same.
+    ///
+    /// The hash is FNV-1a over little endian bytes, It is the same on every platform and between runs but is not cryptographic
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv::new();
+        self.hash_into(&mut hasher);
+        hasher.0
+    }
+
+    fn hash_into(&self, hasher: &mut Fnv) {
+        hasher.write_u32(self.mode as u32);
+        hasher.write_option(self.material_index, |hasher, index| {
+            hasher.write_usize(index);
+        });
+        hasher.write_usize(self.vertices.len());
+        for vertex in &self.vertices {
+            hasher.write_floats(&vertex.position);
+            hasher.write_option(vertex.color, |hasher, color| hasher.write_floats(&color));
+            hasher.write_option(vertex.tex_coord, |hasher, uv| hasher.write_floats(&uv));
+            hasher.write_option(vertex.normal, |hasher, normal| hasher.write_floats(&normal));
+            hasher.write_option(vertex.tangent, |hasher, tangent| {
+                hasher.write_floats(&tangent);
+            });
+        }
+        hasher.write_option(self.indices.as_ref(), |hasher, indices| {
+            hasher.write_usize(indices.len());
+            let values: Box<dyn Iterator<Item = u32>> = match indices {
+                Indices::U8(indices) => Box::new(indices.iter().map(|&i| u32::from(i))),
+                Indices::U16(indices) => Box::new(indices.iter().map(|&i| u32::from(i))),
+                Indices::U32(indices) => Box::new(indices.iter().copied()),
+            };
+            for index in values {
+                hasher.write_u32(index);
+            }
+        });
+        hasher.write_usize(self.morph_targets.len());
+        for target in &self.morph_targets {
+            for displacements in [&target.positions, &target.normals, &target.tangents] {
+                hasher.write_option(displacements.as_ref(), |hasher, displacements| {
+                    hasher.write_usize(displacements.len());
+                    for displacement in displacements {
+                        hasher.write_floats(displacement);
+                    }
+                });
+            }
+        }
+        hasher.write_floats(&self.morph_weights);
+    }
+}
+
+impl Model3D {
+    /// Returns a stable hash of the geometry of all Meshes in order, See [`Mesh::content_hash`]
+    ///
+    /// Materials, Textures and the scene graph are not part of the hash
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv::new();
+        hasher.write_usize(self.meshes.len());
+        for mesh in &self.meshes {
+            mesh.hash_into(&mut hasher);
+        }
+        hasher.0
+    }
+}
+
+impl Model3D {
+    /// Hashes everything a writer could export, So [`Model3D::unmodified_source`] notices any modification
+    ///
+    /// Extends [`Model3D::content_hash`] by the Materials, Mesh names, Cameras, Lights and the scene graph.
+    /// The non geometry data is hashed through its `Debug` output, Which has to stay the same only for the lifetime of the Model
+    pub(crate) fn source_hash(&self) -> u64 {
+        let mut hasher = Fnv(self.content_hash());
+        // Writing into the hasher can not fail
+        for mesh in &self.meshes {
+            let _ = write!(
+                hasher,
+                "{:?}{:?}{:?}{:?}{:?}",
+                mesh.name,
+                mesh.source_mesh,
+                mesh.material_variants,
+                mesh.bounds,
+                mesh.original_indices
+            );
+            #[cfg(feature = "extras")]
+            let _ = write!(hasher, "{:?}", mesh.extras);
+        }
+        let _ = write!(
+            hasher,
+            "{:?}{:?}{:?}{:?}{:?}",
+            self.materials, self.cameras, self.lights, self.mesh_instances, self.variants
+        );
+        hasher.0
+    }
+}
+
+/// 64 bit FNV-1a, `std`'s `DefaultHasher` is not guaranteed to be stable between Rust versions
+struct Fnv(u64);
+
+impl Fnv {
+    const fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        // Always 64 bit, So 32 bit targets produce the same hash
+        self.write(&(value as u64).to_le_bytes());
+    }
+
+    fn write_floats(&mut self, values: &[f32]) {
+        for value in values {
+            self.write_u32(value.to_bits());
+        }
+    }
+
+    fn write_option<T>(&mut self, value: Option<T>, write: impl FnOnce(&mut Self, T)) {
+        match value {
+            Some(value) => {
+                self.write(&[1]);
+                write(self, value);
+            }
+            None => self.write(&[0]),
+        }
+    }
+}
+
+impl std::fmt::Write for Fnv {
+    fn write_str(&mut self, text: &str) -> std::fmt::Result {
+        self.write(text.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{math, Mesh};
+
+/// A neighbor candidate, Ordered by its squared distance
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance)
+    }
+}
+
+/// A kd-tree over Vertex positions, Built with [`Mesh::build_kdtree`]
+///
+/// Queries return indices into the Vertices of the Mesh. Unlike [`crate::MeshBvh`] the tree copies the positions,
+/// So the Mesh can be modified while the tree is alive but the tree does not see the changes.
+/// The tree is stored implicitly, Every range of `order` has its median as the splitting node
+#[derive(Debug, Clone)]
+pub struct VertexKdTree {
+    positions: Vec<[f32; 3]>,
+    /// Vertex indices, Sorted so every range is split at its median
+    order: Vec<usize>,
+}
+
+impl Mesh {
+    /// Builds a [`VertexKdTree`] over the Vertex positions for nearest neighbor queries
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("scan.ply").expect("Failed to load");
+    /// let tree = model.meshes[0].build_kdtree();
+    /// let closest = tree.nearest([0.0, 0.0, 0.0]);
+    /// ```
+    #[must_use]
+    pub fn build_kdtree(&self) -> VertexKdTree {
+        VertexKdTree::new(self.vertices.iter().map(|v| v.position).collect())
+    }
+}
+
+impl VertexKdTree {
+    pub(crate) fn new(positions: Vec<[f32; 3]>) -> Self {
+        let mut order = (0..positions.len()).collect::<Vec<_>>();
+        build(&positions, &mut order, 0);
+        Self { positions, order }
+    }
+
+    /// Returns the Vertex nearest to `point`, None if the Mesh has no Vertices
+    #[must_use]
+    pub fn nearest(&self, point: [f32; 3]) -> Option<usize> {
+        self.k_nearest(point, 1).first().copied()
+    }
+
+    /// Returns the `k` Vertices nearest to `point` sorted by distance, The query point itself is included if it is a Vertex
+    #[must_use]
+    pub fn k_nearest(&self, point: [f32; 3], k: usize) -> Vec<usize> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        let mut limit = f32::INFINITY;
+        self.search(
+            point,
+            (0, self.order.len()),
+            0,
+            &mut limit,
+            &mut |candidate, limit| {
+                heap.push(candidate);
+                if heap.len() > k {
+                    heap.pop();
+                }
+                if heap.len() == k {
+                    *limit = heap.peek().map_or(f32::INFINITY, |worst| worst.distance);
+                }
+            },
+        );
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|candidate| candidate.index)
+            .collect()
+    }
+
+    /// Returns all Vertices within `radius` of `point` sorted by distance
+    #[must_use]
+    pub fn within_radius(&self, point: [f32; 3], radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut limit = radius * radius;
+        self.search(
+            point,
+            (0, self.order.len()),
+            0,
+            &mut limit,
+            &mut |candidate, limit| {
+                if candidate.distance <= *limit {
+                    found.push(candidate);
+                }
+            },
+        );
+        found.sort_unstable();
+        found.into_iter().map(|candidate| candidate.index).collect()
+    }
+
+    /// Returns the number of Vertices in the tree
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true if the tree has no Vertices
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Visits every Vertex which may be closer than `limit` (a squared distance), Which `visit` can lower
+    fn search(
+        &self,
+        point: [f32; 3],
+        (start, end): (usize, usize),
+        depth: usize,
+        limit: &mut f32,
+        visit: &mut impl FnMut(Candidate, &mut f32),
+    ) {
+        if start >= end {
+            return;
+        }
+        let mid = usize::midpoint(start, end);
+        let index = self.order[mid];
+        let offset = math::sub(point, self.positions[index]);
+        let distance = math::dot(offset, offset);
+        visit(Candidate { distance, index }, limit);
+        let diff = offset[depth % 3];
+        let (near, far) = if diff < 0.0 {
+            ((start, mid), (mid + 1, end))
+        } else {
+            ((mid + 1, end), (start, mid))
+        };
+        self.search(point, near, depth + 1, limit, visit);
+        // The other side can only hold closer Vertices if the splitting plane is within the limit
+        if diff * diff <= *limit {
+            self.search(point, far, depth + 1, limit, visit);
+        }
+    }
+}
+
+/// Sorts `order` so every range is split at its median along the axis of its depth
+fn build(positions: &[[f32; 3]], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = order.len() / 2;
+    order.select_nth_unstable_by(mid, |&a, &b| {
+        positions[a][axis].total_cmp(&positions[b][axis])
+    });
+    let (left, right) = order.split_at_mut(mid);
+    build(positions, left, depth + 1);
+    build(positions, &mut right[1..], depth + 1);
+}
@@ -2,21 +2,103 @@
 #![warn(clippy::pedantic)]
 #![warn(clippy::nursery)]
 #![warn(clippy::cargo)]
+#![allow(clippy::multiple_crate_versions)]
 #![expect(clippy::single_call_fn)]
 #![expect(clippy::exhaustive_enums)]
 #![expect(clippy::exhaustive_structs)]
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
+mod adjacency;
+mod attributes;
+mod bounds;
+mod bvh;
+mod capabilities;
+mod color;
+mod crop;
+#[cfg(feature = "image")]
+mod decode;
+mod draw;
+#[cfg(feature = "dxf")]
+mod dxf;
+mod extract;
+#[cfg(feature = "fuzzing")]
+mod fuzz;
 #[cfg(feature = "gltf")]
 mod gltf;
+#[cfg(feature = "half")]
+mod half_precision;
+mod hash;
+mod kdtree;
+#[cfg(feature = "image")]
+mod loader;
+mod math;
+mod memory;
+mod mesh;
+mod normals;
 #[cfg(feature = "obj")]
 mod obj;
-#[cfg(feature = "stl")]
-mod ply;
+mod options;
 #[cfg(feature = "ply")]
+pub mod ply;
+mod pointcloud;
+mod postprocess;
+mod query;
+mod scene;
+mod sniff;
+mod soa;
+mod source;
+mod split;
+#[cfg(feature = "stl")]
 mod stl;
+#[cfg(feature = "usd")]
+mod usd;
+mod uv;
+mod validate;
+mod variants;
+#[cfg(feature = "x3d")]
+mod x3d;
 
+pub use attributes::VertexAttr;
+pub use bvh::MeshBvh;
+pub use capabilities::FormatCapabilities;
+pub use crop::CropMode;
+pub use draw::DrawCall;
+#[cfg(feature = "fuzzing")]
+pub use fuzz::fuzz_load;
+/// The `half` crate of the f16 type, See [`Mesh::to_packed_f16`]
+#[cfg(feature = "half")]
+pub use half;
+/// The `image` crate used to decode Textures, See [`Texture::decode`]
+#[cfg(feature = "image")]
+pub use image;
+pub use kdtree::VertexKdTree;
+#[cfg(feature = "image")]
+pub use loader::{DecodedImage, ModelLoader};
+pub use memory::MemoryUsage;
+#[cfg(feature = "obj")]
+pub use obj::{PolygonMesh, RawObjIndex, RawObjMesh};
+pub use options::{LoadOptions, SharedTextureResolver, TextureResolver, TextureTimeout};
+pub use postprocess::PostProcess;
+pub use query::{ClosestPoint, RayHit};
+pub use scene::{Camera, Light, LightKind, MeshInstance, Projection};
+pub use soa::MeshSoA;
+#[cfg(feature = "stl")]
+pub use stl::StlWinding;
+pub use uv::Axis;
+pub use validate::ValidationWarning;
+pub use variants::MaterialVariants;
+
+/// A loaded 3D Model
+///
+/// # Thread safety
+///
+/// The Model owns all its data (no `Rc` or interior mutability), So it is `Send` and `Sync`.
+/// Models can be loaded on worker threads and shared between threads behind an `Arc`
 pub struct Model3D {
     /// All meshes the Model has.
     ///
@@ -29,19 +111,122 @@ pub struct Model3D {
 
     /// The format which was used to load the Model
     pub format: ModelFormat,
+
+    /// All Cameras placed in the Scene
+    ///
+    /// Only glTF supports Cameras, For all other Formats the Vec will be empty
+    pub cameras: Vec<Camera>,
+
+    /// All Lights placed in the Scene
+    ///
+    /// Only glTF supports Lights (`KHR_lights_punctual`), For all other Formats the Vec will be empty
+    pub lights: Vec<Light>,
+
+    /// All Meshes placed in the Scene by nodes, See [`Model3D::instances`]
+    ///
+    /// Only glTF has a scene graph, For all other Formats the Vec will be empty
+    pub mesh_instances: Vec<MeshInstance>,
+
+    /// Names of all Material variants, See [`Model3D::apply_variant`]
+    ///
+    /// Only glTF supports variants (`KHR_materials_variants`), For all other Formats the Vec will be empty
+    pub variants: Vec<String>,
+
+    /// Problems which did not stop the loading, e.g. Textures whose resolver timed out
+    ///
+    /// The affected data falls back to what the loader would use without the failing feature
+    pub warnings: Vec<ModelError>,
+
+    /// The parsed glTF document, Only kept when [`LoadOptions::retain_source`] is set
+    #[cfg(feature = "gltf")]
+    gltf_document: Option<::gltf::Document>,
+
+    /// The bytes of the loaded File and the `source_hash` right after loading, See [`LoadOptions::retain_source_bytes`]
+    source_bytes: Option<(Arc<[u8]>, u64)>,
 }
 
 impl Model3D {
-    /// Load an Full 3D Model from the Given File extension
+    /// Creates a new Model without any Cameras, Lights and Mesh instances
+    #[must_use]
+    pub const fn new(meshes: Vec<Mesh>, materials: Vec<Material>, format: ModelFormat) -> Self {
+        Self {
+            meshes,
+            materials,
+            format,
+            cameras: Vec::new(),
+            lights: Vec::new(),
+            mesh_instances: Vec::new(),
+            variants: Vec::new(),
+            warnings: Vec::new(),
+            #[cfg(feature = "gltf")]
+            gltf_document: None,
+            source_bytes: None,
+        }
+    }
+
+    /// Returns the bytes of the File the Model was loaded from, As long as its geometry was not modified
+    ///
+    /// Writers can pass these bytes through for a lossless re-export instead of encoding the Model again.
+    /// Only available when the Model was loaded with [`LoadOptions::retain_source_bytes`] set, Otherwise `None`.
+    /// Modifications are detected by hashing the geometry like [`Model3D::content_hash`] together with the Materials,
+    /// Mesh names, Cameras, Lights and the scene graph, Only `warnings` are not compared
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{LoadOptions, Model3D};
+    ///
+    /// let options = LoadOptions {
+    ///     retain_source_bytes: true,
+    ///     ..Default::default()
+    /// };
+    /// let model = Model3D::load_with_options("model.gltf", &options).expect("Failed to load");
+    /// if let Some(bytes) = model.unmodified_source() {
+    ///     std::fs::write("copy.gltf", bytes).expect("Failed to write");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn unmodified_source(&self) -> Option<&[u8]> {
+        self.source_bytes
+            .as_ref()
+            .filter(|(_, hash)| *hash == self.source_hash())
+            .map(|(bytes, _)| &**bytes)
+    }
+
+    /// Returns the parsed glTF document the Model was loaded from, For extensions and data the crate does not model
+    ///
+    /// Only available when the Model was loaded from a glTF File with [`LoadOptions::retain_source`] set, Otherwise `None`.
+    /// The raw JSON is available through `Document::as_json`. Meshes of the document are split into one Mesh per primitive
     ///
     /// # Examples
     ///
+    /// ```no_run
+    /// use modelz::{LoadOptions, Model3D};
+    ///
+    /// let options = LoadOptions {
+    ///     retain_source: true,
+    ///     ..Default::default()
+    /// };
+    /// let model = Model3D::load_with_options("model.gltf", &options).expect("Failed to load");
+    /// let document = model.gltf_document().expect("Not a glTF File");
+    /// println!("Extensions used: {:?}", document.extensions_used().collect::<Vec<_>>());
     /// ```
+    #[cfg(feature = "gltf")]
+    #[must_use]
+    pub const fn gltf_document(&self) -> Option<&::gltf::Document> {
+        self.gltf_document.as_ref()
+    }
+
+    /// Load an Full 3D Model from the Given File extension
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
     /// use modelz::Model3D;
     ///
-    /// let model = Model3D::load("model.gltf");
+    /// let model = Model3D::load("model.gltf").expect("Failed to load");
     ///
-    ///  for mesh in model.meshes {
+    /// for mesh in model.meshes {
     ///     println!("{}", mesh.name.unwrap());
     ///     for vert in mesh.vertices {
     ///        println!("{:?}", vert)
@@ -53,7 +238,43 @@ impl Model3D {
     ///
     /// Returns an Error is loading the Model was unsuccessful
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ModelError> {
+        Self::load_with_options(path, &LoadOptions::default())
+    }
+
+    /// Load an Full 3D Model from the Given File extension using custom `LoadOptions`
+    ///
+    /// # Errors
+    ///
+    /// Returns an Error is loading the Model was unsuccessful
+    pub fn load_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &LoadOptions,
+    ) -> Result<Self, ModelError> {
         let format = get_format(&path)?;
+        Self::from_format_with_options(path, &format, options)
+    }
+
+    /// Load an Full 3D Model, Detecting the Format from the File content
+    ///
+    /// Useful for Files without or with a wrong extension. Looks at the first bytes of the File for
+    /// the glTF/GLB, PLY and STL signatures and for typical OBJ lines. Falls back to the File extension if nothing matches
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load_sniff("download_1234").expect("Failed to load");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an Error is loading the Model was unsuccessful
+    pub fn load_sniff<P: AsRef<Path>>(path: P) -> Result<Self, ModelError> {
+        let format = match sniff::sniff_format(path.as_ref())? {
+            Some(format) => format,
+            None => get_format(&path)?,
+        };
         Self::from_format(path, &format)
     }
 
@@ -61,27 +282,166 @@ impl Model3D {
     ///
     /// # Examples
     ///
-    /// ```
-    /// use modelz::Model3D;
+    /// ```no_run
+    /// use modelz::{Model3D, ModelFormat};
     ///
-    /// let model = Model3D::from_format("model.gltf", ModelFormat::GLTF);
+    /// let model = Model3D::from_format("model.gltf", &ModelFormat::GLTF);
     ///
-    /// let model = Model3D::from_format("model", ModelFormat::GLTF);
+    /// let model = Model3D::from_format("model", &ModelFormat::GLTF);
     /// ```
     /// # Errors
     ///
     /// Returns an Error is loading the Model was unsuccessful
     pub fn from_format<P: AsRef<Path>>(path: P, format: &ModelFormat) -> Result<Self, ModelError> {
-        match format {
+        Self::from_format_with_options(path, format, &LoadOptions::default())
+    }
+
+    /// Load an Full 3D Model from the Given `ModelFormat` using custom `LoadOptions`
+    ///
+    /// # Errors
+    ///
+    /// Returns an Error is loading the Model was unsuccessful
+    pub fn from_format_with_options<P: AsRef<Path>>(
+        path: P,
+        format: &ModelFormat,
+        options: &LoadOptions,
+    ) -> Result<Self, ModelError> {
+        Self::load_context(path.as_ref(), format, options::LoadContext::new(options))
+    }
+
+    /// Load an Full 3D Model of the Given `ModelFormat` from memory
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{Model3D, ModelFormat};
+    ///
+    /// let data = std::fs::read("model.glb").expect("Failed to read");
+    /// let model = Model3D::from_bytes(&data, &ModelFormat::GLTF);
+    /// ```
+    /// # Errors
+    ///
+    /// Returns an Error is loading the Model was unsuccessful
+    pub fn from_bytes(data: &[u8], format: &ModelFormat) -> Result<Self, ModelError> {
+        Self::from_bytes_with_options(data, format, &LoadOptions::default())
+    }
+
+    /// Load an Full 3D Model of the Given `ModelFormat` from memory using custom `LoadOptions`
+    ///
+    /// Other Files are never read, So references to them (MTL Files, external glTF Buffers) fail the load unless `mtl_override`
+    /// names the MTL File. Texture paths are kept relative as [`Image::Path`], Use a `texture_resolver` to load them.
+    /// Gzip compressed data is not decompressed
+    ///
+    /// # Errors
+    ///
+    /// Returns an Error is loading the Model was unsuccessful
+    pub fn from_bytes_with_options(
+        data: &[u8],
+        format: &ModelFormat,
+        options: &LoadOptions,
+    ) -> Result<Self, ModelError> {
+        let context = options::LoadContext::from_bytes(options, data.into());
+        Self::load_context(Path::new(""), format, context)
+    }
+
+    /// Runs the loader of the `ModelFormat` and applies the `LoadOptions` of the context to the result
+    fn load_context(
+        path: &Path,
+        format: &ModelFormat,
+        context: options::LoadContext,
+    ) -> Result<Self, ModelError> {
+        let options = context.options;
+        let model = match format {
             #[cfg(feature = "obj")]
-            ModelFormat::OBJ => obj::load(path.as_ref()),
+            ModelFormat::OBJ => obj::load(path, &context),
             #[cfg(feature = "gltf")]
-            ModelFormat::GLTF => gltf::load(path.as_ref()),
+            ModelFormat::GLTF => gltf::load(path, &context),
             #[cfg(feature = "stl")]
-            ModelFormat::STL => stl::load(path.as_ref()),
+            ModelFormat::STL => stl::load(path, &context),
             #[cfg(feature = "ply")]
-            ModelFormat::PLY => ply::load(path.as_ref()),
+            ModelFormat::PLY => ply::load(path, &context),
+            #[cfg(feature = "dxf")]
+            ModelFormat::DXF => dxf::load(path, &context),
+            #[cfg(feature = "x3d")]
+            ModelFormat::X3D => x3d::load(path, &context),
+            #[cfg(feature = "usd")]
+            ModelFormat::USD => usd::load(path, &context),
+        };
+        let (warnings, source) = context.finish();
+        let mut model = Self { warnings, ..model? };
+        if options.triangulate {
+            for mesh in &mut model.meshes {
+                mesh.triangulate();
+            }
+        }
+        if let Some(epsilon) = options.remove_degenerate_triangles {
+            for (index, mesh) in model.meshes.iter_mut().enumerate() {
+                let removed = mesh.remove_degenerate_triangles(epsilon);
+                if removed > 0 {
+                    model.warnings.push(ModelError::ModelParsing(format!(
+                        "Removed {removed} degenerate Triangles from Mesh {index}"
+                    )));
+                }
+            }
+        }
+        // The File as the loader read it from disk (or the data it was given), Compressed Files stay compressed
+        model.source_bytes = source.map(|bytes| (bytes, model.source_hash()));
+        Ok(model)
+    }
+
+    /// Returns the first Mesh with the Given name
+    ///
+    /// Meshes without a name are skipped. If multiple Meshes share the same name the first match is returned
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.obj").expect("Failed to load");
+    /// let cube = model.mesh_by_name("Cube");
+    /// ```
+    #[must_use]
+    pub fn mesh_by_name(&self, name: &str) -> Option<&Mesh> {
+        self.meshes
+            .iter()
+            .find(|mesh| mesh.name.as_deref() == Some(name))
+    }
+
+    /// Mutable version of [`Model3D::mesh_by_name`]
+    pub fn mesh_by_name_mut(&mut self, name: &str) -> Option<&mut Mesh> {
+        self.meshes
+            .iter_mut()
+            .find(|mesh| mesh.name.as_deref() == Some(name))
+    }
+
+    /// Returns the first Material with the Given name
+    ///
+    /// Materials without a name are skipped. If multiple Materials share the same name the first match is returned
+    #[must_use]
+    pub fn material_by_name(&self, name: &str) -> Option<&Material> {
+        self.materials
+            .iter()
+            .find(|material| material.name.as_deref() == Some(name))
+    }
+
+    /// Mutable version of [`Model3D::material_by_name`]
+    pub fn material_by_name_mut(&mut self, name: &str) -> Option<&mut Material> {
+        self.materials
+            .iter_mut()
+            .find(|material| material.name.as_deref() == Some(name))
+    }
+
+    /// Groups all Meshes by their `material_index`, Meshes without a Material are grouped under `None`
+    ///
+    /// Every group keeps the order of `meshes`. Useful to batch draw calls by Material
+    #[must_use]
+    pub fn meshes_by_material(&self) -> HashMap<Option<usize>, Vec<&Mesh>> {
+        let mut groups: HashMap<Option<usize>, Vec<&Mesh>> = HashMap::new();
+        for mesh in &self.meshes {
+            groups.entry(mesh.material_index).or_default().push(mesh);
         }
+        groups
     }
 }
 
@@ -93,6 +453,8 @@ pub enum ModelFormat {
     OBJ,
     #[cfg(feature = "gltf")]
     // gltf 2.0, .gltf | .glb
+    // Interleaved (`byteStride`) and separate vertex attribute buffers are both supported
+    // Loading is deterministic, Meshes, Vertices, Indices and Materials keep the order of the File
     GLTF,
     #[cfg(feature = "stl")]
     // STL .stl
@@ -100,12 +462,23 @@ pub enum ModelFormat {
     #[cfg(feature = "ply")]
     // Polygon File Format .ply
     PLY,
+    #[cfg(feature = "dxf")]
+    // AutoCAD Drawing Exchange Format .dxf, Only ASCII
+    DXF,
+    #[cfg(feature = "x3d")]
+    // VRML 2.0 .wrl and XML encoded X3D .x3d
+    X3D,
+    #[cfg(feature = "usd")]
+    // Universal Scene Description .usda, .usd and .usdz, Only the ASCII encoding
+    // Binary .usdc is not supported, USDZ packages only load when their root layer is USDA.
+    // Most AR packages contain usdc layers, So they fail with `ModelError::ModelParsing`
+    USD,
 }
 
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum ModelError {
-    // Format is not supported for you may have to enable it as a crate feature
+    // Format is not supported, Known Formats which are compiled out return `FeatureDisabled` instead
     UnknowFormat,
     // Given file does not exist
     FileNotExists,
@@ -115,26 +488,81 @@ pub enum ModelError {
     ModelParsing(String),
     // Error loading Material
     MaterialLoad(String),
+    // Mesh can not be converted from one RenderMode into the other
+    UnsupportedTopology { from: RenderMode, to: RenderMode },
+    // Format is known but its loader was compiled out, Enable the named crate feature to load it
+    FeatureDisabled(&'static str),
 }
 
+/// File extensions of every Format together with the crate feature which enables its loader
+const FORMAT_FEATURES: [(&str, &str); 11] = [
+    ("obj", "obj"),
+    ("gltf", "gltf"),
+    ("glb", "gltf"),
+    ("stl", "stl"),
+    ("ply", "ply"),
+    ("dxf", "dxf"),
+    ("wrl", "x3d"),
+    ("x3d", "x3d"),
+    ("usd", "usd"),
+    ("usda", "usd"),
+    ("usdz", "usd"),
+];
+
 fn get_format<P: AsRef<Path>>(path: &P) -> Result<ModelFormat, ModelError> {
     let path = path.as_ref();
     if !path.exists() {
         return Err(ModelError::FileNotExists);
     }
+    format_from_extension(path)
+}
+
+/// Maps the File extension to its `ModelFormat`, Without checking that the File exists
+fn format_from_extension(path: &Path) -> Result<ModelFormat, ModelError> {
+    // `model.obj.gz` is detected by the extension in front of `.gz`
+    #[cfg(feature = "gzip")]
+    let path = if source::is_gzip(path) {
+        Path::new(path.file_stem().unwrap_or_default())
+    } else {
+        path
+    };
 
-    let extension = path
-        .extension()
-        .and_then(|ext| return ext.to_str())
-        .expect("Failed to get File extension");
-    match extension {
-        #[cfg(feature = "obj")]
-        "obj" => Ok(ModelFormat::OBJ),
-        #[cfg(feature = "gltf")]
-        "gltf" | "glb" => Ok(ModelFormat::GLTF),
-        #[cfg(feature = "stl")]
-        "stl" => Ok(ModelFormat::STL),
-        _ => Err(ModelError::UnknowFormat),
+    // Compare as OsStr so non UTF-8 paths still work
+    let Some(extension) = path.extension() else {
+        return Err(ModelError::UnknowFormat);
+    };
+    #[cfg(feature = "obj")]
+    if extension == "obj" {
+        return Ok(ModelFormat::OBJ);
+    }
+    #[cfg(feature = "gltf")]
+    if extension == "gltf" || extension == "glb" {
+        return Ok(ModelFormat::GLTF);
+    }
+    #[cfg(feature = "stl")]
+    if extension == "stl" {
+        return Ok(ModelFormat::STL);
+    }
+    #[cfg(feature = "ply")]
+    if extension == "ply" {
+        return Ok(ModelFormat::PLY);
+    }
+    #[cfg(feature = "dxf")]
+    if extension == "dxf" {
+        return Ok(ModelFormat::DXF);
+    }
+    #[cfg(feature = "x3d")]
+    if extension == "wrl" || extension == "x3d" {
+        return Ok(ModelFormat::X3D);
+    }
+    #[cfg(feature = "usd")]
+    if ["usd", "usda", "usdz"].iter().any(|e| extension == *e) {
+        return Ok(ModelFormat::USD);
+    }
+    // Every enabled Format returned above, So a match here names a disabled crate feature
+    match FORMAT_FEATURES.iter().find(|(e, _)| extension == *e) {
+        Some((_, feature)) => Err(ModelError::FeatureDisabled(feature)),
+        None => Err(ModelError::UnknowFormat),
     }
 }
 
@@ -149,20 +577,91 @@ pub struct Mesh {
     ///
     /// # Examples
     ///
-    /// ```
-    /// let material = model.materials[mesh.material_index];
+    /// ```ignore
+    /// let material = &model.materials[mesh.material_index.unwrap()];
     /// ```
     pub material_index: Option<usize>,
     /// Name of the Mesh.
     ///
     /// Some File Formats do not support Mesh names, In this case this will be `None`
     pub name: Option<String>,
+    /// Morph Targets (Blend shapes) of the Mesh
+    ///
+    /// Only glTF supports Morph Targets, For all other Formats the Vec will be empty
+    pub morph_targets: Vec<MorphTarget>,
+    /// Default weights of the Morph Targets, Has one weight for every Morph Target
+    pub morph_weights: Vec<f32>,
+    /// Precomputed bounding box as `(min, max)`, Used by [`Mesh::aabb`] instead of scanning the Vertices
+    ///
+    /// Only glTF declares bounds (the `min` and `max` of the POSITION accessor), For all other Formats this will be `None`.
+    /// Methods of this crate which move, add or remove Vertices reset it to `None`, Do the same when editing `vertices` directly
+    pub bounds: Option<([f32; 3], [f32; 3])>,
+    /// Index of the Vertex in the source File for every Vertex, To correlate Vertices with data keyed by the original Vertex id
+    ///
+    /// Only PLY sets it, Since its loader gives every face corner its own Vertex. Methods of this crate which reorder, duplicate
+    /// or merge Vertices keep the mapping and never merge Vertices of different source Vertices. Vertices blended from several
+    /// sources (voxel downsampling, clipping) have no single source, So these methods reset it to `None`
+    pub original_indices: Option<Vec<u32>>,
+    /// Index of the Mesh in the source File, Shared by all Meshes loaded from the primitives of the same source Mesh
+    ///
+    /// Only glTF sets it, See [`Model3D::merge_primitives_by_material`]. For all other Formats this will be `None`
+    pub source_mesh: Option<usize>,
+    /// The Materials of the Mesh for the variants of the Model, `None` for Meshes which look the same in every variant
+    pub material_variants: Option<MaterialVariants>,
+    /// Custom application specific JSON data
+    ///
+    /// Only glTF supports extras, For all other Formats this will be `None`
+    #[cfg(feature = "extras")]
+    pub extras: Option<serde_json::Value>,
+}
+
+/// A Morph Target (Blend shape) holding per Vertex displacements
+///
+/// Every present attribute has exactly one displacement for every Vertex of the Mesh.
+/// The displaced Vertex is `vertex + sum(weight[i] * target[i])`
+#[derive(Clone, Debug, Default)]
+pub struct MorphTarget {
+    /// Position displacements
+    pub positions: Option<Vec<[f32; 3]>>,
+    /// Normal displacements
+    pub normals: Option<Vec<[f32; 3]>>,
+    /// Tangent displacements, The handedness (w) can not be displaced
+    pub tangents: Option<Vec<[f32; 3]>>,
+}
+
+impl Mesh {
+    /// Creates a new Mesh without a Material and name
+    #[must_use]
+    pub const fn new(vertices: Vec<Vertex>, indices: Option<Indices>, mode: RenderMode) -> Self {
+        Self {
+            vertices,
+            indices,
+            mode,
+            material_index: None,
+            name: None,
+            morph_targets: Vec::new(),
+            morph_weights: Vec::new(),
+            bounds: None,
+            original_indices: None,
+            source_mesh: None,
+            material_variants: None,
+            #[cfg(feature = "extras")]
+            extras: None,
+        }
+    }
 }
 
+/// The surface of a Mesh, See [`Mesh::material_index`]
+///
+/// The properties of the glTF `KHR_materials_*` extensions (transmission, volume, clear coat, ior, sheen and specular glossiness)
+/// are only loaded from glTF, For all other Formats and glTF Materials without the extension they are `None`
+#[derive(Debug)]
 #[non_exhaustive]
 pub struct Material {
     /// The optional diffuse Texture
     pub diffuse_texture: Option<Texture>,
+    /// The optional tangent space normal map
+    pub normal_texture: Option<Texture>,
     /// The alpha rendering mode of the material.  The material's alpha rendering
     /// mode enumeration specifying the interpretation of the alpha value of the main
     /// factor and texture.
@@ -181,16 +680,60 @@ pub struct Material {
     /// Some File Formats do not support Material names, In this case this will be `None`
     /// # Examples
     ///
-    /// ```
+    /// ```glsl
     /// vec4 texture = texture(texture_diffuse, tex_coord) * material.base_color;
     /// ```
     pub base_color: Option<[f32; 4]>,
+    /// How much light is transmitted through the surface, From `KHR_materials_transmission`
+    pub transmission_factor: Option<f32>,
+    /// The optional transmission Texture, Its red channel is multiplied with `transmission_factor`
+    pub transmission_texture: Option<Texture>,
+    /// Thickness of the volume beneath the surface in Mesh space, From `KHR_materials_volume`
+    pub thickness_factor: Option<f32>,
+    /// The color white light turns into when it reaches `attenuation_distance` inside the volume
+    pub attenuation_color: Option<[f32; 3]>,
+    /// Average distance light travels inside the volume before hitting a particle, Infinite means no attenuation
+    pub attenuation_distance: Option<f32>,
+    /// Strength of the clear coat layer on top of the Material, From `KHR_materials_clearcoat`
+    pub clearcoat_factor: Option<f32>,
+    /// Roughness of the clear coat layer
+    pub clearcoat_roughness_factor: Option<f32>,
+    /// The optional clear coat Texture, Its red channel is multiplied with `clearcoat_factor`
+    pub clearcoat_texture: Option<Texture>,
+    /// The optional tangent space normal map of the clear coat layer
+    pub clearcoat_normal_texture: Option<Texture>,
+    /// Index of refraction of the Material, From `KHR_materials_ior`
+    ///
+    /// glTF Materials without the extension use 1.5
+    pub ior: Option<f32>,
+    /// Color of the sheen layer (e.g. velvet or cloth), From `KHR_materials_sheen`
+    pub sheen_color_factor: Option<[f32; 3]>,
+    /// Roughness of the sheen layer
+    pub sheen_roughness_factor: Option<f32>,
+    /// Specular color of the deprecated `KHR_materials_pbrSpecularGlossiness` workflow
+    ///
+    /// Its diffuse color and Texture are loaded into `base_color` and `diffuse_texture`
+    pub specular_factor: Option<[f32; 3]>,
+    /// Glossiness of the specular glossiness workflow, 1.0 is fully smooth
+    pub glossiness_factor: Option<f32>,
+    /// The optional specular glossiness Texture, Specular color in RGB and glossiness in alpha
+    pub specular_glossiness_texture: Option<Texture>,
+    /// The optional displacement (height) Texture, Only loaded from the MTL `disp` map
+    pub displacement_texture: Option<Texture>,
+    /// The optional reflection (environment) Texture, Only loaded from the MTL `refl` map
+    pub reflection_texture: Option<Texture>,
     /// Name of the Material.
     ///
     /// Some File Formats do not support Material names, In this case this will be `None`
     pub name: Option<String>,
+    /// Custom application specific JSON data
+    ///
+    /// Only glTF supports extras, For all other Formats this will be `None`
+    #[cfg(feature = "extras")]
+    pub extras: Option<serde_json::Value>,
 }
 
+#[derive(Debug)]
 pub struct Texture {
     /// The image from the `image` crate, Which is loaded into RAM
     pub image: Image,
@@ -200,8 +743,42 @@ pub struct Texture {
     ///
     /// Some File Formats do not support Texture names, In this case this will be `None`
     pub name: Option<String>,
+    /// How the color values of the Image are encoded, Depends on the Material slot the Texture is used in
+    pub color_space: ColorSpace,
+    /// Which texture coordinate set the Texture is sampled with, From glTF's `texCoord`
+    ///
+    /// `Vertex::tex_coord` is set 0. Only glTF supports multiple sets, For all other Formats this will be 0
+    pub tex_coord_set: u32,
 }
 
+impl Material {
+    /// Returns the texture coordinate set the diffuse Texture samples, `None` without a diffuse Texture
+    ///
+    /// Set 0 is `Vertex::tex_coord`, See [`Texture::tex_coord_set`]
+    #[must_use]
+    pub const fn diffuse_uv_set(&self) -> Option<u32> {
+        match &self.diffuse_texture {
+            Some(texture) => Some(texture.tex_coord_set),
+            None => None,
+        }
+    }
+}
+
+/// Color space of a Texture
+///
+/// # Rendering
+///
+/// `Srgb` Textures should use an sRGB format (e.g. `vk::Format::R8G8B8A8_SRGB`) so sampling returns linear values,
+/// `Linear` Textures should use an UNORM format
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Color data, Used by diffuse/base color and emissive Textures
+    Srgb,
+    /// Non color data, Used by normal, metallic roughness and occlusion Textures
+    Linear,
+}
+
+#[derive(Debug)]
 pub enum Image {
     Memory {
         data: Vec<u8>,
@@ -213,7 +790,31 @@ pub enum Image {
     },
 }
 
-#[derive(Default)]
+impl Image {
+    /// Returns true for GPU compressed containers, KTX2 (e.g. from glTF `KHR_texture_basisu`) and Basis Universal
+    ///
+    /// These can not be decoded into pixels by modelz, They have to be transcoded (e.g. with the Basis Universal transcoder)
+    /// and uploaded as compressed GPU Textures instead. Uses the mime type, The content or the File extension
+    #[must_use]
+    pub fn is_gpu_compressed(&self) -> bool {
+        let is_compressed = |mime_type: &str| matches!(mime_type, "image/ktx2" | "image/basis");
+        match self {
+            Self::Memory { data, mime_type } => mime_type
+                .clone()
+                .or_else(|| sniff::sniff_mime_type(data))
+                .is_some_and(|mime_type| is_compressed(&mime_type)),
+            Self::Path { path, mime_type } => {
+                mime_type.as_deref().is_some_and(is_compressed)
+                    || path.extension().is_some_and(|extension| {
+                        extension.eq_ignore_ascii_case("ktx2")
+                            || extension.eq_ignore_ascii_case("basis")
+                    })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct Sampler {
     pub mag_filter: Option<MagFilter>,
     pub min_filter: Option<MinFilter>,
@@ -222,6 +823,28 @@ pub struct Sampler {
     pub name: Option<String>,
 }
 
+impl Sampler {
+    /// Fills missing filters with the defaults the glTF spec recommends, `Linear` and `LinearMipmapLinear`
+    ///
+    /// Loaders keep filters the File does not specify as `None`, Which leaves the choice to the renderer
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modelz::{MagFilter, MinFilter, Sampler};
+    ///
+    /// let sampler = Sampler::default().with_gltf_defaults();
+    /// assert_eq!(sampler.mag_filter, Some(MagFilter::Linear));
+    /// assert_eq!(sampler.min_filter, Some(MinFilter::LinearMipmapLinear));
+    /// ```
+    #[must_use]
+    pub fn with_gltf_defaults(mut self) -> Self {
+        self.mag_filter.get_or_insert(MagFilter::Linear);
+        self.min_filter.get_or_insert(MinFilter::LinearMipmapLinear);
+        self
+    }
+}
+
 /// Mag Filter
 ///
 /// # Rendering
@@ -328,15 +951,22 @@ pub enum RenderMode {
     TriangleFan,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Vertex {
     pub position: [f32; 3],
-    pub color: Option<[f32; 4]>, // rgba f32
+    /// RGBA color, Always in the 0.0..=1.0 range
+    ///
+    /// Normalized integer colors (e.g. glTF unsigned byte/short) are converted to floats, Colors without alpha get an alpha of 1.0
+    pub color: Option<[f32; 4]>,
     pub tex_coord: Option<[f32; 2]>,
     pub normal: Option<[f32; 3]>,
+    /// Tangent in xyz, w is the handedness of the bitangent (-1.0 or 1.0)
+    ///
+    /// bitangent = cross(normal, tangent.xyz) * tangent.w
+    pub tangent: Option<[f32; 4]>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 /// Indicies
 ///
 /// # Rendering
@@ -348,3 +978,59 @@ pub enum Indices {
     U16(Vec<u16>),
     U32(Vec<u32>),
 }
+
+impl Indices {
+    /// Number of Indices
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::U8(indices) => indices.len(),
+            Self::U16(indices) => indices.len(),
+            Self::U32(indices) => indices.len(),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the Index at the Given position as `usize`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds
+    #[must_use]
+    pub fn get(&self, i: usize) -> usize {
+        match self {
+            Self::U8(indices) => indices[i] as usize,
+            Self::U16(indices) => indices[i] as usize,
+            Self::U32(indices) => indices[i] as usize,
+        }
+    }
+
+    /// Iterates over all Indices as `u32`
+    #[must_use]
+    pub fn iter(&self) -> Box<dyn Iterator<Item = u32> + '_> {
+        match self {
+            Self::U8(indices) => Box::new(indices.iter().map(|&i| u32::from(i))),
+            Self::U16(indices) => Box::new(indices.iter().map(|&i| u32::from(i))),
+            Self::U32(indices) => Box::new(indices.iter().copied()),
+        }
+    }
+
+    /// Returns a copy of all Indices converted to `u32`
+    #[must_use]
+    pub fn to_u32(&self) -> Vec<u32> {
+        self.iter().collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a Indices {
+    type Item = u32;
+    type IntoIter = Box<dyn Iterator<Item = u32> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use crate::{Image, LoadOptions, Model3D, ModelError, Texture};
+
+/// Decoded RGBA8 pixels of a Texture, Shared between all Models of a [`ModelLoader`]
+#[derive(Debug)]
+pub struct DecodedImage {
+    /// Tightly packed RGBA8 pixels
+    pub pixels: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Identifies the same Image across Models
+#[derive(PartialEq, Eq, Hash)]
+enum ImageKey {
+    /// Canonical path of an Image File
+    Path(PathBuf),
+    /// Hash and length of embedded Image data
+    Memory(u64, usize),
+}
+
+/// Loads multiple Models which share a Texture cache
+///
+/// Scenes made of many Files often reference the same Textures, The loader decodes every Image only once.
+/// Image Files are identified by their canonical path and embedded Images by their content
+///
+/// # Examples
+///
+/// ```no_run
+/// use modelz::ModelLoader;
+///
+/// let mut loader = ModelLoader::default();
+/// let house = loader.load("house.obj").expect("Failed to load");
+/// let shed = loader.load("shed.obj").expect("Failed to load");
+/// for material in house.materials.iter().chain(&shed.materials) {
+///     if let Some(texture) = &material.diffuse_texture {
+///         // Decoded once, Even if both Models use the same Texture
+///         let image = loader.decode(texture).expect("Failed to decode");
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct ModelLoader {
+    /// Options used for every Model
+    pub options: LoadOptions,
+    images: HashMap<ImageKey, Arc<DecodedImage>>,
+}
+
+impl ModelLoader {
+    /// Creates a loader with an empty cache
+    #[must_use]
+    pub fn new(options: LoadOptions) -> Self {
+        Self {
+            options,
+            images: HashMap::new(),
+        }
+    }
+
+    /// Loads a Model with the loader's options, See [`Model3D::load_with_options`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Model3D::load_with_options`]
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<Model3D, ModelError> {
+        Model3D::load_with_options(path, &self.options)
+    }
+
+    /// Decodes the Texture into RGBA8 pixels, Returns the cached pixels if the same Image was decoded before
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModelError::MaterialLoad`] if the Image could not be read or decoded
+    pub fn decode(&mut self, texture: &Texture) -> Result<Arc<DecodedImage>, ModelError> {
+        let key = match &texture.image {
+            Image::Path { path, .. } => {
+                ImageKey::Path(std::fs::canonicalize(path).unwrap_or_else(|_| path.clone()))
+            }
+            Image::Memory { data, .. } => {
+                let mut hasher = DefaultHasher::new();
+                data.hash(&mut hasher);
+                ImageKey::Memory(hasher.finish(), data.len())
+            }
+        };
+        if let Some(image) = self.images.get(&key) {
+            return Ok(Arc::clone(image));
+        }
+        let (pixels, width, height) = texture.to_rgba8()?;
+        let image = Arc::new(DecodedImage {
+            pixels,
+            width,
+            height,
+        });
+        self.images.insert(key, Arc::clone(&image));
+        Ok(image)
+    }
+
+    /// Number of distinct Images decoded so far
+    #[must_use]
+    pub fn image_count(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Drops all cached Images, Images still used elsewhere stay alive through their `Arc`
+    pub fn clear_cache(&mut self) {
+        self.images.clear();
+    }
+}
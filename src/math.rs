@@ -0,0 +1,83 @@
+// Small vector helpers used by the mesh utilities, We do not want to pull in a full math crate for this
+
+pub const fn add(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+pub const fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub const fn scale(a: [f32; 3], s: f32) -> [f32; 3] {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+pub const fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub const fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+pub fn length(a: [f32; 3]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+/// Normalizes the Vector, Returns a zero Vector if the length is zero
+pub fn normalize(a: [f32; 3]) -> [f32; 3] {
+    let len = length(a);
+    if len > 0.0 {
+        scale(a, 1.0 / len)
+    } else {
+        [0.0; 3]
+    }
+}
+
+/// Unnormalized normal of the triangle, its length is twice the triangle area
+pub const fn triangle_normal(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> [f32; 3] {
+    cross(sub(b, a), sub(c, a))
+}
+
+/// Blends the three values of a Triangle using barycentric weights
+pub fn interpolate<const N: usize>(values: [[f32; N]; 3], weights: [f32; 3]) -> [f32; N] {
+    std::array::from_fn(|k| {
+        values[2][k].mul_add(
+            weights[2],
+            values[1][k].mul_add(weights[1], values[0][k] * weights[0]),
+        )
+    })
+}
+
+/// Column major 4x4 identity Matrix
+pub const IDENTITY: [f32; 16] = [
+    1.0, 0.0, 0.0, 0.0, //
+    0.0, 1.0, 0.0, 0.0, //
+    0.0, 0.0, 1.0, 0.0, //
+    0.0, 0.0, 0.0, 1.0,
+];
+
+/// Multiplies two column major 4x4 matrices (`a * b`)
+#[cfg(feature = "gltf")]
+pub fn mat4_mul(a: &[f32; 16], b: &[f32; 16]) -> [f32; 16] {
+    let mut out = [0.0; 16];
+    for column in 0..4 {
+        for row in 0..4 {
+            out[column * 4 + row] = (0..4).map(|k| a[k * 4 + row] * b[column * 4 + k]).sum();
+        }
+    }
+    out
+}
+
+#[cfg(feature = "gltf")]
+pub fn from_columns(columns: [[f32; 4]; 4]) -> [f32; 16] {
+    let mut out = [0.0; 16];
+    for (i, column) in columns.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(column);
+    }
+    out
+}
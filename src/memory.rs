@@ -0,0 +1,66 @@
+use std::mem::size_of;
+
+use crate::{Image, Indices, Model3D, Vertex};
+
+/// Bytes of RAM occupied by the data of a [`Model3D`], See [`Model3D::memory_usage`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Vertices and Morph Target displacements of all Meshes
+    pub vertices: usize,
+    /// Indices of all Meshes
+    pub indices: usize,
+    /// Embedded (`Image::Memory`) Texture data, Textures referencing a File are not loaded and take no space
+    pub textures: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the sum of all parts
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.vertices + self.indices + self.textures
+    }
+}
+
+impl Model3D {
+    /// Returns how many bytes the Vertices, Indices and embedded Textures of the Model occupy
+    ///
+    /// Only the buffers are counted, Small per Mesh and per Material overhead like names is ignored.
+    /// Vectors may have reserved more capacity than they use, Which is not counted either
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.gltf").expect("Failed to load");
+    /// println!("{} KiB", model.memory_usage().total() / 1024);
+    /// ```
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut usage = MemoryUsage::default();
+        for mesh in &self.meshes {
+            usage.vertices += mesh.vertices.len() * size_of::<Vertex>();
+            for target in &mesh.morph_targets {
+                usage.vertices += [&target.positions, &target.normals, &target.tangents]
+                    .into_iter()
+                    .flatten()
+                    .map(|displacements| displacements.len() * size_of::<[f32; 3]>())
+                    .sum::<usize>();
+            }
+            usage.indices += match &mesh.indices {
+                Some(Indices::U8(indices)) => indices.len(),
+                Some(Indices::U16(indices)) => indices.len() * size_of::<u16>(),
+                Some(Indices::U32(indices)) => indices.len() * size_of::<u32>(),
+                None => 0,
+            };
+        }
+        for material in &self.materials {
+            for texture in material.textures().into_iter().flatten() {
+                if let Image::Memory { data, .. } = &texture.image {
+                    usage.textures += data.len();
+                }
+            }
+        }
+        usage
+    }
+}
@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+
+use crate::{math, normals::position_key, Indices, Mesh, ModelError, RenderMode};
+
+impl Mesh {
+    /// Returns the Vertex index at the Given position, Resolving the Indices if the Mesh has some
+    pub(crate) fn index(&self, i: usize) -> usize {
+        self.indices.as_ref().map_or(i, |indices| indices.get(i))
+    }
+
+    /// Number of elements which are drawn, The Indices count or the Vertex count for non indexed Meshes
+    pub(crate) fn element_count(&self) -> usize {
+        self.indices
+            .as_ref()
+            .map_or(self.vertices.len(), Indices::len)
+    }
+
+    /// Iterates over all Triangles of the Mesh as Vertex indices
+    ///
+    /// Indices are resolved and `TriangleStrip` and `TriangleFan` are expanded keeping the winding order.
+    /// Meshes using `Points` or any Line mode yield no Triangles
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.obj").expect("Failed to load");
+    /// for [a, b, c] in model.meshes[0].triangles() {
+    ///     println!("{:?}", model.meshes[0].vertices[a].position);
+    /// }
+    /// ```
+    pub fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        let len = self.element_count();
+        let count = match self.mode {
+            RenderMode::Triangles => len / 3,
+            RenderMode::TriangleStrip | RenderMode::TriangleFan => len.saturating_sub(2),
+            _ => 0,
+        };
+        let mode = self.mode;
+        (0..count).map(move |i| {
+            let corners = match mode {
+                // Every second Triangle in a strip has to be flipped to keep the winding order
+                RenderMode::TriangleStrip if i % 2 == 1 => [i + 1, i, i + 2],
+                RenderMode::TriangleStrip => [i, i + 1, i + 2],
+                RenderMode::TriangleFan => [0, i + 1, i + 2],
+                _ => [i * 3, i * 3 + 1, i * 3 + 2],
+            };
+            corners.map(|corner| self.index(corner))
+        })
+    }
+
+    /// Converts `TriangleStrip` and `TriangleFan` Meshes into an indexed `Triangles` list
+    ///
+    /// Meshes which already use `Triangles` or are not made of Triangles stay untouched
+    pub fn triangulate(&mut self) {
+        if !matches!(
+            self.mode,
+            RenderMode::TriangleStrip | RenderMode::TriangleFan
+        ) {
+            return;
+        }
+        let indices = self.triangle_indices();
+        self.indices = Some(Indices::U32(indices));
+        self.mode = RenderMode::Triangles;
+    }
+
+    /// Returns an indexed `Triangles` copy of the Mesh, The Mesh itself stays untouched
+    ///
+    /// Strips and fans are expanded like [`Mesh::triangulate`] does, Vertices, Morph Targets, the Material and the name are copied.
+    /// Meshes using `Points` or any Line mode have no Triangles, So their copy draws nothing
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.gltf").expect("Failed to load");
+    /// let triangles = model.meshes[0].triangulated();
+    /// println!("{} Triangles", triangles.triangle_count());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    #[must_use]
+    pub fn triangulated(&self) -> Self {
+        let mut mesh = Self::new(
+            self.vertices.clone(),
+            Some(Indices::U32(self.triangle_indices())),
+            RenderMode::Triangles,
+        );
+        mesh.material_index = self.material_index;
+        mesh.name.clone_from(&self.name);
+        mesh.morph_targets.clone_from(&self.morph_targets);
+        mesh.morph_weights.clone_from(&self.morph_weights);
+        mesh.bounds = self.bounds;
+        mesh.original_indices.clone_from(&self.original_indices);
+        mesh.source_mesh = self.source_mesh;
+        mesh.material_variants.clone_from(&self.material_variants);
+        #[cfg(feature = "extras")]
+        mesh.extras.clone_from(&self.extras);
+        mesh
+    }
+
+    /// Rewrites the Indices so the Mesh uses the Given list topology
+    ///
+    /// Supported are `TriangleStrip` and `TriangleFan` to `Triangles` and `LineStrip` and `LineLoop` to `Lines`,
+    /// Useful since Vulkan has no `LineLoop` and some backends have no `TriangleFan`. Converting into the current mode does nothing
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{Model3D, RenderMode};
+    ///
+    /// let mut model = Model3D::load("model.gltf").expect("Failed to load");
+    /// model.meshes[0]
+    ///     .convert_topology(RenderMode::Triangles)
+    ///     .expect("Not a Triangle Mesh");
+    /// ```
+    /// # Errors
+    ///
+    /// Returns `ModelError::UnsupportedTopology` for any other conversion, The Mesh stays untouched then
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    pub fn convert_topology(&mut self, target: RenderMode) -> Result<(), ModelError> {
+        let indices = match (self.mode, target) {
+            (from, to) if from == to => return Ok(()),
+            (RenderMode::TriangleStrip | RenderMode::TriangleFan, RenderMode::Triangles) => {
+                self.triangle_indices()
+            }
+            (RenderMode::LineStrip | RenderMode::LineLoop, RenderMode::Lines) => {
+                self.line_indices()
+            }
+            (from, to) => return Err(ModelError::UnsupportedTopology { from, to }),
+        };
+        self.indices = Some(Indices::U32(indices));
+        self.mode = target;
+        Ok(())
+    }
+
+    /// All Line segments of a `LineStrip` or `LineLoop` flattened into a `u32` index list
+    pub(crate) fn line_indices(&self) -> Vec<u32> {
+        let len = self.element_count();
+        // The loop closes from the last back to the first Vertex
+        let closing = (self.mode == RenderMode::LineLoop && len > 1).then_some([len - 1, 0]);
+        (1..len)
+            .map(|i| [i - 1, i])
+            .chain(closing)
+            .flatten()
+            .map(|i| u32::try_from(self.index(i)).expect("Vertex index does not fit into u32"))
+            .collect()
+    }
+
+    /// All Triangles flattened into a `u32` index list
+    pub(crate) fn triangle_indices(&self) -> Vec<u32> {
+        self.triangles()
+            .flatten()
+            .map(|i| u32::try_from(i).expect("Vertex index does not fit into u32"))
+            .collect()
+    }
+
+    /// Merges all Vertices which have exactly the same attributes (and Morph Target displacements)
+    ///
+    /// The Mesh will be indexed afterwards using `Indices::U32`, The `RenderMode` is kept
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    pub fn join_identical_vertices(&mut self) {
+        let mut unique: HashMap<Vec<u32>, u32> = HashMap::new();
+        let mut sources = Vec::new();
+        let mut remap = Vec::with_capacity(self.vertices.len());
+        for i in 0..self.vertices.len() {
+            let index = *unique.entry(self.vertex_key(i)).or_insert_with(|| {
+                sources.push(i);
+                u32::try_from(sources.len() - 1).expect("Vertex index does not fit into u32")
+            });
+            remap.push(index);
+        }
+
+        let indices = (0..self.element_count())
+            .map(|i| remap[self.index(i)])
+            .collect();
+        self.gather_vertices(&sources);
+        self.indices = Some(Indices::U32(indices));
+    }
+
+    /// Merges Vertices within `epsilon` of each other, But only if all their other attributes match exactly
+    ///
+    /// Unlike welding by position alone UV and normal seams survive, The Vertices on both sides of a seam stay separate.
+    /// Nearby Vertices are snapped onto the first Vertex of their position group beforehand, So both sides of a seam end up at
+    /// exactly the same position. The Mesh will be indexed afterwards using `Indices::U32`
+    ///
+    /// Returns the position group of every remaining Vertex, Vertices sharing a group sit at the same position.
+    /// Algorithms which need connectivity across seams (smoothing, connected components) can treat them as one Vertex
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    pub fn weld_positions_keep_seams(&mut self, epsilon: f32) -> Vec<usize> {
+        let tree = self.build_kdtree();
+        let mut canonical = vec![usize::MAX; self.vertices.len()];
+        for i in 0..self.vertices.len() {
+            if canonical[i] != usize::MAX {
+                continue;
+            }
+            canonical[i] = i;
+            for j in tree.within_radius(self.vertices[i].position, epsilon) {
+                if canonical[j] == usize::MAX {
+                    canonical[j] = i;
+                }
+            }
+        }
+        for (i, &source) in canonical.iter().enumerate() {
+            self.vertices[i].position = self.vertices[source].position;
+        }
+        self.join_identical_vertices();
+
+        // Snapped positions of different groups never match, So the exact position identifies the group
+        let mut groups = HashMap::new();
+        self.vertices
+            .iter()
+            .map(|vertex| {
+                let next = groups.len();
+                *groups.entry(position_key(vertex.position)).or_insert(next)
+            })
+            .collect()
+    }
+
+    /// Bit pattern of all attributes of a Vertex, Including its Morph Target displacements
+    fn vertex_key(&self, i: usize) -> Vec<u32> {
+        // A presence marker keeps `None` and `Some(0.0)` apart
+        fn push<const N: usize>(key: &mut Vec<u32>, value: Option<[f32; N]>) {
+            key.push(u32::from(value.is_some()));
+            key.extend(value.into_iter().flatten().map(f32::to_bits));
+        }
+        let vertex = &self.vertices[i];
+        let mut key = vertex.position.map(f32::to_bits).to_vec();
+        push(&mut key, vertex.color);
+        push(&mut key, vertex.tex_coord);
+        push(&mut key, vertex.normal);
+        push(&mut key, vertex.tangent);
+        for target in &self.morph_targets {
+            for attribute in [&target.positions, &target.normals, &target.tangents] {
+                push(&mut key, attribute.as_ref().map(|values| values[i]));
+            }
+        }
+        // Vertices of different source Vertices stay apart
+        key.extend(self.original_indices.as_ref().map(|original| original[i]));
+        key
+    }
+
+    /// Rebuilds the Vertices and Morph Target displacements from the Given source Vertex indices
+    pub(crate) fn gather_vertices(&mut self, sources: &[usize]) {
+        self.vertices = sources.iter().map(|&i| self.vertices[i].clone()).collect();
+        self.bounds = None;
+        if let Some(original) = &mut self.original_indices {
+            *original = sources.iter().map(|&i| original[i]).collect();
+        }
+        for target in &mut self.morph_targets {
+            for values in [
+                &mut target.positions,
+                &mut target.normals,
+                &mut target.tangents,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *values = sources.iter().map(|&i| values[i]).collect();
+            }
+        }
+    }
+
+    /// Removes all Vertices which are not referenced by any index and remaps the Indices
+    ///
+    /// The order of the remaining Vertices and the index type are kept. Does nothing for Meshes without Indices
+    pub fn remove_unused_vertices(&mut self) {
+        let Some(indices) = &self.indices else {
+            return;
+        };
+        let mut used = vec![false; self.vertices.len()];
+        for index in indices {
+            used[index as usize] = true;
+        }
+        let sources = (0..self.vertices.len())
+            .filter(|&i| used[i])
+            .collect::<Vec<_>>();
+        if sources.len() == self.vertices.len() {
+            return;
+        }
+        let mut remap = vec![0_u32; self.vertices.len()];
+        for (new, &old) in (0..).zip(&sources) {
+            remap[old] = new;
+        }
+
+        self.indices = Some(match indices {
+            Indices::U8(indices) => Indices::U8(remap_indices(indices, &remap)),
+            Indices::U16(indices) => Indices::U16(remap_indices(indices, &remap)),
+            Indices::U32(indices) => Indices::U32(remap_indices(indices, &remap)),
+        });
+        self.gather_vertices(&sources);
+    }
+
+    /// Removes Triangles which have no area and returns how many were removed
+    ///
+    /// A Triangle is degenerate when two corners use the same Vertex or its height is at most `epsilon` times its longest edge,
+    /// This covers corners at the same position and collinear corners. Comparing against the longest edge makes `epsilon` independent of the Model scale.
+    /// When something was removed strips and fans become an indexed `Triangles` list and Vertices which are no longer used are dropped
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    pub fn remove_degenerate_triangles(&mut self, epsilon: f32) -> usize {
+        let is_degenerate = |[a, b, c]: [usize; 3]| {
+            if a == b || b == c || a == c {
+                return true;
+            }
+            let [a, b, c] = [a, b, c].map(|i| self.vertices[i].position);
+            let longest = [math::sub(b, a), math::sub(c, b), math::sub(a, c)]
+                .map(math::length)
+                .into_iter()
+                .fold(0.0, f32::max);
+            math::length(math::cross(math::sub(b, a), math::sub(c, a)))
+                <= epsilon * longest * longest
+        };
+        let triangles = self.triangles().collect::<Vec<_>>();
+        let kept = triangles
+            .iter()
+            .copied()
+            .filter(|&triangle| !is_degenerate(triangle))
+            .collect::<Vec<_>>();
+        let removed = triangles.len() - kept.len();
+        if removed == 0 {
+            return 0;
+        }
+        let indices = kept
+            .into_iter()
+            .flatten()
+            .map(|i| u32::try_from(i).expect("Vertex index does not fit into u32"))
+            .collect();
+        self.indices = Some(Indices::U32(indices));
+        self.mode = RenderMode::Triangles;
+        self.bounds = None;
+        self.remove_unused_vertices();
+        removed
+    }
+
+    /// Flips the V texture coordinate of every Vertex (`v = 1.0 - v`)
+    ///
+    /// Useful when the renderer expects the texture origin in another corner than the File Format
+    pub fn flip_uvs(&mut self) {
+        for vertex in &mut self.vertices {
+            if let Some(tex_coord) = &mut vertex.tex_coord {
+                tex_coord[1] = 1.0 - tex_coord[1];
+            }
+        }
+    }
+}
+
+/// Maps every index through `remap`, Keeping the index type
+fn remap_indices<T: Copy + Into<u32> + TryFrom<u32>>(indices: &[T], remap: &[u32]) -> Vec<T> {
+    // A remapped index is never larger than the old one, So the conversion back never fails
+    indices
+        .iter()
+        .filter_map(|&i| T::try_from(remap[i.into() as usize]).ok())
+        .collect()
+}
+
+/// Makes sure every index points at an existing Vertex, Loaders call this so corrupt Files fail instead of panicking later
+#[cfg(any(feature = "obj", feature = "gltf", feature = "ply"))]
+pub fn validate_indices(vertices_len: usize, indices: &Indices) -> Result<(), ModelError> {
+    indices
+        .iter()
+        .find(|&index| index as usize >= vertices_len)
+        .map_or(Ok(()), |index| {
+            Err(ModelError::ModelParsing(format!(
+                "Index {index} is out of range for {vertices_len} Vertices"
+            )))
+        })
+}
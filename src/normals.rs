@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use crate::{math, Indices, Mesh, Model3D, RenderMode};
+
+impl Model3D {
+    /// Flips the normals of every Mesh, See [`Mesh::flip_normals`]
+    pub fn flip_normals(&mut self, reverse_winding: bool) {
+        for mesh in &mut self.meshes {
+            mesh.flip_normals(reverse_winding);
+        }
+    }
+}
+
+impl Mesh {
+    /// Negates every Vertex normal (and Morph Target normal displacement), Useful for Models authored with inward facing normals
+    ///
+    /// The tangent handedness (w) is negated too so the bitangent keeps its direction.
+    /// When `reverse_winding` is set the Triangle winding order is reversed as well, So back face culling stays consistent
+    /// with the normals. Triangle strips and fans are converted into a `Triangles` list for this
+    pub fn flip_normals(&mut self, reverse_winding: bool) {
+        for vertex in &mut self.vertices {
+            if let Some(normal) = &mut vertex.normal {
+                *normal = math::scale(*normal, -1.0);
+            }
+            if let Some(tangent) = &mut vertex.tangent {
+                tangent[3] = -tangent[3];
+            }
+        }
+        for normals in self
+            .morph_targets
+            .iter_mut()
+            .filter_map(|target| target.normals.as_mut())
+        {
+            for normal in normals {
+                *normal = math::scale(*normal, -1.0);
+            }
+        }
+        if reverse_winding && self.is_triangle_mesh() {
+            self.reverse_winding();
+        }
+    }
+
+    fn reverse_winding(&mut self) {
+        fn swap<T>(indices: &mut [T]) {
+            for triangle in indices.chunks_exact_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+        if self.mode != RenderMode::Triangles {
+            self.triangulate();
+        }
+        match &mut self.indices {
+            Some(Indices::U8(indices)) => swap(indices),
+            Some(Indices::U16(indices)) => swap(indices),
+            Some(Indices::U32(indices)) => swap(indices),
+            None => {
+                let sources = (0..self.vertices.len())
+                    .map(|i| match i % 3 {
+                        1 if i + 1 < self.vertices.len() => i + 1,
+                        2 => i - 1,
+                        _ => i,
+                    })
+                    .collect::<Vec<_>>();
+                self.gather_vertices(&sources);
+            }
+        }
+    }
+
+    /// Returns the geometric normal of every Triangle, In the order of [`Mesh::triangles`]
+    ///
+    /// The normals are computed from the winding order and ignore the Vertex normals. Degenerated Triangles get a zero normal
+    #[must_use]
+    pub fn face_normals(&self) -> Vec<[f32; 3]> {
+        self.triangles()
+            .map(|[a, b, c]| {
+                math::normalize(math::triangle_normal(
+                    self.vertices[a].position,
+                    self.vertices[b].position,
+                    self.vertices[c].position,
+                ))
+            })
+            .collect()
+    }
+
+    /// Generates flat (per face) normals, Overwriting existing normals
+    ///
+    /// Every Triangle gets its own three Vertices so the Mesh is converted into a non indexed `Triangles` list.
+    /// Meshes which are not made of Triangles stay untouched
+    pub fn compute_flat_normals(&mut self) {
+        if !self.is_triangle_mesh() {
+            return;
+        }
+        let normals = self.face_normals();
+        let sources: Vec<usize> = self.triangles().flatten().collect();
+        self.gather_vertices(&sources);
+        for (triangle, normal) in self.vertices.chunks_mut(3).zip(normals) {
+            for vertex in triangle {
+                vertex.normal = Some(normal);
+            }
+        }
+        self.indices = None;
+        self.mode = RenderMode::Triangles;
+    }
+
+    /// Generates smooth normals, Overwriting existing normals
+    ///
+    /// Every Vertex normal is the area weighted average of all faces sharing its position,
+    /// so Vertices which are split (e.g. at UV seams) are still smoothed together
+    pub fn compute_smooth_normals(&mut self) {
+        if !self.is_triangle_mesh() {
+            return;
+        }
+        let normals = self.smooth_normals();
+        for vertex in &mut self.vertices {
+            if let Some(normal) = normals.get(&position_key(vertex.position)) {
+                vertex.normal = Some(math::normalize(*normal));
+            }
+        }
+    }
+
+    /// Generates normals which are smooth across soft edges and hard across edges sharper than `degrees`, Overwriting existing normals
+    ///
+    /// Every face corner averages the area weighted normals of the faces sharing its position whose angle to its own face is at most `degrees`.
+    /// Vertices at hard edges get one copy per distinct normal, So the Mesh is converted into an indexed `Triangles` list.
+    /// `0.0` gives flat shading and `180.0` the same normals as [`Mesh::compute_smooth_normals`]. Meshes which are not made of Triangles stay untouched
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let mut model = Model3D::load("model.stl").expect("Failed to load");
+    /// model.meshes[0].compute_normals_with_angle(30.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mesh has more Vertices than fit into `u32`
+    pub fn compute_normals_with_angle(&mut self, degrees: f32) {
+        if !self.is_triangle_mesh() {
+            return;
+        }
+        let triangles = self.triangles().collect::<Vec<_>>();
+        let face_normals = triangles
+            .iter()
+            .map(|&[a, b, c]| {
+                math::triangle_normal(
+                    self.vertices[a].position,
+                    self.vertices[b].position,
+                    self.vertices[c].position,
+                )
+            })
+            .collect::<Vec<_>>();
+        let unit_normals = face_normals
+            .iter()
+            .map(|&normal| math::normalize(normal))
+            .collect::<Vec<_>>();
+        let mut faces_at: HashMap<[u32; 3], Vec<usize>> = HashMap::new();
+        for (face, triangle) in triangles.iter().enumerate() {
+            for &i in triangle {
+                faces_at
+                    .entry(position_key(self.vertices[i].position))
+                    .or_default()
+                    .push(face);
+            }
+        }
+
+        let min_cos = degrees.to_radians().cos();
+        let mut unique: HashMap<(usize, [u32; 3]), u32> = HashMap::new();
+        let mut sources = Vec::new();
+        let mut normals = Vec::new();
+        let mut indices = Vec::with_capacity(triangles.len() * 3);
+        for (face, triangle) in triangles.iter().enumerate() {
+            for &i in triangle {
+                // The own face always counts, Even when it is degenerated
+                let normal = math::normalize(
+                    faces_at[&position_key(self.vertices[i].position)]
+                        .iter()
+                        .filter(|&&other| {
+                            other == face
+                                || math::dot(unit_normals[face], unit_normals[other]) >= min_cos
+                        })
+                        .fold([0.0; 3], |sum, &other| math::add(sum, face_normals[other])),
+                );
+                let index = *unique
+                    .entry((i, normal.map(f32::to_bits)))
+                    .or_insert_with(|| {
+                        sources.push(i);
+                        normals.push(normal);
+                        u32::try_from(sources.len() - 1)
+                            .expect("Vertex index does not fit into u32")
+                    });
+                indices.push(index);
+            }
+        }
+        self.gather_vertices(&sources);
+        for (vertex, normal) in self.vertices.iter_mut().zip(normals) {
+            vertex.normal = Some(normal);
+        }
+        self.indices = Some(Indices::U32(indices));
+        self.mode = RenderMode::Triangles;
+    }
+
+    /// Generates smooth normals only for Vertices without a normal, Existing normals are kept
+    ///
+    /// Useful for Meshes which only have some normals, e.g. after merging Meshes. The generated normals are the same as
+    /// [`Mesh::compute_smooth_normals`] would generate, All faces are used for the average
+    pub fn fill_missing_normals(&mut self) {
+        if !self.is_triangle_mesh() || self.vertices.iter().all(|v| v.normal.is_some()) {
+            return;
+        }
+        let normals = self.smooth_normals();
+        for vertex in &mut self.vertices {
+            if vertex.normal.is_none() {
+                vertex.normal = normals
+                    .get(&position_key(vertex.position))
+                    .map(|normal| math::normalize(*normal));
+            }
+        }
+    }
+
+    /// Sums the area weighted face normals for every position
+    fn smooth_normals(&self) -> HashMap<[u32; 3], [f32; 3]> {
+        let mut normals: HashMap<[u32; 3], [f32; 3]> = HashMap::new();
+        for [a, b, c] in self.triangles() {
+            // Not normalized, so larger faces have more influence
+            let normal = math::triangle_normal(
+                self.vertices[a].position,
+                self.vertices[b].position,
+                self.vertices[c].position,
+            );
+            for i in [a, b, c] {
+                let sum = normals
+                    .entry(position_key(self.vertices[i].position))
+                    .or_insert([0.0; 3]);
+                *sum = math::add(*sum, normal);
+            }
+        }
+        normals
+    }
+
+    /// Generates tangents from the normals and texture coordinates, Overwriting existing tangents
+    ///
+    /// Vertices without a normal or texture coordinate get no tangent. The w component stores the handedness of the bitangent
+    pub fn compute_tangents(&mut self) {
+        let mut tangents = vec![[0.0; 3]; self.vertices.len()];
+        let mut bitangents = vec![[0.0; 3]; self.vertices.len()];
+        for [a, b, c] in self.triangles() {
+            let (Some(uv_a), Some(uv_b), Some(uv_c)) = (
+                self.vertices[a].tex_coord,
+                self.vertices[b].tex_coord,
+                self.vertices[c].tex_coord,
+            ) else {
+                continue;
+            };
+            let edge1 = math::sub(self.vertices[b].position, self.vertices[a].position);
+            let edge2 = math::sub(self.vertices[c].position, self.vertices[a].position);
+            let (s1, t1) = (uv_b[0] - uv_a[0], uv_b[1] - uv_a[1]);
+            let (s2, t2) = (uv_c[0] - uv_a[0], uv_c[1] - uv_a[1]);
+            let det = s1.mul_add(t2, -(s2 * t1));
+            if det.abs() <= f32::EPSILON {
+                // Degenerated UV mapping
+                continue;
+            }
+            let r = 1.0 / det;
+            let tangent = math::scale(math::sub(math::scale(edge1, t2), math::scale(edge2, t1)), r);
+            let bitangent =
+                math::scale(math::sub(math::scale(edge2, s1), math::scale(edge1, s2)), r);
+            for i in [a, b, c] {
+                tangents[i] = math::add(tangents[i], tangent);
+                bitangents[i] = math::add(bitangents[i], bitangent);
+            }
+        }
+        for (i, vertex) in self.vertices.iter_mut().enumerate() {
+            let (Some(normal), Some(_)) = (vertex.normal, vertex.tex_coord) else {
+                vertex.tangent = None;
+                continue;
+            };
+            // Gram-Schmidt orthogonalize
+            let tangent = math::normalize(math::sub(
+                tangents[i],
+                math::scale(normal, math::dot(normal, tangents[i])),
+            ));
+            let handedness = if math::dot(math::cross(normal, tangent), bitangents[i]) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            vertex.tangent = Some([tangent[0], tangent[1], tangent[2], handedness]);
+        }
+    }
+
+    pub(crate) const fn is_triangle_mesh(&self) -> bool {
+        matches!(
+            self.mode,
+            RenderMode::Triangles | RenderMode::TriangleStrip | RenderMode::TriangleFan
+        )
+    }
+}
+
+pub fn position_key(position: [f32; 3]) -> [u32; 3] {
+    // Adding 0.0 turns -0.0 into 0.0 so both share the same key
+    position.map(|v| (v + 0.0).to_bits())
+}
@@ -1,14 +1,33 @@
 use std::path::Path;
 
-use crate::{Model3D, ModelError, Vertex};
+use crate::{
+    mesh::validate_indices, options::LoadContext, LoadOptions, Model3D, ModelError, Vertex,
+};
 
-pub fn load(path: &Path) -> Result<Model3D, ModelError> {
-    let (models, materials) = match tobj::load_obj(path, &tobj::GPU_LOAD_OPTIONS) {
-        Ok(r) => r,
-        Err(e) => return Err(ModelError::ModelParsing(format!("{e}"))),
+pub fn load(path: &Path, options: &LoadContext) -> Result<Model3D, ModelError> {
+    let load_options = tobj::LoadOptions {
+        single_index: options.obj_single_index,
+        triangulate: options.obj_triangulate,
+        ignore_points: options.obj_ignore_points,
+        ignore_lines: options.obj_ignore_lines,
     };
+    let data = crate::source::read(path, options)?;
+    let content = expand_elements(&String::from_utf8_lossy(&data));
+    let (models, materials) = parse(
+        &mut content.as_bytes(),
+        options.model_dir(path),
+        load_options,
+        options,
+    )
+    .map_err(|e| ModelError::ModelParsing(format!("{e}")))?;
 
-    let path = path.parent().unwrap_or_else(|| Path::new("./"));
+    // Textures are relative to the MTL File
+    let path = options
+        .mtl_override
+        .as_deref()
+        .unwrap_or(path)
+        .parent()
+        .unwrap_or_else(|| Path::new("./"));
 
     let mut final_materials = Vec::new();
 
@@ -24,7 +43,7 @@ pub fn load(path: &Path) -> Result<Model3D, ModelError> {
     let len = materials.len();
     for (i, material) in materials.into_iter().enumerate() {
         log::debug!("Loading Material {} {}/{}", material.name, i + 1, len,);
-        final_materials.push(load_material(material, path));
+        final_materials.push(load_material(material, path, options)?);
     }
 
     let mut meshes = Vec::new();
@@ -33,92 +52,489 @@ pub fn load(path: &Path) -> Result<Model3D, ModelError> {
     for (i, model) in models.into_iter().enumerate() {
         log::debug!("Loading Material {} {}/{}", model.name, i + 1, len,);
         let mesh = model.mesh;
-        let vertices = load_mesh(&mesh);
-        meshes.push(crate::Mesh {
+        options.check_vertices(mesh.positions.len() / 3)?;
+        options.check_indices(mesh.indices.len())?;
+        let indices = crate::Indices::U32(mesh.indices.clone()); // OBJ only has u32 indices
+        validate_indices(mesh.positions.len() / 3, &indices)?;
+        let (vertices, indices) = if options.obj_single_index {
+            let indices = if indices.is_empty() {
+                None
+            } else {
+                Some(indices)
+            };
+            (load_mesh(&mesh), indices)
+        } else {
+            validate_indices(
+                mesh.texcoords.len() / 2,
+                &crate::Indices::U32(mesh.texcoord_indices.clone()),
+            )?;
+            validate_indices(
+                mesh.normals.len() / 3,
+                &crate::Indices::U32(mesh.normal_indices.clone()),
+            )?;
+            (load_corners(&mesh), None)
+        };
+        let loaded = crate::Mesh {
             vertices,
-            indices: {
-                if mesh.indices.is_empty() {
-                    None
-                } else {
-                    Some(crate::Indices::U32(mesh.indices.clone())) // OBJ only has u32 indices
-                }
-            },
-            mode: crate::RenderMode::TriangleFan,
+            indices,
+            mode: crate::RenderMode::Triangles,
             name: Some(model.name),
             material_index: mesh.material_id,
-        });
+            morph_targets: Vec::new(),
+            morph_weights: Vec::new(),
+            bounds: None,
+            original_indices: None,
+            source_mesh: None,
+            material_variants: None,
+            #[cfg(feature = "extras")]
+            extras: None,
+        };
+        // Without triangulation tobj keeps the faces as they are, Unless all of them are Triangles
+        if mesh.face_arities.is_empty() {
+            meshes.push(loaded);
+        } else {
+            meshes.extend(split_faces(&loaded, &mesh.face_arities));
+        }
     }
 
-    Ok(Model3D {
+    Ok(Model3D::new(
         meshes,
-        materials: final_materials,
-        format: crate::ModelFormat::OBJ,
+        final_materials,
+        crate::ModelFormat::OBJ,
+    ))
+}
+
+/// Parses the OBJ File with tobj, The MTL File is loaded relative to `model_dir` unless `mtl_override` is set
+///
+/// Without a `model_dir` (Models loaded from memory) only the `mtl_override` can be loaded
+fn parse(
+    reader: &mut impl std::io::BufRead,
+    model_dir: Option<&Path>,
+    load_options: tobj::LoadOptions,
+    options: &LoadOptions,
+) -> tobj::LoadResult {
+    tobj::load_obj_buf(reader, &load_options, |mtl_path| {
+        match (&options.mtl_override, model_dir) {
+            (Some(mtl_override), _) => tobj::load_mtl(mtl_override),
+            (None, Some(model_dir)) => tobj::load_mtl(model_dir.join(mtl_path)),
+            (None, None) => Err(tobj::LoadError::OpenFileFailed),
+        }
     })
 }
 
-fn load_material(material: tobj::Material, model_dir: &Path) -> crate::Material {
-    let base_color = material.diffuse.as_ref().map(|d| [d[0], d[1], d[2], 1.0]);
+/// Rewrites `p` elements into single Vertex faces and `l` polylines into segments, So tobj reads them like faces
+///
+/// tobj skips `p` elements and reads `l` elements with more than two Vertices as Polygons
+fn expand_elements(content: &str) -> String {
+    let mut expanded = String::with_capacity(content.len());
+    for line in content.lines() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("p") => {
+                for corner in words {
+                    expanded.push_str("f ");
+                    expanded.push_str(corner);
+                    expanded.push('\n');
+                }
+            }
+            Some("l") => {
+                let corners = words.collect::<Vec<_>>();
+                for segment in corners.windows(2) {
+                    expanded.push_str("l ");
+                    expanded.push_str(&segment.join(" "));
+                    expanded.push('\n');
+                }
+            }
+            _ => {
+                expanded.push_str(line);
+                expanded.push('\n');
+            }
+        }
+    }
+    expanded
+}
 
-    let diffuse_texture = material.diffuse_texture.map(|ref texture| {
-        let image = crate::Image::Path {
-            path: model_dir.join(texture),
-            mime_type: None,
-        };
-        crate::Texture {
-            image,
-            sampler: crate::Sampler::default(),
-            name: Some(texture.to_string()),
+/// Splits the faces into a Points, a Lines and a Triangles Mesh, Polygons become Triangle fans
+///
+/// Meshes without faces of a kind are skipped, The unused Vertices of every Mesh are removed
+fn split_faces(mesh: &crate::Mesh, face_arities: &[u32]) -> Vec<crate::Mesh> {
+    let mut points = Vec::new();
+    let mut lines = Vec::new();
+    let mut triangles = Vec::new();
+    let mut start = 0;
+    for &arity in face_arities {
+        let arity = arity as usize;
+        match arity {
+            1 => points.push(start),
+            2 => lines.extend([start, start + 1]),
+            _ => {
+                for i in 1..arity.saturating_sub(1) {
+                    triangles.extend([start, start + i, start + i + 1]);
+                }
+            }
         }
-    });
+        start += arity;
+    }
+    [
+        (points, crate::RenderMode::Points),
+        (lines, crate::RenderMode::Lines),
+        (triangles, crate::RenderMode::Triangles),
+    ]
+    .into_iter()
+    .filter(|(corners, _)| !corners.is_empty())
+    .map(|(corners, mode)| {
+        let mut split = crate::Mesh::new(mesh.vertices.clone(), None, mode);
+        split.name.clone_from(&mesh.name);
+        split.material_index = mesh.material_index;
+        split.source_mesh = mesh.source_mesh;
+        if let Some(indices) = &mesh.indices {
+            split.indices = Some(crate::Indices::U32(
+                corners
+                    .iter()
+                    .map(|&corner| indices.get(corner))
+                    .map(|index| u32::try_from(index).unwrap_or(u32::MAX))
+                    .collect(),
+            ));
+            split.remove_unused_vertices();
+        } else {
+            // Every corner has its own Vertex
+            split.gather_vertices(&corners);
+        }
+        split
+    })
+    .collect()
+}
+
+fn load_material(
+    material: tobj::Material,
+    model_dir: &Path,
+    options: &LoadContext,
+) -> Result<crate::Material, ModelError> {
+    let base_color = material.diffuse.as_ref().map(|d| [d[0], d[1], d[2], 1.0]);
 
-    crate::Material {
+    let diffuse_texture = material
+        .diffuse_texture
+        .map(|texture| load_texture(model_dir, texture, options, crate::ColorSpace::Srgb))
+        .transpose()?;
+    let normal_texture = material
+        .normal_texture
+        .map(|texture| load_texture(model_dir, texture, options, crate::ColorSpace::Linear))
+        .transpose()?;
+    // tobj does not know these maps, They end up in the unknown parameters
+    let unknown_texture = |key: &str, color_space| {
+        material
+            .unknown_param
+            .get(key)
+            .map(|map| load_texture(model_dir, map_path(map).to_string(), options, color_space))
+            .transpose()
+    };
+    let displacement_texture = unknown_texture("disp", crate::ColorSpace::Linear)?;
+    let reflection_texture = unknown_texture("refl", crate::ColorSpace::Srgb)?;
+
+    Ok(crate::Material {
         double_sided: false,
         alpha_cutoff: material.dissolve,
         alpha_mode: crate::AlphaMode::Opaque,
         diffuse_texture,
+        normal_texture,
         base_color,
+        transmission_factor: None,
+        transmission_texture: None,
+        thickness_factor: None,
+        attenuation_color: None,
+        attenuation_distance: None,
+        clearcoat_factor: None,
+        clearcoat_roughness_factor: None,
+        clearcoat_texture: None,
+        clearcoat_normal_texture: None,
+        ior: None,
+        sheen_color_factor: None,
+        sheen_roughness_factor: None,
+        specular_factor: None,
+        glossiness_factor: None,
+        specular_glossiness_texture: None,
+        displacement_texture,
+        reflection_texture,
         name: Some(material.name),
+        #[cfg(feature = "extras")]
+        extras: None,
+    })
+}
+
+/// Returns the File of a texture map statement, Options like `-type sphere` or `-mm 0 1` come before it
+fn map_path(map: &str) -> &str {
+    if map.starts_with('-') {
+        map.split_whitespace().last().unwrap_or(map)
+    } else {
+        map
     }
 }
 
+fn load_texture(
+    model_dir: &Path,
+    texture: String,
+    options: &LoadContext,
+    color_space: crate::ColorSpace,
+) -> Result<crate::Texture, ModelError> {
+    let image = options
+        .resolve_texture(&texture)?
+        .unwrap_or_else(|| crate::Image::Path {
+            path: model_dir.join(&texture),
+            mime_type: None,
+        });
+    Ok(crate::Texture {
+        image,
+        sampler: crate::Sampler::default(),
+        name: Some(texture),
+        color_space,
+        tex_coord_set: 0,
+    })
+}
+
 fn load_mesh(mesh: &tobj::Mesh) -> Vec<Vertex> {
     (0..mesh.positions.len() / 3)
-        .map(|i| Vertex {
-            position: [
-                mesh.positions[i * 3],
-                mesh.positions[i * 3 + 1],
-                mesh.positions[i * 3 + 2],
-            ],
-            tex_coord: {
-                if mesh.texcoords.is_empty() {
-                    None
-                } else {
-                    Some([mesh.texcoords[i * 2], mesh.texcoords[i * 2 + 1]])
-                }
-            },
-            color: {
-                if mesh.vertex_color.is_empty() {
-                    None
-                } else {
-                    Some([
-                        mesh.vertex_color[i * 3],
-                        mesh.vertex_color[i * 3 + 1],
-                        mesh.vertex_color[i * 3 + 2],
-                        0.0, // OBJ does not have vertex color alpha
-                    ])
-                }
-            },
-            normal: {
-                if mesh.normals.is_empty() {
-                    None
-                } else {
-                    Some([
-                        mesh.normals[i * 3],
-                        mesh.normals[i * 3 + 1],
-                        mesh.normals[i * 3 + 2],
-                    ])
-                }
-            },
+        .map(|i| load_vertex(mesh, i, i, i))
+        .collect::<Vec<_>>()
+}
+
+/// Builds every face corner as its own Vertex, Resolving the separate position, texture coordinate and normal indices
+fn load_corners(mesh: &tobj::Mesh) -> Vec<Vertex> {
+    mesh.indices
+        .iter()
+        .enumerate()
+        .map(|(corner, &position)| {
+            let tex_coord = mesh.texcoord_indices.get(corner).unwrap_or(&position);
+            let normal = mesh.normal_indices.get(corner).unwrap_or(&position);
+            load_vertex(
+                mesh,
+                position as usize,
+                *tex_coord as usize,
+                *normal as usize,
+            )
         })
         .collect::<Vec<_>>()
 }
+
+/// Builds one Vertex, Attributes whose array does not cover their index (e.g. faces mixing `v//vn` and `v/vt/vn` corners) are `None`
+fn load_vertex(mesh: &tobj::Mesh, position: usize, tex_coord: usize, normal: usize) -> Vertex {
+    Vertex {
+        // tobj rejects faces referencing a missing position
+        position: [
+            mesh.positions[position * 3],
+            mesh.positions[position * 3 + 1],
+            mesh.positions[position * 3 + 2],
+        ],
+        tex_coord: attribute(&mesh.texcoords, tex_coord),
+        // OBJ does not have vertex color alpha
+        color: attribute(&mesh.vertex_color, position).map(|[r, g, b]| [r, g, b, 1.0]),
+        normal: attribute(&mesh.normals, normal),
+        tangent: None,
+    }
+}
+
+/// Returns the `N` components of the attribute at `index`, `None` if `values` does not cover it
+fn attribute<const N: usize>(values: &[f32], index: usize) -> Option<[f32; N]> {
+    values.get(index * N..(index + 1) * N)?.try_into().ok()
+}
+
+/// An OBJ Mesh with its original, separate position, texture coordinate and normal indices
+///
+/// [`crate::Model3D::load`] merges these indices into one index per Vertex, Which is what a GPU wants but loses
+/// which face corners originally shared a position. Useful for editing and conversion tools
+#[derive(Clone, Debug, Default)]
+pub struct RawObjMesh {
+    /// Name of the OBJ object or group
+    pub name: String,
+    pub positions: Vec<[f32; 3]>,
+    pub tex_coords: Vec<[f32; 2]>,
+    pub normals: Vec<[f32; 3]>,
+    /// Faces as they appear in the File, Every face has 3 or more corners
+    pub faces: Vec<Vec<RawObjIndex>>,
+    /// Material index in the loaded MTL file
+    pub material_index: Option<usize>,
+}
+
+/// The `v/vt/vn` index triplet of one face corner
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RawObjIndex {
+    /// Index into [`RawObjMesh::positions`]
+    pub position: u32,
+    /// Index into [`RawObjMesh::tex_coords`]
+    pub tex_coord: Option<u32>,
+    /// Index into [`RawObjMesh::normals`]
+    pub normal: Option<u32>,
+}
+
+impl RawObjMesh {
+    /// Loads all Meshes of an OBJ File without merging their indices or triangulating their faces
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::RawObjMesh;
+    ///
+    /// let meshes = RawObjMesh::load("model.obj").expect("Failed to load");
+    /// for face in &meshes[0].faces {
+    ///     println!("{:?}", face);
+    /// }
+    /// ```
+    /// # Errors
+    ///
+    /// Returns an Error is loading the OBJ File was unsuccessful
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, ModelError> {
+        let load_options = tobj::LoadOptions {
+            single_index: false,
+            triangulate: false,
+            ignore_points: true,
+            ignore_lines: true,
+        };
+        let (models, _) = match tobj::load_obj(path.as_ref(), &load_options) {
+            Ok(r) => r,
+            Err(e) => return Err(ModelError::ModelParsing(format!("{e}"))),
+        };
+        Ok(models.into_iter().map(Self::from_model).collect())
+    }
+
+    fn from_model(model: tobj::Model) -> Self {
+        let mesh = model.mesh;
+        // tobj leaves the arities empty when every face is a Triangle
+        let arities = if mesh.face_arities.is_empty() {
+            vec![3; mesh.indices.len() / 3]
+        } else {
+            mesh.face_arities.clone()
+        };
+        let mut corner = 0;
+        let faces = arities
+            .into_iter()
+            .map(|arity| {
+                let face = (corner..corner + arity as usize)
+                    .map(|i| RawObjIndex {
+                        position: mesh.indices[i],
+                        tex_coord: mesh.texcoord_indices.get(i).copied(),
+                        normal: mesh.normal_indices.get(i).copied(),
+                    })
+                    .collect();
+                corner += arity as usize;
+                face
+            })
+            .collect();
+        Self {
+            name: model.name,
+            positions: mesh
+                .positions
+                .chunks_exact(3)
+                .map(|p| [p[0], p[1], p[2]])
+                .collect(),
+            tex_coords: mesh
+                .texcoords
+                .chunks_exact(2)
+                .map(|t| [t[0], t[1]])
+                .collect(),
+            normals: mesh
+                .normals
+                .chunks_exact(3)
+                .map(|n| [n[0], n[1], n[2]])
+                .collect(),
+            faces,
+            material_index: mesh.material_id,
+        }
+    }
+}
+
+/// An OBJ Mesh which keeps its polygons instead of splitting them into Triangles
+///
+/// Subdivision surfaces like Catmull-Clark need the original quads, Which [`crate::Model3D::load`] triangulates by default.
+/// The corners of all faces are stored one after another in `indices`, `face_sizes` says how many corners each face has
+#[derive(Clone, Debug, Default)]
+pub struct PolygonMesh {
+    /// Name of the OBJ object or group
+    pub name: String,
+    /// Vertices of the Mesh, Without `obj_single_index` every face corner has its own Vertex
+    pub vertices: Vec<Vertex>,
+    /// Corners of all faces flattened into one list, Indexing into `vertices`
+    pub indices: Vec<u32>,
+    /// Number of corners of every face, Every face has 3 or more corners
+    pub face_sizes: Vec<u32>,
+    /// Material index in the loaded MTL file
+    pub material_index: Option<usize>,
+}
+
+impl PolygonMesh {
+    /// Loads all Meshes of an OBJ File keeping quads and other polygons as they are
+    ///
+    /// `obj_single_index`, `max_vertices` and `max_indices` of the options are respected, The other options are ignored.
+    /// Points and lines are skipped. Materials are not loaded, `material_index` matches the Materials of [`crate::Model3D::load`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{LoadOptions, PolygonMesh};
+    ///
+    /// let meshes = PolygonMesh::load_obj("model.obj", &LoadOptions::default()).expect("Failed to load");
+    /// let quads = meshes[0].face_sizes.iter().filter(|&&size| size == 4).count();
+    /// println!("{quads} quads");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an Error is loading the OBJ File was unsuccessful
+    pub fn load_obj<P: AsRef<Path>>(
+        path: P,
+        options: &LoadOptions,
+    ) -> Result<Vec<Self>, ModelError> {
+        let load_options = tobj::LoadOptions {
+            single_index: options.obj_single_index,
+            triangulate: false,
+            ignore_points: true,
+            ignore_lines: true,
+        };
+        let path = path.as_ref();
+        let mut reader = std::io::BufReader::new(crate::source::open(path)?);
+        let model_dir = path.parent().unwrap_or_else(|| Path::new("./"));
+        let (models, _) = parse(&mut reader, Some(model_dir), load_options, options)
+            .map_err(|e| ModelError::ModelParsing(format!("{e}")))?;
+        models
+            .into_iter()
+            .map(|model| Self::from_model(model, options))
+            .collect()
+    }
+
+    fn from_model(model: tobj::Model, options: &LoadOptions) -> Result<Self, ModelError> {
+        let mesh = model.mesh;
+        options.check_vertices(mesh.positions.len() / 3)?;
+        options.check_indices(mesh.indices.len())?;
+        validate_indices(
+            mesh.positions.len() / 3,
+            &crate::Indices::U32(mesh.indices.clone()),
+        )?;
+        let (vertices, indices) = if options.obj_single_index {
+            (load_mesh(&mesh), mesh.indices.clone())
+        } else {
+            validate_indices(
+                mesh.texcoords.len() / 2,
+                &crate::Indices::U32(mesh.texcoord_indices.clone()),
+            )?;
+            validate_indices(
+                mesh.normals.len() / 3,
+                &crate::Indices::U32(mesh.normal_indices.clone()),
+            )?;
+            let corners = u32::try_from(mesh.indices.len()).map_err(|_| {
+                ModelError::ModelParsing("Corner count does not fit into u32".to_string())
+            })?;
+            (load_corners(&mesh), (0..corners).collect())
+        };
+        // tobj leaves the arities empty when every face is a Triangle
+        let face_sizes = if mesh.face_arities.is_empty() {
+            vec![3; indices.len() / 3]
+        } else {
+            mesh.face_arities.clone()
+        };
+        Ok(Self {
+            name: model.name,
+            vertices,
+            indices,
+            face_sizes,
+            material_index: mesh.material_id,
+        })
+    }
+}
@@ -0,0 +1,352 @@
+use std::{cell::RefCell, path::PathBuf, sync::Arc, time::Duration};
+
+use crate::ModelError;
+
+/// Fetches the bytes of a texture by its URI, See [`LoadOptions::texture_resolver`]
+pub type TextureResolver = Box<dyn Fn(&str) -> Option<Vec<u8>>>;
+
+/// A [`TextureResolver`] which can be shared with a worker thread, See [`TextureTimeout`]
+pub type SharedTextureResolver = Arc<dyn Fn(&str) -> Option<Vec<u8>> + Send + Sync>;
+
+/// A texture resolver whose calls are bounded by a timeout, See [`LoadOptions::texture_timeout`]
+///
+/// The resolver runs on a worker thread, So unlike [`TextureResolver`] it has to be `Send` and `Sync`
+pub struct TextureTimeout {
+    /// Longest time a single call of the `resolver` may take
+    pub duration: Duration,
+    /// Fetches the bytes of a texture by its URI, Like [`LoadOptions::texture_resolver`]
+    pub resolver: SharedTextureResolver,
+}
+
+/// Options to control how a Model is loaded, Used by [`crate::Model3D::load_with_options`]
+///
+/// # Examples
+///
+/// ```no_run
+/// use modelz::{LoadOptions, Model3D};
+///
+/// let options = LoadOptions {
+///     obj_single_index: false,
+///     ..Default::default()
+/// };
+/// let model = Model3D::load_with_options("model.obj", &options).expect("Failed to load");
+/// ```
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "The OBJ toggles map directly to the tobj options"
+)]
+pub struct LoadOptions {
+    /// OBJ: Merge the separate position, texture coordinate and normal indices into a single index (default `true`)
+    ///
+    /// When enabled every unique position/texture coordinate/normal combination becomes one Vertex and the Mesh is indexed,
+    /// This is what a GPU wants and uses the least memory.
+    /// When disabled every face corner becomes its own Vertex and the Mesh is not indexed, This uses more memory
+    /// but keeps every corner separate, which is useful for editing per corner attributes
+    pub obj_single_index: bool,
+    /// OBJ: Let tobj turn every face into Triangles (default `true`)
+    ///
+    /// Points and lines which are not ignored become zero area Triangles. When disabled points and lines are loaded
+    /// as separate `RenderMode::Points` and `RenderMode::Lines` Meshes instead, Polygons are still split into Triangle fans
+    pub obj_triangulate: bool,
+    /// OBJ: Skip `p` point elements and Faces with a single Vertex (default `true`)
+    pub obj_ignore_points: bool,
+    /// OBJ: Skip `l` line elements and Faces with two Vertices (default `true`)
+    ///
+    /// Polylines with more than two Vertices are split into segments
+    pub obj_ignore_lines: bool,
+    /// Maximum number of Vertices a single Mesh may declare, `None` means no limit (default)
+    ///
+    /// A small File can declare huge element counts, Setting the limits protects against running out of memory
+    /// when loading untrusted Files. The limits are checked before the data is allocated, Exceeding one fails with
+    /// [`ModelError::ModelParsing`]. Gzip compressed Files are decompressed into memory first without a limit,
+    /// So check the size of untrusted `.gz` Files before loading them
+    pub max_vertices: Option<usize>,
+    /// Maximum number of Indices a single Mesh may declare, `None` means no limit (default)
+    pub max_indices: Option<usize>,
+    /// Maximum size in bytes of a single embedded texture, `None` means no limit (default)
+    pub max_texture_bytes: Option<usize>,
+    /// OBJ: Load this MTL File instead of the one referenced by the OBJ File (default `None`)
+    ///
+    /// Texture paths inside the MTL File are resolved relative to the MTL File
+    pub mtl_override: Option<PathBuf>,
+    /// glTF: Only load Meshes whose name is in the list (default `None`, Which loads all Meshes)
+    ///
+    /// The primitives of skipped Meshes are never read, Which saves time and memory on big Files. Meshes without a name are skipped
+    pub mesh_filter: Option<Vec<String>>,
+    /// glTF: Keep primitives without Vertices as empty Meshes (default `false`)
+    ///
+    /// Empty primitives draw nothing and most code expects Meshes to have Vertices, So they are skipped by default.
+    /// Enable this if the Mesh indices have to match the primitives of the File. Only primitives whose POSITION accessor has
+    /// no Vertices are empty, A POSITION accessor whose Vertices can not be read fails the load
+    pub keep_empty_primitives: bool,
+    /// glTF: Skip nodes whose `extras` contain `"hidden": true` together with their children (default `false`)
+    ///
+    /// Helper and hidden geometry exported by DCC tools is left out this way, Meshes which are only referenced by
+    /// hidden nodes are not loaded at all. Cameras and Lights of hidden nodes are skipped as well
+    #[cfg(feature = "extras")]
+    pub respect_hidden_extras: bool,
+    /// glTF: Keep the parsed document in the Model, See [`crate::Model3D::gltf_document`] (default `false`)
+    #[cfg(feature = "gltf")]
+    pub retain_source: bool,
+    /// Keep the bytes of the loaded File in the Model, See [`crate::Model3D::unmodified_source`] (default `false`)
+    ///
+    /// The loader reads the whole File into memory and parses that copy, So the bytes take as much memory as the File
+    pub retain_source_bytes: bool,
+    /// Convert `TriangleStrip` and `TriangleFan` Meshes into indexed `Triangles` lists after loading (default `false`)
+    ///
+    /// Most utilities and renderers expect Triangle lists, See [`crate::Mesh::triangulate`].
+    /// Only glTF can contain strips and fans
+    pub triangulate: bool,
+    /// STL: How faces whose stored normal opposes their winding are repaired (default [`crate::StlWinding::Keep`])
+    ///
+    /// The number of such faces is added as a [`ModelError::ModelParsing`] to `Model3D::warnings` in every mode
+    #[cfg(feature = "stl")]
+    pub stl_winding: crate::StlWinding,
+    /// Remove Triangles without area after loading, The value is the epsilon (default `None`, Which keeps them)
+    ///
+    /// See [`crate::Mesh::remove_degenerate_triangles`] for what counts as degenerate, `Some(0.0)` only removes exactly collinear Triangles.
+    /// Every Mesh which lost Triangles adds a [`ModelError::ModelParsing`] with the count to `Model3D::warnings`
+    pub remove_degenerate_triangles: Option<f32>,
+    /// Called with the URI of every texture which is not embedded to fetch its bytes (default `None`)
+    ///
+    /// Useful for virtual filesystems, archives or remapped asset paths. Returned bytes become an [`crate::Image::Memory`],
+    /// When the resolver returns `None` the texture is loaded as a [`crate::Image::Path`] relative to the Model as usual
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{LoadOptions, Model3D};
+    ///
+    /// let options = LoadOptions {
+    ///     texture_resolver: Some(Box::new(|uri| std::fs::read(format!("assets/{uri}")).ok())),
+    ///     ..Default::default()
+    /// };
+    /// let model = Model3D::load_with_options("model.gltf", &options).expect("Failed to load");
+    /// ```
+    pub texture_resolver: Option<TextureResolver>,
+    /// Resolves textures on a worker thread and gives up on a texture after the timeout (default `None`)
+    ///
+    /// Used instead of `texture_resolver` when set. When a call takes longer the texture falls back to its File path and a
+    /// [`ModelError::MaterialLoad`] is added to `Model3D::warnings`. The worker can not be cancelled, It keeps running
+    /// in the background and its result is dropped. Textures loaded from a path are only read when they are decoded,
+    /// So loading them is never bounded
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// use modelz::{LoadOptions, Model3D, TextureTimeout};
+    ///
+    /// let options = LoadOptions {
+    ///     texture_timeout: Some(TextureTimeout {
+    ///         duration: Duration::from_secs(5),
+    ///         resolver: Arc::new(|uri| std::fs::read(format!("assets/{uri}")).ok()),
+    ///     }),
+    ///     ..Default::default()
+    /// };
+    /// let model = Model3D::load_with_options("model.gltf", &options).expect("Failed to load");
+    /// ```
+    pub texture_timeout: Option<TextureTimeout>,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            obj_single_index: true,
+            obj_triangulate: true,
+            obj_ignore_points: true,
+            obj_ignore_lines: true,
+            max_vertices: None,
+            max_indices: None,
+            max_texture_bytes: None,
+            mtl_override: None,
+            mesh_filter: None,
+            keep_empty_primitives: false,
+            #[cfg(feature = "extras")]
+            respect_hidden_extras: false,
+            #[cfg(feature = "gltf")]
+            retain_source: false,
+            retain_source_bytes: false,
+            triangulate: false,
+            #[cfg(feature = "stl")]
+            stl_winding: crate::StlWinding::Keep,
+            remove_degenerate_triangles: None,
+            texture_resolver: None,
+            texture_timeout: None,
+        }
+    }
+}
+
+impl LoadOptions {
+    #[cfg(any(
+        feature = "obj",
+        feature = "gltf",
+        feature = "stl",
+        feature = "ply",
+        feature = "x3d",
+        feature = "usd"
+    ))]
+    pub(crate) fn check_vertices(&self, count: usize) -> Result<(), ModelError> {
+        check_limit("Vertex", count, self.max_vertices)
+    }
+
+    #[cfg(any(
+        feature = "obj",
+        feature = "gltf",
+        feature = "ply",
+        feature = "x3d",
+        feature = "usd"
+    ))]
+    pub(crate) fn check_indices(&self, count: usize) -> Result<(), ModelError> {
+        check_limit("Index", count, self.max_indices)
+    }
+
+    #[cfg(any(feature = "obj", feature = "gltf", feature = "x3d", feature = "usd"))]
+    pub(crate) fn check_texture_bytes(&self, count: usize) -> Result<(), ModelError> {
+        check_limit("Texture byte", count, self.max_texture_bytes)
+    }
+}
+
+/// State of a single load, Handed to the loaders instead of the bare [`LoadOptions`]
+///
+/// Every load has its own context, So nested loads (e.g. from a texture resolver) keep their warnings apart
+pub struct LoadContext<'a> {
+    pub options: &'a LoadOptions,
+    /// Problems which did not stop the loading, Moved into `Model3D::warnings` once the loader returns
+    warnings: RefCell<Vec<ModelError>>,
+    /// The raw bytes of the Model File, Only kept with `retain_source_bytes`
+    source: RefCell<Option<Arc<[u8]>>>,
+    /// The content of a Model loaded from memory, Read instead of the File
+    data: Option<Arc<[u8]>>,
+}
+
+impl<'a> LoadContext<'a> {
+    pub const fn new(options: &'a LoadOptions) -> Self {
+        Self {
+            options,
+            warnings: RefCell::new(Vec::new()),
+            source: RefCell::new(None),
+            data: None,
+        }
+    }
+
+    /// Creates the context of a Model loaded from memory, See [`crate::Model3D::from_bytes_with_options`]
+    pub const fn from_bytes(options: &'a LoadOptions, data: Arc<[u8]>) -> Self {
+        Self {
+            options,
+            warnings: RefCell::new(Vec::new()),
+            source: RefCell::new(None),
+            data: Some(data),
+        }
+    }
+
+    /// Returns the content of a Model loaded from memory, `None` when the Model File is read
+    pub const fn data(&self) -> Option<&Arc<[u8]>> {
+        self.data.as_ref()
+    }
+
+    /// Returns the directory other Files referenced by the Model File are read from
+    ///
+    /// Models loaded from memory have no directory, So they can not read other Files (e.g. MTL Files or glTF Buffers)
+    #[cfg(any(feature = "obj", feature = "gltf"))]
+    pub fn model_dir<'p>(&self, path: &'p std::path::Path) -> Option<&'p std::path::Path> {
+        if self.data.is_some() {
+            return None;
+        }
+        Some(path.parent().unwrap_or_else(|| std::path::Path::new("./")))
+    }
+
+    /// Records a problem which does not stop the loading
+    #[cfg(any(
+        feature = "obj",
+        feature = "gltf",
+        feature = "stl",
+        feature = "x3d",
+        feature = "usd"
+    ))]
+    pub fn warn(&self, warning: ModelError) {
+        log::warn!("{warning:?}");
+        self.warnings.borrow_mut().push(warning);
+    }
+
+    /// Keeps the raw bytes of the Model File, See [`crate::source::open_model`]
+    pub fn retain_source(&self, bytes: Arc<[u8]>) {
+        *self.source.borrow_mut() = Some(bytes);
+    }
+
+    /// Returns the warnings and the retained source bytes
+    pub fn finish(self) -> (Vec<ModelError>, Option<Arc<[u8]>>) {
+        (self.warnings.into_inner(), self.source.into_inner())
+    }
+
+    /// Fetches a texture through the `texture_timeout` or `texture_resolver`, `None` if there is no resolver or it does not know the URI
+    #[cfg(any(feature = "obj", feature = "gltf", feature = "x3d", feature = "usd"))]
+    pub fn resolve_texture(&self, uri: &str) -> Result<Option<crate::Image>, ModelError> {
+        let data = match (&self.texture_timeout, &self.texture_resolver) {
+            (Some(timeout), _) => self.resolve_with_timeout(timeout, uri)?,
+            (None, Some(resolve)) => resolve(uri),
+            (None, None) => None,
+        };
+        let Some(data) = data else {
+            return Ok(None);
+        };
+        self.check_texture_bytes(data.len())?;
+        let mime_type = crate::sniff::sniff_mime_type(&data);
+        Ok(Some(crate::Image::Memory { data, mime_type }))
+    }
+
+    /// Runs the resolver on a worker thread, A timeout is recorded as a warning and returns `None`
+    #[cfg(any(feature = "obj", feature = "gltf", feature = "x3d", feature = "usd"))]
+    fn resolve_with_timeout(
+        &self,
+        timeout: &TextureTimeout,
+        uri: &str,
+    ) -> Result<Option<Vec<u8>>, ModelError> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let resolve = Arc::clone(&timeout.resolver);
+        let owned_uri = uri.to_string();
+        std::thread::spawn(move || {
+            // The receiver is gone after a timeout, The late result is dropped
+            let _ = sender.send(resolve(&owned_uri));
+        });
+        match receiver.recv_timeout(timeout.duration) {
+            Ok(data) => Ok(data),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                self.warn(ModelError::MaterialLoad(format!(
+                    "Resolving texture {uri} timed out after {:?}",
+                    timeout.duration
+                )));
+                Ok(None)
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => Err(ModelError::MaterialLoad(
+                format!("Texture resolver panicked on {uri}"),
+            )),
+        }
+    }
+}
+
+impl std::ops::Deref for LoadContext<'_> {
+    type Target = LoadOptions;
+
+    fn deref(&self) -> &LoadOptions {
+        self.options
+    }
+}
+
+#[cfg(any(
+    feature = "obj",
+    feature = "gltf",
+    feature = "stl",
+    feature = "ply",
+    feature = "x3d",
+    feature = "usd"
+))]
+fn check_limit(what: &str, count: usize, limit: Option<usize>) -> Result<(), ModelError> {
+    match limit {
+        Some(limit) if count > limit => Err(ModelError::ModelParsing(format!(
+            "{what} count {count} exceeds the limit of {limit}"
+        ))),
+        _ => Ok(()),
+    }
+}
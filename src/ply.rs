@@ -1,4 +1,10 @@
-use std::{fs::File, path::Path};
+//! PLY loading, See [`stream`] for reading huge point clouds without keeping them in memory
+
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, Cursor, Read},
+    path::Path,
+};
 
 extern crate ply_rs;
 use ply_rs::{
@@ -6,7 +12,7 @@ use ply_rs::{
     ply::{Property, PropertyAccess},
 };
 
-use crate::{Model3D, ModelError};
+use crate::{mesh::validate_indices, options::LoadContext, Indices, Model3D, ModelError};
 
 #[derive(Debug, Default, Clone, Copy)]
 struct Vertex {
@@ -26,16 +32,16 @@ impl PropertyAccess for Vertex {
     }
     fn set_property(&mut self, key: String, property: Property) {
         match (key.as_ref(), property) {
-            ("x", Property::Float(v)) => self.x = v,
-            ("y", Property::Float(v)) => self.y = v,
-            ("z", Property::Float(v)) => self.z = v,
+            ("x", property) => self.x = property_float(&property).unwrap_or_default(),
+            ("y", property) => self.y = property_float(&property).unwrap_or_default(),
+            ("z", property) => self.z = property_float(&property).unwrap_or_default(),
 
-            ("nx", Property::Float(v)) => self.x_norm = Some(v),
-            ("ny", Property::Float(v)) => self.y_norm = Some(v),
-            ("nz", Property::Float(v)) => self.z_norm = Some(v),
+            ("nx", property) => self.x_norm = property_float(&property),
+            ("ny", property) => self.y_norm = property_float(&property),
+            ("nz", property) => self.z_norm = property_float(&property),
             // NOTE: Blender3D exports texture coordinates as s,t tuples
-            ("u" | "s" | "tx" | "texture_u", Property::Float(v)) => self.tex_x = Some(v),
-            ("v" | "t" | "ty" | "texture_v", Property::Float(v)) => self.tex_y = Some(v),
+            ("u" | "s" | "tx" | "texture_u", property) => self.tex_x = property_float(&property),
+            ("v" | "t" | "ty" | "texture_v", property) => self.tex_y = property_float(&property),
             (k, _) => eprintln!("Vertex: Unexpected key/value combination: key: {k}"),
         }
     }
@@ -44,34 +50,151 @@ impl PropertyAccess for Vertex {
 #[derive(Debug)]
 struct Face {
     vertex_index: Vec<u32>,
+    material_index: Option<usize>,
+    color: [Option<f32>; 4],
 }
 
 impl PropertyAccess for Face {
     fn new() -> Self {
         Self {
             vertex_index: Vec::new(),
+            material_index: None,
+            color: [None; 4],
         }
     }
     fn set_property(&mut self, key: String, property: Property) {
         match (key.as_ref(), property) {
-            ("vertex_index" | "vertex_indices", Property::ListUInt(vec)) => self.vertex_index = vec,
+            ("vertex_index" | "vertex_indices", property) => {
+                self.vertex_index = property_indices(property);
+            }
+            ("material_index" | "material", property) => {
+                self.material_index = property_index(&property);
+            }
+            ("red", property) => self.color[0] = property_color(&property),
+            ("green", property) => self.color[1] = property_color(&property),
+            ("blue", property) => self.color[2] = property_color(&property),
+            ("alpha", property) => self.color[3] = property_color(&property),
             (k, _) => eprintln!("Face: Unexpected key/value combination: key: {k}"),
         }
     }
 }
 
-pub fn load(path: &Path) -> Result<Model3D, ModelError> {
-    let mut file = File::open(path).map_err(|e| ModelError::OpenFile(e.to_string()))?;
-    let mut reader = std::io::BufReader::new(&mut file);
+impl Face {
+    /// Corners of the Triangles of the face, Polygons are split into a Triangle fan around the first Vertex
+    fn triangle_corners(&self) -> impl Iterator<Item = u32> + '_ {
+        let first = self.vertex_index[0];
+        self.vertex_index[1..]
+            .windows(2)
+            .flat_map(move |edge| [first, edge[0], edge[1]])
+    }
+
+    /// RGBA color of the face, Alpha defaults to 1.0
+    const fn color(&self) -> Option<[f32; 4]> {
+        match self.color {
+            [Some(r), Some(g), Some(b), alpha] => {
+                Some([r, g, b, if let Some(a) = alpha { a } else { 1.0 }])
+            }
+            _ => None,
+        }
+    }
+}
+
+fn property_index(property: &Property) -> Option<usize> {
+    match *property {
+        Property::Char(v) => usize::try_from(v).ok(),
+        Property::UChar(v) => Some(v.into()),
+        Property::Short(v) => usize::try_from(v).ok(),
+        Property::UShort(v) => Some(v.into()),
+        Property::Int(v) => usize::try_from(v).ok(),
+        Property::UInt(v) => usize::try_from(v).ok(),
+        _ => None,
+    }
+}
+
+/// Double precision values are rounded to f32
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "Vertex attributes are stored as f32"
+)]
+const fn property_float(property: &Property) -> Option<f32> {
+    match *property {
+        Property::Float(v) => Some(v),
+        Property::Double(v) => Some(v as f32),
+        _ => None,
+    }
+}
+
+/// Face indices of any integer list type, Negative indices become `u32::MAX` so they fail validation
+fn property_indices(property: Property) -> Vec<u32> {
+    fn convert<T: TryInto<u32>>(values: Vec<T>) -> Vec<u32> {
+        values
+            .into_iter()
+            .map(|v| v.try_into().unwrap_or(u32::MAX))
+            .collect()
+    }
+    match property {
+        Property::ListChar(vec) => convert(vec),
+        Property::ListUChar(vec) => convert(vec),
+        Property::ListShort(vec) => convert(vec),
+        Property::ListUShort(vec) => convert(vec),
+        Property::ListInt(vec) => convert(vec),
+        Property::ListUInt(vec) => vec,
+        _ => Vec::new(),
+    }
+}
+
+/// Integer colors are normalized to 0.0..=1.0
+fn property_color(property: &Property) -> Option<f32> {
+    match *property {
+        Property::UChar(v) => Some(f32::from(v) / 255.0),
+        Property::UShort(v) => Some(f32::from(v) / 65535.0),
+        Property::Float(v) => Some(v),
+        _ => None,
+    }
+}
+
+const fn convert_vertex(vertex: &Vertex) -> crate::Vertex {
+    let normal = match (vertex.x_norm, vertex.y_norm, vertex.z_norm) {
+        (Some(x), Some(y), Some(z)) => Some([x, y, z]),
+        _ => None,
+    };
+    let tex_coord = match (vertex.tex_x, vertex.tex_y) {
+        (Some(x), Some(y)) => Some([x, y]),
+        _ => None,
+    };
+    crate::Vertex {
+        position: [vertex.x, vertex.y, vertex.z],
+        tex_coord,
+        color: None,
+        normal,
+        tangent: None,
+    }
+}
+
+/// Loads a PLY File
+///
+/// Faces with a `red`/`green`/`blue` (and optional `alpha`) property color all their Vertices.
+/// Faces with a `material_index` property are split into one Mesh per index. PLY has no Materials, So every index
+/// gets a placeholder Material named `material_{index}` which the Mesh points at, In the order the indices first appear sorted ascending.
+/// Faces with more than three Vertices are split into Triangle fans.
+/// Every Triangle corner becomes its own Vertex, `original_indices` maps them back to the Vertices of the File.
+/// Files without faces are point clouds, They become a single `RenderMode::Points` Mesh of all Vertices
+pub(crate) fn load(path: &Path, options: &LoadContext) -> Result<Model3D, ModelError> {
+    let mut reader = std::io::BufReader::new(crate::source::open_model(path, options)?);
 
+    let parse_error = |e: std::io::Error| ModelError::ModelParsing(e.to_string());
     // Create a parser for each struct. Parsers are cheap objects.
     let vertex_parser = ply::parser::Parser::<Vertex>::new();
     let face_parser = ply::parser::Parser::<Face>::new();
+    let other_parser = ply::parser::Parser::<ply::ply::DefaultElement>::new();
 
     // lets first consume the header
     // We also could use `face_parser`, The configuration is a parser's only state.
     // The reading position only depends on `f`.
-    let header = vertex_parser.read_header(&mut reader).unwrap();
+    let mut reader = Cursor::new(check_header(&mut reader)?).chain(reader);
+    let header = vertex_parser
+        .read_header(&mut reader)
+        .map_err(parse_error)?;
 
     // Depending on the header, read the data into our structs..
     let mut vertex_list = Vec::new();
@@ -80,98 +203,204 @@ pub fn load(path: &Path) -> Result<Model3D, ModelError> {
         // we could also just parse them in sequence, but the file format might change
         match element.name.as_ref() {
             "vertex" => {
+                options.check_vertices(element.count)?;
                 vertex_list = vertex_parser
                     .read_payload_for_element(&mut reader, element, &header)
-                    .unwrap();
+                    .map_err(parse_error)?;
             }
             "face" => {
+                options.check_indices(element.count.saturating_mul(3))?;
                 face_list = face_parser
                     .read_payload_for_element(&mut reader, element, &header)
-                    .unwrap();
+                    .map_err(parse_error)?;
+            }
+            // Other elements (e.g. `edge`) are unused, But have to be read to reach the ones behind them
+            _ => {
+                other_parser
+                    .read_payload_for_element(&mut reader, element, &header)
+                    .map_err(parse_error)?;
             }
-            _ => panic!("Enexpeced element!"),
         }
     }
-    let mut vertices = Vec::new();
-    for face in face_list {
-        // Every face (triangle) has 3 Vertices
-        let vertex = vertex_list[face.vertex_index[0] as usize];
-        let vertex1 = vertex_list[face.vertex_index[1] as usize];
-        let vertex2 = vertex_list[face.vertex_index[2] as usize];
-
-        let mut normal = None;
-        let mut tex_coord = None;
-        if let Some(x) = vertex.x_norm {
-            if let Some(y) = vertex.z_norm {
-                if let Some(z) = vertex.z_norm {
-                    normal = Some([x, y, z]);
-                }
+    if let Some(face) = face_list.iter().find(|face| face.vertex_index.len() < 3) {
+        return Err(ModelError::ModelParsing(format!(
+            "Face has only {} Vertices",
+            face.vertex_index.len()
+        )));
+    }
+    if face_list.is_empty() && !vertex_list.is_empty() {
+        let vertices = vertex_list.iter().map(convert_vertex).collect::<Vec<_>>();
+        let count = u32::try_from(vertices.len()).map_err(|_| {
+            ModelError::ModelParsing("Vertex count does not fit into u32".to_string())
+        })?;
+        let mut mesh = crate::Mesh::new(vertices, None, crate::RenderMode::Points);
+        mesh.original_indices = Some((0..count).collect());
+        return Ok(Model3D::new(vec![mesh], vec![], crate::ModelFormat::PLY));
+    }
+    // Faces are split into one Mesh per Material index
+    let mut groups: BTreeMap<Option<usize>, Vec<&Face>> = BTreeMap::new();
+    for face in &face_list {
+        groups.entry(face.material_index).or_default().push(face);
+    }
+    let mut meshes = Vec::new();
+    let mut materials = Vec::new();
+    for (material_index, faces) in groups {
+        let indices = Indices::U32(
+            faces
+                .iter()
+                .flat_map(|face| face.triangle_corners())
+                .collect(),
+        );
+        options.check_indices(indices.len())?;
+        validate_indices(vertex_list.len(), &indices)?;
+        let vertices = faces
+            .iter()
+            .flat_map(|face| {
+                face.triangle_corners().map(|index| {
+                    let mut vertex = convert_vertex(&vertex_list[index as usize]);
+                    vertex.color = face.color();
+                    vertex
+                })
+            })
+            .collect();
+        let mut mesh = crate::Mesh::new(vertices, None, crate::RenderMode::Triangles);
+        mesh.material_index = material_index.map(|index| {
+            materials.push(placeholder_material(format!("material_{index}")));
+            materials.len() - 1
+        });
+        mesh.original_indices = Some(indices.to_u32());
+        meshes.push(mesh);
+    }
+
+    Ok(Model3D::new(meshes, materials, crate::ModelFormat::PLY))
+}
+
+/// Material for a PLY `material_index`, Which only names the Material
+const fn placeholder_material(name: String) -> crate::Material {
+    crate::Material {
+        diffuse_texture: None,
+        normal_texture: None,
+        alpha_mode: crate::AlphaMode::Opaque,
+        alpha_cutoff: None,
+        double_sided: false,
+        base_color: None,
+        transmission_factor: None,
+        transmission_texture: None,
+        thickness_factor: None,
+        attenuation_color: None,
+        attenuation_distance: None,
+        clearcoat_factor: None,
+        clearcoat_roughness_factor: None,
+        clearcoat_texture: None,
+        clearcoat_normal_texture: None,
+        ior: None,
+        sheen_color_factor: None,
+        sheen_roughness_factor: None,
+        specular_factor: None,
+        glossiness_factor: None,
+        specular_glossiness_texture: None,
+        displacement_texture: None,
+        reflection_texture: None,
+        name: Some(name),
+        #[cfg(feature = "extras")]
+        extras: None,
+    }
+}
+
+/// Parses the Vertices of a PLY File one by one and calls `callback` for each, Without collecting them
+///
+/// Useful for point clouds which do not fit into memory, e.g. to downsample them or to write them
+/// directly into a GPU buffer. Faces and all other elements are skipped, Vertex colors are not read
+///
+/// # Examples
+///
+/// ```no_run
+/// let file = std::fs::File::open("scan.ply").expect("Failed to open");
+/// let mut count = 0;
+/// modelz::ply::stream(file, |_vertex| count += 1).expect("Failed to parse");
+/// println!("{count} Vertices");
+/// ```
+/// # Errors
+///
+/// Returns `ModelError::ModelParsing` if the header or a Vertex can not be parsed
+pub fn stream<R: Read, F: FnMut(&crate::Vertex)>(
+    reader: R,
+    mut callback: F,
+) -> Result<(), ModelError> {
+    let parse_error = |e: std::io::Error| ModelError::ModelParsing(e.to_string());
+    let mut reader = std::io::BufReader::new(reader);
+    let vertex_parser = ply::parser::Parser::<Vertex>::new();
+    let other_parser = ply::parser::Parser::<ply::ply::DefaultElement>::new();
+    let mut reader = Cursor::new(check_header(&mut reader)?).chain(reader);
+    let header = vertex_parser
+        .read_header(&mut reader)
+        .map_err(parse_error)?;
+
+    for element in header.elements.values() {
+        if element.name == "vertex" {
+            for _ in 0..element.count {
+                let vertex = read_element(&vertex_parser, &header, element, &mut reader)
+                    .map_err(parse_error)?;
+                callback(&convert_vertex(&vertex));
             }
+            return Ok(());
         }
-        if let Some(x) = vertex.tex_x {
-            if let Some(y) = vertex.tex_y {
-                tex_coord = Some([x, y]);
-            }
+        // Elements before the Vertices still have to be read to reach them
+        for _ in 0..element.count {
+            read_element(&other_parser, &header, element, &mut reader).map_err(parse_error)?;
         }
+    }
+    Ok(())
+}
 
-        let v1 = crate::Vertex {
-            position: [vertex.x, vertex.y, vertex.z],
-            tex_coord,
-            color: None,
-            normal,
-        };
-        if let Some(x) = vertex1.x_norm {
-            if let Some(y) = vertex1.z_norm {
-                if let Some(z) = vertex1.z_norm {
-                    normal = Some([x, y, z]);
-                }
-            }
-        }
-        if let Some(x) = vertex1.tex_x {
-            if let Some(y) = vertex1.tex_y {
-                tex_coord = Some([x, y]);
-            }
+/// Reads the header and checks its numbers before `ply_rs` parses it, It panics on numbers which do not fit into `u64`
+///
+/// Returns the bytes read, Which have to be chained in front of the reader again since it can not be rewound
+fn check_header(reader: &mut impl BufRead) -> Result<Vec<u8>, ModelError> {
+    let parse_error = |e: std::io::Error| ModelError::ModelParsing(e.to_string());
+    let mut header = Vec::new();
+    loop {
+        let start = header.len();
+        if reader.read_until(b'\n', &mut header).map_err(parse_error)? == 0 {
+            break;
         }
-        let v2 = crate::Vertex {
-            position: [vertex1.x, vertex1.y, vertex1.z],
-            tex_coord,
-            color: None,
-            normal,
-        };
-        if let Some(x) = vertex2.x_norm {
-            if let Some(y) = vertex2.z_norm {
-                if let Some(z) = vertex2.z_norm {
-                    normal = Some([x, y, z]);
-                }
-            }
+        let line = String::from_utf8_lossy(&header[start..]);
+        // Without the magic word `ply_rs` fails on the first line, So the rest is not buffered
+        if line.trim_end() == "end_header" || (start == 0 && line.trim_end() != "ply") {
+            break;
         }
-        if let Some(x) = vertex2.tex_x {
-            if let Some(y) = vertex2.tex_y {
-                tex_coord = Some([x, y]);
+        // Counts and the version are the only numbers `ply_rs` parses in the header
+        if line.starts_with("element") || line.starts_with("format") {
+            let too_large = line
+                .split(|c: char| c.is_whitespace() || c == '.')
+                .filter(|word| !word.is_empty() && word.bytes().all(|b| b.is_ascii_digit()))
+                .find(|number| number.parse::<u64>().is_err());
+            if let Some(number) = too_large {
+                return Err(ModelError::ModelParsing(format!(
+                    "Header number {number} is too large"
+                )));
             }
         }
-        let v3 = crate::Vertex {
-            position: [vertex2.x, vertex2.y, vertex2.z],
-            tex_coord,
-            color: None,
-            normal,
-        };
-
-        vertices.push(v1);
-        vertices.push(v2);
-        vertices.push(v3);
-    }
-    let mesh = crate::Mesh {
-        vertices,
-        indices: None,
-        material_index: None,
-        mode: crate::RenderMode::TriangleFan,
-        name: None,
-    };
+    }
+    Ok(header)
+}
 
-    Ok(Model3D {
-        meshes: vec![mesh],
-        materials: vec![],
-        format: crate::ModelFormat::PLY,
-    })
+/// Reads a single element in the encoding of the File
+fn read_element<E: PropertyAccess>(
+    parser: &ply::parser::Parser<E>,
+    header: &ply::ply::Header,
+    element: &ply::ply::ElementDef,
+    reader: &mut impl BufRead,
+) -> std::io::Result<E> {
+    match header.encoding {
+        ply::ply::Encoding::Ascii => {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            parser.read_ascii_element(&line, element)
+        }
+        ply::ply::Encoding::BinaryBigEndian => parser.read_big_endian_element(reader, element),
+        ply::ply::Encoding::BinaryLittleEndian => {
+            parser.read_little_endian_element(reader, element)
+        }
+    }
 }
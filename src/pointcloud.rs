@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use crate::{math, Mesh, RenderMode, Vertex};
+
+/// Running sums of all Vertices inside one voxel
+struct Cell {
+    count: f32,
+    position: [f32; 3],
+    color: Option<[f32; 4]>,
+    tex_coord: Option<[f32; 2]>,
+    normal: Option<[f32; 3]>,
+}
+
+impl Cell {
+    const fn new(vertex: &Vertex) -> Self {
+        Self {
+            count: 1.0,
+            position: vertex.position,
+            color: vertex.color,
+            tex_coord: vertex.tex_coord,
+            normal: vertex.normal,
+        }
+    }
+
+    fn add(&mut self, vertex: &Vertex) {
+        self.count += 1.0;
+        self.position = math::add(self.position, vertex.position);
+        self.color = sum(self.color, vertex.color);
+        self.tex_coord = sum(self.tex_coord, vertex.tex_coord);
+        self.normal = sum(self.normal, vertex.normal);
+    }
+
+    fn average(&self) -> Vertex {
+        Vertex {
+            position: math::scale(self.position, 1.0 / self.count),
+            color: self.color.map(|sum| sum.map(|x| x / self.count)),
+            tex_coord: self.tex_coord.map(|sum| sum.map(|x| x / self.count)),
+            normal: self.normal.map(math::normalize),
+            tangent: None,
+        }
+    }
+}
+
+/// Sums an optional attribute, `None` as soon as one Vertex is missing it
+fn sum<const N: usize>(a: Option<[f32; N]>, b: Option<[f32; N]>) -> Option<[f32; N]> {
+    let (a, b) = (a?, b?);
+    Some(std::array::from_fn(|k| a[k] + b[k]))
+}
+
+impl Mesh {
+    /// Replaces all points inside each cubic voxel of `cell_size` with a single point at their average
+    ///
+    /// Colors and texture coordinates are averaged, Normals are averaged and renormalized. Attributes only some points
+    /// of a voxel have are dropped for that voxel, Tangents and Morph Targets are dropped. The Mesh is not indexed afterwards.
+    /// Only `Points` Meshes are downsampled, Other Meshes and a `cell_size` which is not positive stay untouched
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let mut model = Model3D::load("scan.ply").expect("Failed to load");
+    /// model.meshes[0].voxel_downsample(0.05);
+    /// ```
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "Voxel coordinates of sensible cell sizes fit into i64"
+    )]
+    pub fn voxel_downsample(&mut self, cell_size: f32) {
+        if self.mode != RenderMode::Points || cell_size <= 0.0 || !cell_size.is_finite() {
+            return;
+        }
+        let mut cells: Vec<Cell> = Vec::new();
+        let mut lookup: HashMap<[i64; 3], usize> = HashMap::new();
+        for i in 0..self.element_count() {
+            let vertex = &self.vertices[self.index(i)];
+            let key = vertex.position.map(|x| (x / cell_size).floor() as i64);
+            if let Some(&cell) = lookup.get(&key) {
+                cells[cell].add(vertex);
+            } else {
+                lookup.insert(key, cells.len());
+                cells.push(Cell::new(vertex));
+            }
+        }
+        self.vertices = cells.iter().map(Cell::average).collect();
+        self.indices = None;
+        self.bounds = None;
+        self.original_indices = None;
+        self.morph_targets.clear();
+        self.morph_weights.clear();
+    }
+
+    /// Estimates a normal for every point from a plane fitted through its `k` nearest neighbors
+    ///
+    /// The plane is fitted with a principal component analysis, Its normal is the direction of least variance.
+    /// Normals are oriented away from the centroid of the cloud, Which works well for closed scans. Points lying in a plane
+    /// through the centroid get normals with a positive largest component instead. `k` is at least 3.
+    /// Only `Points` Meshes get normals, Other Meshes stay untouched
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let mut model = Model3D::load("scan.ply").expect("Failed to load");
+    /// model.meshes[0].estimate_point_normals(12);
+    /// ```
+    pub fn estimate_point_normals(&mut self, k: usize) {
+        if self.mode != RenderMode::Points || self.vertices.is_empty() {
+            return;
+        }
+        let positions = self.vertices.iter().map(|v| v.position).collect::<Vec<_>>();
+        #[expect(clippy::cast_precision_loss, reason = "Only used for averaging")]
+        let count = positions.len() as f32;
+        let centroid = math::scale(
+            positions.iter().fold([0.0; 3], |sum, &p| math::add(sum, p)),
+            1.0 / count,
+        );
+        let tree = self.build_kdtree();
+        for i in 0..self.vertices.len() {
+            let position = self.vertices[i].position;
+            let neighbors = tree.k_nearest(position, k.max(3));
+            let normal = plane_normal(
+                &neighbors
+                    .iter()
+                    .map(|&n| self.vertices[n].position)
+                    .collect::<Vec<_>>(),
+            );
+            let outward = math::sub(position, centroid);
+            let side = math::dot(normal, outward);
+            let flip = if side.abs() > 1e-3 * math::length(outward) {
+                side < 0.0
+            } else {
+                // No side to face, Pick a deterministic orientation
+                let largest = (0..3)
+                    .max_by(|&a, &b| normal[a].abs().total_cmp(&normal[b].abs()))
+                    .unwrap_or(0);
+                normal[largest] < 0.0
+            };
+            self.vertices[i].normal = Some(if flip {
+                math::scale(normal, -1.0)
+            } else {
+                normal
+            });
+        }
+    }
+}
+
+/// Normal of the plane fitted through the points, The eigenvector of the smallest covariance eigenvalue
+fn plane_normal(points: &[[f32; 3]]) -> [f32; 3] {
+    #[expect(clippy::cast_precision_loss, reason = "Only used for averaging")]
+    let count = points.len() as f32;
+    let mean = math::scale(
+        points.iter().fold([0.0; 3], |sum, &p| math::add(sum, p)),
+        1.0 / count,
+    );
+    let mut covariance = [[0.0_f32; 3]; 3];
+    for &point in points {
+        let d = math::sub(point, mean);
+        for (row, &a) in covariance.iter_mut().zip(&d) {
+            for (value, &b) in row.iter_mut().zip(&d) {
+                *value = a.mul_add(b, *value);
+            }
+        }
+    }
+    let (values, vectors) = jacobi_eigen(covariance);
+    let smallest = (0..3)
+        .min_by(|&a, &b| values[a].total_cmp(&values[b]))
+        .unwrap_or(0);
+    math::normalize([
+        vectors[0][smallest],
+        vectors[1][smallest],
+        vectors[2][smallest],
+    ])
+}
+
+/// Eigenvalues and eigenvectors (as columns) of a symmetric 3x3 Matrix using Jacobi rotations
+fn jacobi_eigen(mut matrix: [[f32; 3]; 3]) -> ([f32; 3], [[f32; 3]; 3]) {
+    let mut vectors = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..32 {
+        let off = matrix[0][1].abs() + matrix[0][2].abs() + matrix[1][2].abs();
+        if off < 1e-12 {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if matrix[p][q].abs() < 1e-20 {
+                continue;
+            }
+            // Rotation which zeroes matrix[p][q]
+            let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+            let tan = theta.signum() / (theta.abs() + theta.hypot(1.0));
+            let cos = 1.0 / tan.hypot(1.0);
+            let sin = tan * cos;
+            let rotate = |row: &mut [f32; 3]| {
+                let (vp, vq) = (row[p], row[q]);
+                row[p] = cos.mul_add(vp, -sin * vq);
+                row[q] = sin.mul_add(vp, cos * vq);
+            };
+            matrix.iter_mut().for_each(rotate);
+            let (row_p, row_q) = (matrix[p], matrix[q]);
+            for column in 0..3 {
+                matrix[p][column] = cos.mul_add(row_p[column], -sin * row_q[column]);
+                matrix[q][column] = sin.mul_add(row_p[column], cos * row_q[column]);
+            }
+            vectors.iter_mut().for_each(rotate);
+        }
+    }
+    ([matrix[0][0], matrix[1][1], matrix[2][2]], vectors)
+}
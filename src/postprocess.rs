@@ -0,0 +1,318 @@
+use std::collections::HashSet;
+
+use bitflags::bitflags;
+
+use crate::{math, Indices, Mesh, MeshInstance, Model3D, RenderMode};
+
+bitflags! {
+    /// Postprocessing steps which can be applied with [`Model3D::post_process`]
+    ///
+    /// Similar to Assimp's `aiPostProcessSteps`
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+    pub struct PostProcess: u32 {
+        /// Generates flat normals for Meshes missing normals, See [`Mesh::compute_flat_normals`]
+        ///
+        /// Ignored when `GENERATE_SMOOTH_NORMALS` is also set
+        const GENERATE_NORMALS = 1;
+        /// Generates smooth normals for Meshes missing normals, See [`Mesh::compute_smooth_normals`]
+        const GENERATE_SMOOTH_NORMALS = 1 << 1;
+        /// Calculates tangents for Meshes missing tangents, See [`Mesh::compute_tangents`]
+        const CALC_TANGENTS = 1 << 2;
+        /// Converts Triangle strips and fans into Triangle lists, See [`Mesh::triangulate`]
+        const TRIANGULATE = 1 << 3;
+        /// Merges identical Vertices, See [`Mesh::join_identical_vertices`]
+        const JOIN_IDENTICAL_VERTICES = 1 << 4;
+        /// Flips the V texture coordinate, See [`Mesh::flip_uvs`]
+        const FLIP_UVS = 1 << 5;
+        /// Reduces the number of Meshes, See [`Model3D::optimize_meshes`]
+        const OPTIMIZE_MESHES = 1 << 6;
+    }
+}
+
+impl Model3D {
+    /// Applies all the Given postprocessing steps on every Mesh
+    ///
+    /// The steps run in the following order, regardless of how the flags were combined:
+    /// `TRIANGULATE`, `FLIP_UVS`, `GENERATE_NORMALS`/`GENERATE_SMOOTH_NORMALS`, `CALC_TANGENTS`, `JOIN_IDENTICAL_VERTICES`, `OPTIMIZE_MESHES`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{Model3D, PostProcess};
+    ///
+    /// let mut model = Model3D::load("model.obj").expect("Failed to load");
+    /// model.post_process(PostProcess::GENERATE_SMOOTH_NORMALS | PostProcess::CALC_TANGENTS);
+    /// ```
+    pub fn post_process(&mut self, flags: PostProcess) {
+        for mesh in &mut self.meshes {
+            if flags.contains(PostProcess::TRIANGULATE) {
+                mesh.triangulate();
+            }
+            if flags.contains(PostProcess::FLIP_UVS) {
+                mesh.flip_uvs();
+            }
+            let missing_normals = mesh.vertices.iter().any(|v| v.normal.is_none());
+            if flags.contains(PostProcess::GENERATE_SMOOTH_NORMALS) {
+                if missing_normals {
+                    mesh.compute_smooth_normals();
+                }
+            } else if flags.contains(PostProcess::GENERATE_NORMALS) && missing_normals {
+                mesh.compute_flat_normals();
+            }
+            // Tangents authored in the File are more accurate than generated ones
+            if flags.contains(PostProcess::CALC_TANGENTS)
+                && mesh.vertices.iter().any(|v| v.tangent.is_none())
+            {
+                mesh.compute_tangents();
+            }
+            if flags.contains(PostProcess::JOIN_IDENTICAL_VERTICES) {
+                mesh.join_identical_vertices();
+            }
+        }
+        if flags.contains(PostProcess::OPTIMIZE_MESHES) {
+            self.optimize_meshes();
+        }
+    }
+
+    /// Merges all Meshes which share the same Material and `RenderMode` into one indexed Mesh
+    ///
+    /// Only list modes (`Points`, `Lines` and `Triangles`) are merged, Strips, fans and loops are kept as they are.
+    /// Meshes with Morph Targets are also kept as they are. The merged Mesh keeps the name of the first Mesh.
+    /// Merging ignores node transforms, So `mesh_instances` is cleared
+    pub fn optimize_meshes(&mut self) {
+        self.mesh_instances.clear();
+        let mut merged: Vec<Mesh> = Vec::new();
+        for mesh in std::mem::take(&mut self.meshes) {
+            let target = merged.iter_mut().find(|other| can_merge(other, &mesh));
+            match target {
+                Some(target) => target.append(&mesh),
+                None => merged.push(mesh),
+            }
+        }
+        self.meshes = merged;
+    }
+
+    /// Merges the primitives of each source Mesh which share the same Material and `RenderMode` into one indexed Mesh
+    ///
+    /// Unlike [`Model3D::optimize_meshes`] only Meshes with the same [`Mesh::source_mesh`] are merged, Meshes without one are kept as they are.
+    /// All primitives of a source Mesh are placed by the same node transforms, So `mesh_instances` stays valid and is remapped onto the merged Meshes.
+    /// The same rules as for `optimize_meshes` apply to strips, fans, loops and Morph Targets
+    pub fn merge_primitives_by_material(&mut self) {
+        let mut remap = Vec::with_capacity(self.meshes.len());
+        let mut merged: Vec<Mesh> = Vec::new();
+        for mesh in std::mem::take(&mut self.meshes) {
+            let target = merged.iter().position(|other| {
+                mesh.source_mesh.is_some()
+                    && other.source_mesh == mesh.source_mesh
+                    && can_merge(other, &mesh)
+            });
+            if let Some(target) = target {
+                merged[target].append(&mesh);
+                remap.push(target);
+            } else {
+                remap.push(merged.len());
+                merged.push(mesh);
+            }
+        }
+        self.meshes = merged;
+
+        let mut seen = HashSet::new();
+        self.mesh_instances.retain_mut(|instance| {
+            instance.mesh_index = remap[instance.mesh_index];
+            seen.insert((instance.mesh_index, instance.transform.map(f32::to_bits)))
+        });
+    }
+
+    /// Appends the Meshes, Materials, Cameras, Lights and variants of another Model
+    ///
+    /// The `material_index` of every appended Mesh is offset by the number of Materials this Model had, So it keeps pointing
+    /// at the same Material. The `format` of this Model is kept. When only one of both Models has `mesh_instances` the Meshes
+    /// of the other one are placed once with the identity Matrix, So [`Model3D::instances`] still returns every Mesh.
+    /// The `source_mesh` of every appended Mesh is offset as well, So Meshes of both Models never share a source Mesh
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let mut scene = Model3D::load("level.gltf").expect("Failed to load");
+    /// scene.append(Model3D::load("props.obj").expect("Failed to load"));
+    /// ```
+    pub fn append(&mut self, mut other: Self) {
+        let mesh_offset = self.meshes.len();
+        let material_offset = self.materials.len();
+        let source_offset = self
+            .meshes
+            .iter()
+            .filter_map(|mesh| mesh.source_mesh)
+            .max()
+            .map_or(0, |max| max + 1);
+        let place_all = |model: &mut Self| {
+            model.mesh_instances = (0..model.meshes.len())
+                .map(|mesh_index| MeshInstance {
+                    mesh_index,
+                    transform: math::IDENTITY,
+                })
+                .collect();
+        };
+        if self.mesh_instances.is_empty() != other.mesh_instances.is_empty() {
+            if self.mesh_instances.is_empty() {
+                place_all(self);
+            } else {
+                place_all(&mut other);
+            }
+        }
+        for mesh in &mut other.meshes {
+            if let Some(index) = &mut mesh.material_index {
+                *index += material_offset;
+            }
+            if let Some(source) = &mut mesh.source_mesh {
+                *source += source_offset;
+            }
+            if let Some(variants) = &mut mesh.material_variants {
+                let indices = variants
+                    .base
+                    .iter_mut()
+                    .chain(variants.materials.values_mut());
+                for index in indices {
+                    *index += material_offset;
+                }
+            }
+        }
+        for instance in &mut other.mesh_instances {
+            instance.mesh_index += mesh_offset;
+        }
+        self.meshes.append(&mut other.meshes);
+        self.materials.append(&mut other.materials);
+        self.cameras.append(&mut other.cameras);
+        self.lights.append(&mut other.lights);
+        self.mesh_instances.append(&mut other.mesh_instances);
+        self.warnings.append(&mut other.warnings);
+        for variant in other.variants {
+            if !self.variants.contains(&variant) {
+                self.variants.push(variant);
+            }
+        }
+        self.source_bytes = None;
+    }
+
+    /// Removes all Meshes for which the predicate returns `false`
+    ///
+    /// `mesh_instances` of removed Meshes are dropped and the others are remapped onto the remaining Meshes.
+    /// Materials are kept, So every `material_index` stays valid. Call [`Model3D::remove_unused_materials`] afterwards to drop the Materials no Mesh uses anymore
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{Model3D, RenderMode};
+    ///
+    /// let mut model = Model3D::load("model.obj").expect("Failed to load");
+    /// // The renderer only draws Triangles
+    /// model.retain_meshes(|mesh| mesh.mode == RenderMode::Triangles);
+    /// model.remove_unused_materials();
+    /// ```
+    pub fn retain_meshes<F: Fn(&Mesh) -> bool>(&mut self, predicate: F) {
+        let mut remap = Vec::with_capacity(self.meshes.len());
+        let mut kept = 0;
+        for mesh in &self.meshes {
+            let keep = predicate(mesh);
+            remap.push(keep.then_some(kept));
+            kept += usize::from(keep);
+        }
+        if kept == self.meshes.len() {
+            return;
+        }
+        let mut keep = remap.iter().map(Option::is_some);
+        self.meshes.retain(|_| keep.next().unwrap_or(false));
+        self.mesh_instances.retain_mut(|instance| {
+            remap[instance.mesh_index].is_some_and(|index| {
+                instance.mesh_index = index;
+                true
+            })
+        });
+    }
+
+    /// Removes all Materials which are not used by any Mesh, Neither directly nor through a Material variant
+    ///
+    /// The `material_index` and `material_variants` of every Mesh are remapped onto the remaining Materials.
+    /// Indices which are out of range are left unchanged, See [`Model3D::validate`]
+    pub fn remove_unused_materials(&mut self) {
+        let mut used = vec![false; self.materials.len()];
+        for mesh in &self.meshes {
+            let variants = mesh
+                .material_variants
+                .iter()
+                .flat_map(|variants| variants.base.iter().chain(variants.materials.values()));
+            for &index in mesh.material_index.iter().chain(variants) {
+                if let Some(used) = used.get_mut(index) {
+                    *used = true;
+                }
+            }
+        }
+        let mut remap = Vec::with_capacity(used.len());
+        let mut kept = 0;
+        for &used in &used {
+            remap.push(kept);
+            kept += usize::from(used);
+        }
+        if kept == self.materials.len() {
+            return;
+        }
+        let mut keep = used.into_iter();
+        self.materials.retain(|_| keep.next().unwrap_or(false));
+        for mesh in &mut self.meshes {
+            let variants = mesh.material_variants.iter_mut().flat_map(|variants| {
+                variants
+                    .base
+                    .iter_mut()
+                    .chain(variants.materials.values_mut())
+            });
+            for index in mesh.material_index.iter_mut().chain(variants) {
+                if let Some(&remapped) = remap.get(*index) {
+                    *index = remapped;
+                }
+            }
+        }
+    }
+}
+
+/// Whether `mesh` can be appended onto `target`
+fn can_merge(target: &Mesh, mesh: &Mesh) -> bool {
+    let list = |mesh: &Mesh| {
+        mesh.morph_targets.is_empty()
+            && matches!(
+                mesh.mode,
+                RenderMode::Points | RenderMode::Lines | RenderMode::Triangles
+            )
+    };
+    list(target)
+        && list(mesh)
+        && target.mode == mesh.mode
+        && target.material_index == mesh.material_index
+        && target.material_variants == mesh.material_variants
+}
+
+impl Mesh {
+    /// Appends the Vertices and Indices of another Mesh using the same `RenderMode`
+    pub(crate) fn append(&mut self, other: &Self) {
+        let offset =
+            u32::try_from(self.vertices.len()).expect("Vertex index does not fit into u32");
+        let mut indices = (0..self.element_count())
+            .map(|i| u32::try_from(self.index(i)).expect("Vertex index does not fit into u32"))
+            .collect::<Vec<_>>();
+        indices.extend((0..other.element_count()).map(|i| {
+            offset + u32::try_from(other.index(i)).expect("Vertex index does not fit into u32")
+        }));
+        self.vertices.extend(other.vertices.iter().cloned());
+        self.bounds = None;
+        self.original_indices = self
+            .original_indices
+            .take()
+            .zip(other.original_indices.as_ref())
+            .map(|(mut original, other)| {
+                original.extend(other);
+                original
+            });
+        self.indices = Some(Indices::U32(indices));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{math, Mesh};
+
+/// The result of [`Mesh::raycast`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// Distance from the ray origin to the hit point
+    pub distance: f32,
+    /// The hit point in Mesh space
+    pub point: [f32; 3],
+    /// Index of the hit Triangle, In the order of [`Mesh::triangles`]
+    pub triangle: usize,
+    /// Barycentric weights of the three Triangle corners at the hit point
+    pub barycentric: [f32; 3],
+    /// Interpolated texture coordinate, `None` if the Triangle has no texture coordinates
+    pub tex_coord: Option<[f32; 2]>,
+    /// Interpolated and normalized Vertex normal, `None` if the Triangle has no normals
+    pub normal: Option<[f32; 3]>,
+}
+
+/// The result of [`Mesh::closest_point`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosestPoint {
+    /// The nearest point on the Mesh surface
+    pub point: [f32; 3],
+    /// Distance from the query point to `point`
+    pub distance: f32,
+    /// Index of the Triangle containing `point`, In the order of [`Mesh::triangles`]
+    pub triangle: usize,
+}
+
+impl Mesh {
+    /// Casts a ray against all Triangles and returns the nearest hit
+    ///
+    /// Uses the Möller–Trumbore intersection test, Triangles are hit from both sides.
+    /// `dir` does not have to be normalized. This is a brute force test over all Triangles
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.obj").expect("Failed to load");
+    /// if let Some(hit) = model.meshes[0].raycast([0.0, 0.0, 5.0], [0.0, 0.0, -1.0]) {
+    ///     println!("Hit triangle {} at {:?}", hit.triangle, hit.point);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn raycast(&self, origin: [f32; 3], dir: [f32; 3]) -> Option<RayHit> {
+        let dir = math::normalize(dir);
+        let mut nearest: Option<(usize, [usize; 3], f32, [f32; 3])> = None;
+        for (i, corners) in self.triangles().enumerate() {
+            let triangle = corners.map(|corner| self.vertices[corner].position);
+            if let Some((t, barycentric)) = ray_triangle(origin, dir, &triangle) {
+                if nearest.is_none_or(|(_, _, nearest_t, _)| t < nearest_t) {
+                    nearest = Some((i, corners, t, barycentric));
+                }
+            }
+        }
+        nearest.map(|(triangle, corners, t, barycentric)| {
+            self.ray_hit(origin, dir, (triangle, corners), t, barycentric)
+        })
+    }
+
+    /// Finds the nearest point on the Mesh surface to the Given point
+    ///
+    /// Returns `None` if the Mesh has no Triangles. This is a brute force test over all Triangles
+    #[must_use]
+    pub fn closest_point(&self, point: [f32; 3]) -> Option<ClosestPoint> {
+        let mut nearest: Option<ClosestPoint> = None;
+        for (i, corners) in self.triangles().enumerate() {
+            let triangle = corners.map(|corner| self.vertices[corner].position);
+            let candidate = closest_point_on_triangle(point, &triangle);
+            let distance = math::length(math::sub(point, candidate));
+            if nearest.is_none_or(|nearest| distance < nearest.distance) {
+                nearest = Some(ClosestPoint {
+                    point: candidate,
+                    distance,
+                    triangle: i,
+                });
+            }
+        }
+        nearest
+    }
+
+    pub(crate) fn ray_hit(
+        &self,
+        origin: [f32; 3],
+        dir: [f32; 3],
+        (triangle, corners): (usize, [usize; 3]),
+        t: f32,
+        barycentric: [f32; 3],
+    ) -> RayHit {
+        let corners = corners.map(|i| &self.vertices[i]);
+
+        let tex_coord = match corners.map(|v| v.tex_coord) {
+            [Some(a), Some(b), Some(c)] => Some(math::interpolate([a, b, c], barycentric)),
+            _ => None,
+        };
+        let normal = match corners.map(|v| v.normal) {
+            [Some(a), Some(b), Some(c)] => {
+                Some(math::normalize(math::interpolate([a, b, c], barycentric)))
+            }
+            _ => None,
+        };
+        RayHit {
+            distance: t,
+            point: math::add(origin, math::scale(dir, t)),
+            triangle,
+            barycentric,
+            tex_coord,
+            normal,
+        }
+    }
+}
+
+/// Möller–Trumbore ray Triangle intersection, Returns the ray parameter and the barycentric weights
+pub fn ray_triangle(
+    origin: [f32; 3],
+    dir: [f32; 3],
+    [v0, v1, v2]: &[[f32; 3]; 3],
+) -> Option<(f32, [f32; 3])> {
+    let edge1 = math::sub(*v1, *v0);
+    let edge2 = math::sub(*v2, *v0);
+    let pvec = math::cross(dir, edge2);
+    let det = math::dot(edge1, pvec);
+    if det.abs() < f32::EPSILON {
+        // Ray is parallel to the Triangle
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let tvec = math::sub(origin, *v0);
+    let u = math::dot(tvec, pvec) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let qvec = math::cross(tvec, edge1);
+    let v = math::dot(dir, qvec) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = math::dot(edge2, qvec) * inv_det;
+    (t >= 0.0).then_some((t, [1.0 - u - v, u, v]))
+}
+
+/// Closest point on a Triangle, See "Real-Time Collision Detection" by Christer Ericson, 5.1.5
+pub fn closest_point_on_triangle(point: [f32; 3], [v0, v1, v2]: &[[f32; 3]; 3]) -> [f32; 3] {
+    let ab = math::sub(*v1, *v0);
+    let ac = math::sub(*v2, *v0);
+
+    // Vertex region of v0
+    let ap = math::sub(point, *v0);
+    let d1 = math::dot(ab, ap);
+    let d2 = math::dot(ac, ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return *v0;
+    }
+
+    // Vertex region of v1
+    let bp = math::sub(point, *v1);
+    let d3 = math::dot(ab, bp);
+    let d4 = math::dot(ac, bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return *v1;
+    }
+
+    // Edge region of v0 v1
+    let vc = d1.mul_add(d4, -(d3 * d2));
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return math::add(*v0, math::scale(ab, v));
+    }
+
+    // Vertex region of v2
+    let cp = math::sub(point, *v2);
+    let d5 = math::dot(ab, cp);
+    let d6 = math::dot(ac, cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return *v2;
+    }
+
+    // Edge region of v0 v2
+    let vb = d5.mul_add(d2, -(d1 * d6));
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return math::add(*v0, math::scale(ac, w));
+    }
+
+    // Edge region of v1 v2
+    let va = d3.mul_add(d6, -(d5 * d4));
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return math::add(*v1, math::scale(math::sub(*v2, *v1), w));
+    }
+
+    // Inside the face region
+    let denom = 1.0 / (va + vb + vc);
+    let v = vb * denom;
+    let w = vc * denom;
+    math::add(*v0, math::add(math::scale(ab, v), math::scale(ac, w)))
+}
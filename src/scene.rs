@@ -0,0 +1,133 @@
+use crate::{math, Mesh, Model3D};
+
+/// A Mesh placed in the Scene by a node, See [`Model3D::instances`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshInstance {
+    /// Index into `Model3D::meshes`
+    pub mesh_index: usize,
+    /// World transform of the node as column major 4x4 Matrix
+    pub transform: [f32; 16],
+}
+
+impl Model3D {
+    /// Returns every placed Mesh together with its world transform as column major 4x4 Matrix
+    ///
+    /// A Mesh referenced by multiple nodes is returned once per node, Which allows drawing it with GPU instancing
+    /// instead of baking the transforms into the Vertices. Meshes no node references are skipped, For glTF only nodes of
+    /// the default scene (or the first scene without a default) count. Formats without a scene graph and glTF Files without
+    /// any scene return every Mesh once with the identity Matrix
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("scene.gltf").expect("Failed to load");
+    /// for (mesh, transform) in model.instances() {
+    ///     println!("{:?} at {:?}", mesh.name, &transform[12..15]);
+    /// }
+    /// ```
+    pub fn instances(&self) -> impl Iterator<Item = (&Mesh, [f32; 16])> {
+        let flat = self.mesh_instances.is_empty();
+        self.mesh_instances
+            .iter()
+            .map(|instance| (&self.meshes[instance.mesh_index], instance.transform))
+            .chain(
+                self.meshes
+                    .iter()
+                    .filter(move |_| flat)
+                    .map(|mesh| (mesh, math::IDENTITY)),
+            )
+    }
+}
+
+/// A Camera placed in the Scene
+#[derive(Debug)]
+pub struct Camera {
+    /// The projection of the Camera
+    pub projection: Projection,
+    /// World transform of the Camera as column major 4x4 Matrix
+    ///
+    /// The Camera looks along its local -Z axis with +Y up
+    pub transform: [f32; 16],
+    /// Name of the Camera.
+    ///
+    /// Some File Formats do not support Camera names, In this case this will be `None`
+    pub name: Option<String>,
+}
+
+impl Camera {
+    /// World position of the Camera, Taken from the translation of `transform`
+    #[must_use]
+    pub const fn position(&self) -> [f32; 3] {
+        [self.transform[12], self.transform[13], self.transform[14]]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    Perspective {
+        /// The vertical field of view in radians
+        yfov: f32,
+        /// The aspect ratio (width / height), When `None` the aspect ratio of the viewport should be used
+        aspect_ratio: Option<f32>,
+        /// Distance to the near clipping plane
+        znear: f32,
+        /// Distance to the far clipping plane, When `None` the projection is infinite
+        zfar: Option<f32>,
+    },
+    Orthographic {
+        /// The horizontal magnification of the view
+        xmag: f32,
+        /// The vertical magnification of the view
+        ymag: f32,
+        /// Distance to the near clipping plane
+        znear: f32,
+        /// Distance to the far clipping plane
+        zfar: f32,
+    },
+}
+
+/// A punctual Light placed in the Scene
+#[derive(Debug)]
+pub struct Light {
+    /// The type of the Light
+    pub kind: LightKind,
+    /// Linear RGB color of the Light
+    pub color: [f32; 3],
+    /// Brightness of the Light, Point and Spot Lights use candela (lm/sr) while Directional Lights use lux (lm/m2)
+    pub intensity: f32,
+    /// Distance cutoff at which the Light's intensity may be considered to have reached zero, When `None` the range is infinite
+    pub range: Option<f32>,
+    /// World transform of the Light as column major 4x4 Matrix
+    ///
+    /// Directional and Spot Lights shine along their local -Z axis
+    pub transform: [f32; 16],
+    /// Name of the Light.
+    ///
+    /// Some File Formats do not support Light names, In this case this will be `None`
+    pub name: Option<String>,
+}
+
+impl Light {
+    /// World position of the Light, Taken from the translation of `transform`
+    #[must_use]
+    pub const fn position(&self) -> [f32; 3] {
+        [self.transform[12], self.transform[13], self.transform[14]]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LightKind {
+    /// Infinitely far away Light, Position and scale are ignored
+    Directional,
+    /// Light emitting in all directions from its position
+    Point,
+    /// Light emitting in a cone
+    Spot {
+        /// Angle in radians from the center of the cone where the falloff begins
+        inner_cone_angle: f32,
+        /// Angle in radians from the center of the cone where the falloff ends
+        outer_cone_angle: f32,
+    },
+}
@@ -0,0 +1,96 @@
+use std::{
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use crate::{ModelError, ModelFormat};
+
+/// How many bytes are read from the start of the File
+const SNIFF_LEN: u64 = 512;
+
+/// Detects the Format from the File content, Returns `None` if no enabled Format matches
+pub fn sniff_format(path: &Path) -> Result<Option<ModelFormat>, ModelError> {
+    let mut file = crate::source::open(path)?;
+    let size = file
+        .seek(SeekFrom::End(0))
+        .and_then(|size| file.seek(SeekFrom::Start(0)).map(|_| size))
+        .map_err(|e| ModelError::OpenFile(e.to_string()))?;
+    let mut head = Vec::new();
+    file.take(SNIFF_LEN)
+        .read_to_end(&mut head)
+        .map_err(|e| ModelError::OpenFile(e.to_string()))?;
+    Ok(detect(&head, size))
+}
+
+#[cfg_attr(not(feature = "stl"), allow(unused_variables))]
+#[cfg_attr(
+    not(any(feature = "gltf", feature = "ply", feature = "obj")),
+    allow(clippy::missing_const_for_fn)
+)]
+fn detect(head: &[u8], size: u64) -> Option<ModelFormat> {
+    let text = head.trim_ascii_start();
+
+    #[cfg(feature = "gltf")]
+    if head.starts_with(b"glTF") || text.starts_with(b"{") {
+        return Some(ModelFormat::GLTF);
+    }
+    #[cfg(feature = "ply")]
+    if head.starts_with(b"ply\n") || head.starts_with(b"ply\r") {
+        return Some(ModelFormat::PLY);
+    }
+    #[cfg(feature = "stl")]
+    if is_binary_stl(head, size) || is_ascii_stl(text) {
+        return Some(ModelFormat::STL);
+    }
+    #[cfg(feature = "obj")]
+    if is_obj(text) {
+        return Some(ModelFormat::OBJ);
+    }
+    None
+}
+
+/// 80 byte header, Triangle count and 50 bytes per Triangle, The size has to match exactly
+#[cfg(feature = "stl")]
+pub fn is_binary_stl(head: &[u8], size: u64) -> bool {
+    head.get(80..84).is_some_and(|count| {
+        let count = u32::from_le_bytes([count[0], count[1], count[2], count[3]]);
+        84 + u64::from(count) * 50 == size
+    })
+}
+
+#[cfg(feature = "stl")]
+fn is_ascii_stl(text: &[u8]) -> bool {
+    text.starts_with(b"solid") && text.windows(5).any(|word| word == b"facet")
+}
+
+#[cfg(feature = "obj")]
+fn is_obj(text: &[u8]) -> bool {
+    const KEYWORDS: [&[u8]; 8] = [
+        b"v ", b"vt ", b"vn ", b"f ", b"o ", b"g ", b"mtllib ", b"usemtl ",
+    ];
+    text.split(|&b| b == b'\n')
+        .map(<[u8]>::trim_ascii_start)
+        .any(|line| KEYWORDS.iter().any(|keyword| line.starts_with(keyword)))
+}
+
+/// Guesses the mime type of an encoded Image from its magic bytes
+pub fn sniff_mime_type(data: &[u8]) -> Option<String> {
+    let mime_type = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        "image/webp"
+    } else if data.starts_with(b"\xABKTX 20\xBB") {
+        "image/ktx2"
+    } else if data.starts_with(b"sB") {
+        "image/basis"
+    } else if data.starts_with(b"#?RADIANCE") || data.starts_with(b"#?RGBE") {
+        "image/vnd.radiance"
+    } else if data.starts_with(&[0x76, 0x2F, 0x31, 0x01]) {
+        "image/x-exr"
+    } else {
+        return None;
+    };
+    Some(mime_type.to_string())
+}
@@ -0,0 +1,51 @@
+use crate::{Mesh, Vertex};
+
+/// Structure of arrays layout of the Vertices of a Mesh, See [`Mesh::to_soa`]
+///
+/// Every present attribute has exactly one value for every Vertex, In the order of `Mesh::vertices`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeshSoA {
+    /// Positions of all Vertices
+    pub positions: Vec<[f32; 3]>,
+    /// Normals, `None` unless every Vertex has a normal
+    pub normals: Option<Vec<[f32; 3]>>,
+    /// Texture coordinates, `None` unless every Vertex has a texture coordinate
+    pub tex_coords: Option<Vec<[f32; 2]>>,
+    /// RGBA colors, `None` unless every Vertex has a color
+    pub colors: Option<Vec<[f32; 4]>>,
+    /// Tangents, `None` unless every Vertex has a tangent
+    pub tangents: Option<Vec<[f32; 4]>>,
+}
+
+impl Mesh {
+    /// Splits the Vertices into one contiguous buffer per attribute
+    ///
+    /// Useful to upload attributes one at a time into separate GPU buffers. An attribute only some Vertices have is
+    /// left out (`None`) instead of being filled with made up values, Use [`Mesh::fill_missing_normals`] or similar beforehand
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.obj").expect("Failed to load");
+    /// let soa = model.meshes[0].to_soa();
+    /// println!("{} positions, normals: {}", soa.positions.len(), soa.normals.is_some());
+    /// ```
+    #[must_use]
+    pub fn to_soa(&self) -> MeshSoA {
+        fn collect<T>(
+            vertices: &[Vertex],
+            attribute: impl Fn(&Vertex) -> Option<T>,
+        ) -> Option<Vec<T>> {
+            vertices.iter().map(attribute).collect()
+        }
+        MeshSoA {
+            positions: self.vertices.iter().map(|v| v.position).collect(),
+            normals: collect(&self.vertices, |v| v.normal),
+            tex_coords: collect(&self.vertices, |v| v.tex_coord),
+            colors: collect(&self.vertices, |v| v.color),
+            tangents: collect(&self.vertices, |v| v.tangent),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    fs::File,
+    io::{BufReader, Cursor, Read, Seek},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{options::LoadContext, ModelError};
+
+/// A readable and seekable Model File
+pub trait Source: Read + Seek {}
+
+impl<T: Read + Seek> Source for T {}
+
+/// Opens a Model File for the loaders
+///
+/// With the `gzip` feature Files ending in `.gz` are decompressed into memory, So loaders see the plain content.
+/// The decompressed size is not limited, `max_vertices` and `max_indices` are only checked while parsing it
+pub fn open(path: &Path) -> Result<Box<dyn Source>, ModelError> {
+    let file = File::open(path).map_err(|e| ModelError::OpenFile(e.to_string()))?;
+    #[cfg(feature = "gzip")]
+    if is_gzip(path) {
+        return decompress(file);
+    }
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Opens the Model File of a load like [`open`]
+///
+/// With `retain_source_bytes` the whole File is read into memory first and kept in the context, The loader parses that copy.
+/// Models loaded from memory read the bytes of the context instead, Which are never decompressed
+pub fn open_model(path: &Path, context: &LoadContext) -> Result<Box<dyn Source>, ModelError> {
+    if let Some(data) = context.data() {
+        if context.retain_source_bytes {
+            context.retain_source(Arc::clone(data));
+        }
+        return Ok(Box::new(Cursor::new(Arc::clone(data))));
+    }
+    if !context.retain_source_bytes {
+        return open(path);
+    }
+    let bytes: Arc<[u8]> = std::fs::read(path)
+        .map_err(|e| ModelError::OpenFile(e.to_string()))?
+        .into();
+    context.retain_source(Arc::clone(&bytes));
+    #[cfg(feature = "gzip")]
+    if is_gzip(path) {
+        return decompress(Cursor::new(bytes));
+    }
+    Ok(Box::new(Cursor::new(bytes)))
+}
+
+#[cfg(feature = "gzip")]
+fn decompress(compressed: impl Read) -> Result<Box<dyn Source>, ModelError> {
+    let mut data = Vec::new();
+    flate2::read::GzDecoder::new(compressed)
+        .read_to_end(&mut data)
+        .map_err(|e| ModelError::OpenFile(format!("Failed to decompress, {e}")))?;
+    Ok(Box::new(Cursor::new(data)))
+}
+
+/// Reads the whole Model File of a load into memory
+#[cfg(any(feature = "obj", feature = "usd"))]
+pub fn read(path: &Path, context: &LoadContext) -> Result<Vec<u8>, ModelError> {
+    let mut data = Vec::new();
+    open_model(path, context)?
+        .read_to_end(&mut data)
+        .map_err(|e| ModelError::OpenFile(e.to_string()))?;
+    Ok(data)
+}
+
+/// Reads the whole Model File of a load as text
+#[cfg(any(feature = "dxf", feature = "x3d"))]
+pub fn read_to_string(path: &Path, context: &LoadContext) -> Result<String, ModelError> {
+    let mut text = String::new();
+    open_model(path, context)?
+        .read_to_string(&mut text)
+        .map_err(|e| ModelError::OpenFile(e.to_string()))?;
+    Ok(text)
+}
+
+#[cfg(feature = "gzip")]
+pub fn is_gzip(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "gz")
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use crate::{Indices, Mesh, MorphTarget, RenderMode};
+
+/// Most Vertices a Mesh indexed with `u16` can reference
+const U16_VERTICES: usize = 1 << 16;
+
+impl Mesh {
+    /// Splits the Mesh into Meshes which reference at most 65536 Vertices each and use `Indices::U16`
+    ///
+    /// For GPUs and engines which only support 16 bit Indices. Primitives are kept in order and never cut, Vertices shared by
+    /// primitives of different chunks are duplicated. Strips and fans become `Triangles`, Line strips and loops become `Lines`.
+    /// Vertex attributes, Morph Targets, the Material and the name are kept. Meshes which already fit are returned as one chunk
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("scan.ply").expect("Failed to load");
+    /// let chunks = model.meshes[0].split_for_u16();
+    /// println!("{} draw calls", chunks.len());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a line strip or loop has more Vertices than fit into `u32`
+    #[must_use]
+    pub fn split_for_u16(&self) -> Vec<Self> {
+        let (mode, size, list) = match self.mode {
+            RenderMode::Points | RenderMode::Lines => (
+                self.mode,
+                if self.mode == RenderMode::Points {
+                    1
+                } else {
+                    2
+                },
+                (0..self.element_count()).map(|i| self.index(i)).collect(),
+            ),
+            RenderMode::LineStrip | RenderMode::LineLoop => (
+                RenderMode::Lines,
+                2,
+                self.line_indices()
+                    .into_iter()
+                    .map(|i| i as usize)
+                    .collect(),
+            ),
+            _ => (
+                RenderMode::Triangles,
+                3,
+                self.triangles().flatten().collect::<Vec<_>>(),
+            ),
+        };
+        let mut chunks = Vec::new();
+        let mut remap = HashMap::new();
+        let mut sources = Vec::new();
+        let mut indices = Vec::new();
+        for primitive in list.chunks_exact(size) {
+            let added = primitive
+                .iter()
+                .enumerate()
+                .filter(|&(i, vertex)| {
+                    !remap.contains_key(vertex) && !primitive[..i].contains(vertex)
+                })
+                .count();
+            if sources.len() + added > U16_VERTICES {
+                chunks.push(self.chunk(mode, &sources, std::mem::take(&mut indices)));
+                sources.clear();
+                remap.clear();
+            }
+            for &vertex in primitive {
+                let index = *remap.entry(vertex).or_insert_with(|| {
+                    sources.push(vertex);
+                    u16::try_from(sources.len() - 1).expect("Chunk exceeds the u16 range")
+                });
+                indices.push(index);
+            }
+        }
+        if !indices.is_empty() {
+            chunks.push(self.chunk(mode, &sources, indices));
+        }
+        chunks
+    }
+
+    /// Builds a Mesh from the Given source Vertices, Copying everything but the geometry
+    fn chunk(&self, mode: RenderMode, sources: &[usize], indices: Vec<u16>) -> Self {
+        let gather = |values: &Option<Vec<[f32; 3]>>| {
+            values
+                .as_ref()
+                .map(|values| sources.iter().map(|&i| values[i]).collect())
+        };
+        let mut chunk = Self::new(
+            sources.iter().map(|&i| self.vertices[i].clone()).collect(),
+            Some(Indices::U16(indices)),
+            mode,
+        );
+        chunk.material_index = self.material_index;
+        chunk.name.clone_from(&self.name);
+        chunk.morph_targets = self
+            .morph_targets
+            .iter()
+            .map(|target| MorphTarget {
+                positions: gather(&target.positions),
+                normals: gather(&target.normals),
+                tangents: gather(&target.tangents),
+            })
+            .collect();
+        chunk.morph_weights.clone_from(&self.morph_weights);
+        chunk.source_mesh = self.source_mesh;
+        chunk.material_variants.clone_from(&self.material_variants);
+        chunk.original_indices = self
+            .original_indices
+            .as_ref()
+            .map(|original| sources.iter().map(|&i| original[i]).collect());
+        #[cfg(feature = "extras")]
+        chunk.extras.clone_from(&self.extras);
+        chunk
+    }
+}
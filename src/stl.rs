@@ -1,54 +1,119 @@
-use std::{fs::File, path::Path};
+use std::{
+    io::{BufReader, Read, SeekFrom},
+    path::Path,
+};
 
-use crate::{Model3D, ModelError, Vertex};
+use crate::{math, options::LoadContext, sniff::is_binary_stl, Model3D, ModelError, Vertex};
 
-pub fn load(path: &Path) -> Result<Model3D, ModelError> {
-    let stl =
-        stl_io::read_stl(&mut File::open(path).map_err(|e| ModelError::OpenFile(e.to_string()))?)
-            .map_err(|e| ModelError::ModelParsing(e.to_string()))?;
+/// What the STL loader does with faces whose stored normal opposes the winding of their Vertices, See [`crate::LoadOptions::stl_winding`]
+///
+/// Some exporters write normals which do not match the Vertex order. Renderers which cull back faces by winding and
+/// shade with the stored normals then show dark or missing faces
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StlWinding {
+    /// Load the faces as they are in the File
+    #[default]
+    Keep,
+    /// Replace the stored normal with the one computed from the winding, Trusts the Vertex order
+    RecomputeNormals,
+    /// Swap two Vertices so the winding matches the stored normal, Trusts the normal
+    FlipWinding,
+}
+
+/// Loads an ASCII or binary STL File
+///
+/// Some exporters write binary Files whose 80 byte header starts with "solid", Which is the ASCII magic word.
+/// So a File is treated as binary whenever its size matches the Triangle count in the header, Only otherwise
+/// the magic word decides.
+///
+/// STL stores the positions of every Triangle corner directly, There are no indices which could point at missing Vertices
+pub fn load(path: &Path, options: &LoadContext) -> Result<Model3D, ModelError> {
+    let mut file = crate::source::open_model(path, options)?;
+    let triangles = read(&mut file, options)?;
 
     let mut vertices = Vec::new();
-    for face in stl.faces {
-        let normal = [face.normal[0], face.normal[1], face.normal[2]];
-        let pos1 = stl.vertices[face.vertices[0]];
-        let pos2 = stl.vertices[face.vertices[1]];
-        let pos3 = stl.vertices[face.vertices[2]];
+    let mut opposing = 0;
+    for face in triangles {
+        let mut normal = [face.normal[0], face.normal[1], face.normal[2]];
+        let mut positions = face
+            .vertices
+            .map(|position| [position[0], position[1], position[2]]);
+        let [a, b, c] = positions;
+        let winding = math::normalize(math::cross(math::sub(b, a), math::sub(c, a)));
+        // Zero normals are common in STL Files, They never oppose the winding
+        if math::dot(normal, winding) < 0.0 {
+            opposing += 1;
+            match options.stl_winding {
+                StlWinding::Keep => {}
+                StlWinding::RecomputeNormals => normal = winding,
+                StlWinding::FlipWinding => positions.swap(1, 2),
+            }
+        }
 
         // Every face (triangle) has 3 Vertices
-        let v1 = Vertex {
-            position: [pos1[0], pos1[1], pos1[2]],
-            tex_coord: None,
-            color: None,
-            normal: Some(normal),
-        };
-        let v2 = Vertex {
-            position: [pos2[0], pos2[1], pos2[2]],
-            tex_coord: None,
-            color: None,
-            normal: Some(normal),
-        };
-        let v3 = Vertex {
-            position: [pos3[0], pos3[1], pos3[2]],
+        vertices.extend(positions.map(|position| Vertex {
+            position,
             tex_coord: None,
             color: None,
             normal: Some(normal),
-        };
-
-        vertices.push(v1);
-        vertices.push(v2);
-        vertices.push(v3);
+            tangent: None,
+        }));
     }
-    let mesh = crate::Mesh {
-        vertices,
-        indices: None,
-        material_index: None,
-        mode: crate::RenderMode::TriangleFan,
-        name: None,
-    };
+    if opposing > 0 {
+        options.warn(ModelError::ModelParsing(format!(
+            "{opposing} STL faces have a normal opposing their winding"
+        )));
+    }
+    let mesh = crate::Mesh::new(vertices, None, crate::RenderMode::Triangles);
 
-    Ok(Model3D {
-        meshes: vec![mesh],
-        materials: vec![],
-        format: crate::ModelFormat::STL,
-    })
+    Ok(Model3D::new(vec![mesh], vec![], crate::ModelFormat::STL))
+}
+
+/// Reads all Triangles, Failing as soon as they exceed `max_vertices`
+///
+/// Binary Files are read here since `stl_io` only detects ASCII by the magic word, ASCII Files are read by `stl_io`
+fn read(
+    file: &mut impl crate::source::Source,
+    options: &LoadContext,
+) -> Result<Vec<stl_io::Triangle>, ModelError> {
+    let parsing = |e: std::io::Error| ModelError::ModelParsing(e.to_string());
+    let size = file.seek(SeekFrom::End(0)).map_err(parsing)?;
+    file.rewind().map_err(parsing)?;
+    let mut head = Vec::new();
+    file.by_ref()
+        .take(84)
+        .read_to_end(&mut head)
+        .map_err(parsing)?;
+    let mut triangles = Vec::new();
+    if is_binary_stl(&head, size) {
+        // The size matches the count, So every Triangle can be read
+        let mut reader = BufReader::new(file);
+        let mut record = [0; 50];
+        for _ in 0..(size - 84) / 50 {
+            options.check_vertices((triangles.len() + 1) * 3)?;
+            reader.read_exact(&mut record).map_err(parsing)?;
+            // Normal and three Vertices of three little endian floats each, Followed by the 2 byte attribute count
+            let vector = |first: usize| {
+                [first, first + 1, first + 2].map(|i| {
+                    f32::from_le_bytes([
+                        record[i * 4],
+                        record[i * 4 + 1],
+                        record[i * 4 + 2],
+                        record[i * 4 + 3],
+                    ])
+                })
+            };
+            triangles.push(stl_io::Triangle {
+                normal: stl_io::Normal::new(vector(0)),
+                vertices: [3, 6, 9].map(|first| stl_io::Vertex::new(vector(first))),
+            });
+        }
+        return Ok(triangles);
+    }
+    file.rewind().map_err(parsing)?;
+    for triangle in stl_io::create_stl_reader(file).map_err(parsing)? {
+        options.check_vertices((triangles.len() + 1) * 3)?;
+        triangles.push(triangle.map_err(parsing)?);
+    }
+    Ok(triangles)
 }
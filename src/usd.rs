@@ -0,0 +1,694 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::{options::LoadContext, Indices, Model3D, ModelError, RenderMode, Vertex};
+
+/// Magic bytes of binary USD (usdc) Files
+const USDC_MAGIC: &[u8] = b"PXR-USDC";
+
+/// A prim of a USDA layer, Its type, name, attributes and child prims
+#[derive(Default)]
+struct Prim {
+    kind: String,
+    name: String,
+    attributes: Vec<Attribute>,
+    children: Vec<Self>,
+}
+
+/// An attribute or relationship, Tuples and arrays are flattened into `values`
+struct Attribute {
+    type_name: String,
+    name: String,
+    values: Vec<String>,
+    /// The `interpolation` metadata of primvars
+    interpolation: Option<String>,
+}
+
+impl Prim {
+    fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.name == name)
+    }
+
+    fn value(&self, name: &str) -> Option<&str> {
+        self.attribute(name)?.values.first().map(String::as_str)
+    }
+
+    /// All numbers of an attribute, Empty if the attribute is missing
+    fn numbers<T: std::str::FromStr>(&self, name: &str) -> Result<Vec<T>, ModelError> {
+        self.attribute(name).map_or_else(
+            || Ok(Vec::new()),
+            |attribute| {
+                attribute
+                    .values
+                    .iter()
+                    .map(|value| {
+                        value.parse().map_err(|_| {
+                            ModelError::ModelParsing(format!(
+                                "Invalid number {value} in attribute {name}"
+                            ))
+                        })
+                    })
+                    .collect()
+            },
+        )
+    }
+}
+
+/// The Files of a USDZ package by their path inside the package
+struct Package {
+    files: HashMap<String, Vec<u8>>,
+}
+
+/// Loads a USDA (.usda, .usd) File or a USDZ package (.usdz)
+///
+/// Every `Mesh` prim becomes a Mesh using its `points`, `faceVertexIndices`, `faceVertexCounts`, `normals` and
+/// `primvars:st`. Bound `UsdPreviewSurface` Materials provide the diffuse color and texture, Textures inside a USDZ
+/// package are loaded as [`crate::Image::Memory`]. Only the ASCII encoding is supported, Binary usdc layers, references,
+/// variants and time samples are not. `xformOp`s are not applied
+pub fn load(path: &Path, options: &LoadContext) -> Result<Model3D, ModelError> {
+    let data = crate::source::read(path, options)?;
+    let (layer, package) = if data.starts_with(b"PK\x03\x04") {
+        let files = read_zip(&data)?;
+        // The root layer is the first USD File in the package
+        let root = files
+            .iter()
+            .find(|(name, _)| {
+                Path::new(name).extension().is_some_and(|extension| {
+                    ["usd", "usda", "usdc"].iter().any(|e| extension == *e)
+                })
+            })
+            .map(|(_, data)| data.to_vec())
+            .ok_or_else(|| ModelError::ModelParsing("USDZ package has no USD layer".to_string()))?;
+        let files = files
+            .into_iter()
+            .map(|(name, data)| (name, data.to_vec()))
+            .collect();
+        (root, Some(Package { files }))
+    } else {
+        (data, None)
+    };
+    if layer.starts_with(USDC_MAGIC) {
+        return Err(ModelError::ModelParsing(
+            "Binary USD (usdc) is not supported, Only USDA".to_string(),
+        ));
+    }
+    let text = String::from_utf8(layer)
+        .map_err(|_| ModelError::ModelParsing("USDA layer is not valid UTF-8".to_string()))?;
+    let prims = parse_usda(&text)?;
+
+    let model_dir = path.parent().unwrap_or_else(|| Path::new("./"));
+    let mut paths = HashMap::new();
+    let mut meshes = Vec::new();
+    let mut stack = prims
+        .iter()
+        .rev()
+        .map(|prim| (prim, String::new()))
+        .collect::<Vec<_>>();
+    while let Some((prim, parent)) = stack.pop() {
+        let path = format!("{parent}/{}", prim.name);
+        if prim.kind == "Mesh" {
+            meshes.push(prim);
+        }
+        stack.extend(
+            prim.children
+                .iter()
+                .rev()
+                .map(|child| (child, path.clone())),
+        );
+        paths.insert(path, prim);
+    }
+
+    let mut materials = Vec::new();
+    let mut material_indices = HashMap::new();
+    let mut final_meshes = Vec::new();
+    for prim in meshes {
+        let mut mesh = load_mesh(prim, options)?;
+        mesh.name = Some(prim.name.clone());
+        if let Some(binding) = prim.value("material:binding") {
+            if let Some(&index) = material_indices.get(binding) {
+                mesh.material_index = Some(index);
+            } else if let Some(material) = paths.get(binding) {
+                let material = load_material(
+                    material,
+                    binding,
+                    &paths,
+                    model_dir,
+                    package.as_ref(),
+                    options,
+                )?;
+                materials.push(material);
+                material_indices.insert(binding.to_string(), materials.len() - 1);
+                mesh.material_index = Some(materials.len() - 1);
+            }
+        }
+        final_meshes.push(mesh);
+    }
+    Ok(Model3D::new(
+        final_meshes,
+        materials,
+        crate::ModelFormat::USD,
+    ))
+}
+
+/// Index into a primvar for the Given point, face corner and face, Based on its interpolation
+fn primvar_index(interpolation: Option<&str>, point: usize, corner: usize, face: usize) -> usize {
+    match interpolation {
+        Some("faceVarying") => corner,
+        Some("uniform") => face,
+        Some("constant") => 0,
+        // `vertex` and `varying` are per point, Which is also the default
+        _ => point,
+    }
+}
+
+fn load_mesh(prim: &Prim, options: &LoadContext) -> Result<crate::Mesh, ModelError> {
+    let points = prim.numbers::<f32>("points")?;
+    let counts = prim.numbers::<usize>("faceVertexCounts")?;
+    let face_indices = prim.numbers::<usize>("faceVertexIndices")?;
+    options.check_vertices(points.len() / 3)?;
+    options.check_indices(face_indices.len())?;
+
+    let normals_name = if prim.attribute("primvars:normals").is_some() {
+        "primvars:normals"
+    } else {
+        "normals"
+    };
+    let normals = prim.numbers::<f32>(normals_name)?;
+    let normal_indices = prim.numbers::<usize>(&format!("{normals_name}:indices"))?;
+    let normal_interpolation = prim
+        .attribute(normals_name)
+        .and_then(|attribute| attribute.interpolation.as_deref());
+    // `st` is the conventional name, Otherwise take the first texture coordinate primvar
+    let st = prim.attribute("primvars:st").or_else(|| {
+        prim.attributes.iter().find(|attribute| {
+            attribute.type_name.starts_with("texCoord2") && !attribute.name.ends_with(":indices")
+        })
+    });
+    let (tex_coords, tex_coord_indices, tex_coord_interpolation) = match st {
+        Some(st) => (
+            prim.numbers::<f32>(&st.name)?,
+            prim.numbers::<usize>(&format!("{}:indices", st.name))?,
+            st.interpolation.as_deref(),
+        ),
+        None => (Vec::new(), Vec::new(), None),
+    };
+    let left_handed = prim.value("orientation") == Some("leftHanded");
+
+    let mut vertices = Vec::new();
+    let mut unique = HashMap::new();
+    let mut indices = Vec::new();
+    let mut corner = 0;
+    for (face, &count) in counts.iter().enumerate() {
+        let mut polygon = Vec::with_capacity(count);
+        for _ in 0..count {
+            let point = *face_indices.get(corner).ok_or_else(|| {
+                ModelError::ModelParsing("faceVertexCounts exceed faceVertexIndices".to_string())
+            })?;
+            let resolve = |values: &[f32], indices: &[usize], interpolation| {
+                (!values.is_empty()).then(|| {
+                    let i = primvar_index(interpolation, point, corner, face);
+                    indices.get(i).copied().unwrap_or(i)
+                })
+            };
+            let normal = resolve(&normals, &normal_indices, normal_interpolation);
+            let tex_coord = resolve(&tex_coords, &tex_coord_indices, tex_coord_interpolation);
+
+            let key = (point, normal, tex_coord);
+            let index = if let Some(&index) = unique.get(&key) {
+                index
+            } else {
+                vertices.push(Vertex {
+                    position: element(&points, point, "Point")?,
+                    normal: normal.map(|i| element(&normals, i, "Normal")).transpose()?,
+                    tex_coord: tex_coord
+                        .map(|i| element(&tex_coords, i, "Texture coordinate"))
+                        .transpose()?,
+                    ..Default::default()
+                });
+                let index = u32::try_from(vertices.len() - 1)
+                    .map_err(|_| ModelError::ModelParsing("Too many Vertices".to_string()))?;
+                unique.insert(key, index);
+                index
+            };
+            polygon.push(index);
+            corner += 1;
+        }
+        for k in 1..polygon.len().saturating_sub(1) {
+            if left_handed {
+                indices.extend([polygon[0], polygon[k + 1], polygon[k]]);
+            } else {
+                indices.extend([polygon[0], polygon[k], polygon[k + 1]]);
+            }
+        }
+    }
+
+    Ok(crate::Mesh::new(
+        vertices,
+        Some(Indices::U32(indices)),
+        RenderMode::Triangles,
+    ))
+}
+
+/// The `index`th group of `N` numbers
+fn element<const N: usize>(
+    values: &[f32],
+    index: usize,
+    what: &str,
+) -> Result<[f32; N], ModelError> {
+    let start = index.checked_mul(N);
+    let range = start.and_then(|start| Some(start..start.checked_add(N)?));
+    range.and_then(|range| values.get(range)).map_or_else(
+        || {
+            Err(ModelError::ModelParsing(format!(
+                "{what} index {index} is out of range"
+            )))
+        },
+        |value| Ok(std::array::from_fn(|k| value[k])),
+    )
+}
+
+/// Strips the property from a connection target like `</Material/Texture.outputs:rgb>`
+fn connected_prim<'a>(paths: &HashMap<String, &'a Prim>, target: &str) -> Option<&'a Prim> {
+    let path = target.split_once('.').map_or(target, |(path, _)| path);
+    paths.get(path).copied()
+}
+
+fn load_material(
+    material: &Prim,
+    path: &str,
+    paths: &HashMap<String, &Prim>,
+    model_dir: &Path,
+    package: Option<&Package>,
+    options: &LoadContext,
+) -> Result<crate::Material, ModelError> {
+    // The surface output connects to the shader, Fall back to any UsdPreviewSurface child
+    let surface = material
+        .value("outputs:surface.connect")
+        .and_then(|target| connected_prim(paths, target))
+        .or_else(|| {
+            material
+                .children
+                .iter()
+                .find(|child| child.value("info:id") == Some("UsdPreviewSurface"))
+        });
+    let (base_color, opacity, diffuse_texture) = match surface {
+        Some(surface) => {
+            let color = surface.numbers::<f32>("inputs:diffuseColor")?;
+            let color = match color.as_slice() {
+                [r, g, b, ..] => [*r, *g, *b],
+                _ => [0.18, 0.18, 0.18],
+            };
+            let opacity = surface
+                .numbers::<f32>("inputs:opacity")?
+                .first()
+                .copied()
+                .unwrap_or(1.0);
+            let texture = surface
+                .value("inputs:diffuseColor.connect")
+                .and_then(|target| connected_prim(paths, target))
+                .and_then(|texture| Some((texture, texture.value("inputs:file")?)))
+                .map(|(texture, file)| load_texture(texture, file, model_dir, package, options))
+                .transpose()?;
+            (color, opacity, texture)
+        }
+        None => ([0.18, 0.18, 0.18], 1.0, None),
+    };
+
+    Ok(crate::Material {
+        diffuse_texture,
+        normal_texture: None,
+        alpha_mode: if opacity < 1.0 {
+            crate::AlphaMode::Blend
+        } else {
+            crate::AlphaMode::Opaque
+        },
+        alpha_cutoff: None,
+        double_sided: false,
+        base_color: Some([base_color[0], base_color[1], base_color[2], opacity]),
+        transmission_factor: None,
+        transmission_texture: None,
+        thickness_factor: None,
+        attenuation_color: None,
+        attenuation_distance: None,
+        clearcoat_factor: None,
+        clearcoat_roughness_factor: None,
+        clearcoat_texture: None,
+        clearcoat_normal_texture: None,
+        ior: None,
+        sheen_color_factor: None,
+        sheen_roughness_factor: None,
+        specular_factor: None,
+        glossiness_factor: None,
+        specular_glossiness_texture: None,
+        displacement_texture: None,
+        reflection_texture: None,
+        name: Some(path.rsplit('/').next().unwrap_or(path).to_string()),
+        #[cfg(feature = "extras")]
+        extras: None,
+    })
+}
+
+fn load_texture(
+    texture: &Prim,
+    file: &str,
+    model_dir: &Path,
+    package: Option<&Package>,
+    options: &LoadContext,
+) -> Result<crate::Texture, ModelError> {
+    let file = file.trim_start_matches("./");
+    let packaged = package.and_then(|package| package.files.get(file));
+    let image = match packaged {
+        Some(data) => {
+            options.check_texture_bytes(data.len())?;
+            crate::Image::Memory {
+                data: data.clone(),
+                mime_type: crate::sniff::sniff_mime_type(data),
+            }
+        }
+        None => options
+            .resolve_texture(file)?
+            .unwrap_or_else(|| crate::Image::Path {
+                path: model_dir.join(file),
+                mime_type: None,
+            }),
+    };
+    let wrapping = |name| match texture.value(name) {
+        Some("clamp") => crate::WrappingMode::ClampToEdge,
+        Some("mirror") => crate::WrappingMode::MirroredRepeat,
+        _ => crate::WrappingMode::Repeat,
+    };
+    Ok(crate::Texture {
+        image,
+        sampler: crate::Sampler {
+            wrap_s: wrapping("inputs:wrapS"),
+            wrap_t: wrapping("inputs:wrapT"),
+            ..Default::default()
+        },
+        name: Some(texture.name.clone()),
+        color_space: crate::ColorSpace::Srgb,
+        tex_coord_set: 0,
+    })
+}
+
+/// Reads all Files of a zip archive, USDZ packages are never compressed
+fn read_zip(data: &[u8]) -> Result<Vec<(String, &[u8])>, ModelError> {
+    let invalid = || ModelError::ModelParsing("Invalid USDZ package".to_string());
+    let u16_at = |offset: usize| {
+        data.get(offset..offset + 2)
+            .map(|b| usize::from(u16::from_le_bytes([b[0], b[1]])))
+            .ok_or_else(invalid)
+    };
+    let u32_at = |offset: usize| {
+        data.get(offset..offset + 4)
+            .and_then(|b| usize::try_from(u32::from_le_bytes([b[0], b[1], b[2], b[3]])).ok())
+            .ok_or_else(invalid)
+    };
+
+    // The end of central directory record sits at the end, Followed by an optional comment
+    let end = (0..data.len().saturating_sub(21))
+        .rev()
+        .find(|&i| data[i..].starts_with(b"PK\x05\x06"))
+        .ok_or_else(invalid)?;
+    let count = u16_at(end + 10)?;
+    let mut entry = u32_at(end + 16)?;
+    let mut files = Vec::with_capacity(count);
+    for _ in 0..count {
+        if !data
+            .get(entry..)
+            .is_some_and(|d| d.starts_with(b"PK\x01\x02"))
+        {
+            return Err(invalid());
+        }
+        let method = u16_at(entry + 10)?;
+        let size = u32_at(entry + 20)?;
+        let name_len = u16_at(entry + 28)?;
+        let extra_len = u16_at(entry + 30)?;
+        let comment_len = u16_at(entry + 32)?;
+        let local = u32_at(entry + 42)?;
+        let name = data
+            .get(entry + 46..entry + 46 + name_len)
+            .ok_or_else(invalid)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        if method != 0 {
+            return Err(ModelError::ModelParsing(format!(
+                "USDZ File {name} is compressed, USDZ packages have to be stored uncompressed"
+            )));
+        }
+        let start = local + 30 + u16_at(local + 26)? + u16_at(local + 28)?;
+        let content = data.get(start..start + size).ok_or_else(invalid)?;
+        files.push((name, content));
+        entry += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(files)
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum Token {
+    /// One of `( ) [ ] { } = , ;`
+    Punct(char),
+    Word(String),
+    Str(String),
+    /// `@asset/path@`
+    Asset(String),
+    /// `</Prim/Path>`
+    Path(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, ModelError> {
+    let unterminated = || ModelError::ModelParsing("Unterminated string or path".to_string());
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '#' {
+            rest = rest.find('\n').map_or("", |end| &rest[end..]);
+        } else if "()[]{}=,;".contains(c) {
+            tokens.push(Token::Punct(c));
+            rest = &rest[1..];
+        } else if rest.starts_with("\"\"\"") || rest.starts_with("'''") {
+            let end = rest[3..].find(&rest[..3]).ok_or_else(unterminated)?;
+            tokens.push(Token::Str(rest[3..3 + end].to_string()));
+            rest = &rest[end + 6..];
+        } else if c == '"' || c == '\'' {
+            let mut value = String::new();
+            let mut chars = rest[1..].char_indices();
+            let end = loop {
+                match chars.next() {
+                    Some((i, q)) if q == c => break i + 2,
+                    Some((_, '\\')) => value.extend(chars.next().map(|(_, c)| c)),
+                    Some((_, c)) => value.push(c),
+                    None => return Err(unterminated()),
+                }
+            };
+            tokens.push(Token::Str(value));
+            rest = &rest[end..];
+        } else if c == '@' {
+            let delimiter = if rest.starts_with("@@@") { "@@@" } else { "@" };
+            let body = &rest[delimiter.len()..];
+            let end = body.find(delimiter).ok_or_else(unterminated)?;
+            tokens.push(Token::Asset(body[..end].to_string()));
+            rest = &body[end + delimiter.len()..];
+        } else if c == '<' {
+            let end = rest.find('>').ok_or_else(unterminated)?;
+            tokens.push(Token::Path(rest[1..end].to_string()));
+            rest = &rest[end + 1..];
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || "()[]{}=,;#\"'@<".contains(c))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses all root prims of a USDA layer
+fn parse_usda(text: &str) -> Result<Vec<Prim>, ModelError> {
+    if !text.starts_with("#usda") {
+        return Err(ModelError::ModelParsing("Missing #usda header".to_string()));
+    }
+    let mut parser = UsdaParser {
+        tokens: tokenize(text)?.into_iter().peekable(),
+    };
+    // Layer metadata
+    if parser.tokens.next_if_eq(&Token::Punct('(')).is_some() {
+        parser.skip_rest('(', ')')?;
+    }
+    let mut prims = Vec::new();
+    while let Some(token) = parser.tokens.next() {
+        match token {
+            Token::Word(specifier) if is_specifier(&specifier) => {
+                if let Some(prim) = parser.prim(&specifier)? {
+                    prims.push(prim);
+                }
+            }
+            _ => return Err(ModelError::ModelParsing("Expected a prim".to_string())),
+        }
+    }
+    Ok(prims)
+}
+
+fn is_specifier(word: &str) -> bool {
+    matches!(word, "def" | "over" | "class")
+}
+
+struct UsdaParser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+}
+
+impl UsdaParser {
+    fn word(&mut self) -> Result<String, ModelError> {
+        match self.tokens.next() {
+            Some(Token::Word(word)) => Ok(word),
+            _ => Err(ModelError::ModelParsing("Expected a name".to_string())),
+        }
+    }
+
+    fn skip_rest(&mut self, open: char, close: char) -> Result<(), ModelError> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.tokens.next() {
+                Some(Token::Punct(c)) if c == open => depth += 1,
+                Some(Token::Punct(c)) if c == close => depth -= 1,
+                Some(_) => {}
+                None => {
+                    return Err(ModelError::ModelParsing(
+                        "Unexpected end of File".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a prim after its specifier, `class` prims are templates and parsed but not returned
+    fn prim(&mut self, specifier: &str) -> Result<Option<Prim>, ModelError> {
+        let mut prim = Prim::default();
+        if let Some(Token::Word(kind)) = self.tokens.next_if(|t| matches!(t, Token::Word(_))) {
+            prim.kind = kind;
+        }
+        match self.tokens.next() {
+            Some(Token::Str(name)) => prim.name = name,
+            _ => return Err(ModelError::ModelParsing("Expected a prim name".to_string())),
+        }
+        if self.tokens.next_if_eq(&Token::Punct('(')).is_some() {
+            self.skip_rest('(', ')')?;
+        }
+        if self.tokens.next() != Some(Token::Punct('{')) {
+            return Err(ModelError::ModelParsing(format!(
+                "Expected the body of prim {}",
+                prim.name
+            )));
+        }
+        loop {
+            match self.tokens.next() {
+                Some(Token::Punct('}')) => break,
+                Some(Token::Punct(';')) => {}
+                Some(Token::Word(word)) if is_specifier(&word) => {
+                    prim.children.extend(self.prim(&word)?);
+                }
+                Some(Token::Word(word)) if word == "variantSet" => {
+                    // variantSet "name" = { ... }
+                    self.tokens.next();
+                    self.tokens.next();
+                    self.tokens.next();
+                    self.skip_rest('{', '}')?;
+                }
+                Some(Token::Word(word)) => prim.attributes.extend(self.property(word)?),
+                _ => return Err(ModelError::ModelParsing("Expected a property".to_string())),
+            }
+        }
+        Ok((specifier != "class").then_some(prim))
+    }
+
+    /// Parses a property starting with `word`, `None` for list edits like `reorder nameChildren`
+    fn property(&mut self, mut word: String) -> Result<Option<Attribute>, ModelError> {
+        let mut list_edit = false;
+        while matches!(
+            word.as_str(),
+            "custom" | "uniform" | "varying" | "prepend" | "append" | "delete" | "add" | "reorder"
+        ) {
+            list_edit |= matches!(word.as_str(), "reorder");
+            word = self.word()?;
+        }
+        let (type_name, name) = if word == "rel" || list_edit {
+            (word, self.word()?)
+        } else {
+            // Array types are written as `float3[]`
+            let mut type_name = word;
+            if self.tokens.next_if_eq(&Token::Punct('[')).is_some() {
+                self.skip_rest('[', ']')?;
+                type_name.push_str("[]");
+            }
+            (type_name, self.word()?)
+        };
+        let mut attribute = Attribute {
+            type_name,
+            name,
+            values: Vec::new(),
+            interpolation: None,
+        };
+        if self.tokens.next_if_eq(&Token::Punct('=')).is_some() {
+            self.value(&mut attribute.values)?;
+        }
+        if self.tokens.next_if_eq(&Token::Punct('(')).is_some() {
+            attribute.interpolation = self.metadata()?;
+        }
+        Ok((!list_edit).then_some(attribute))
+    }
+
+    /// Reads a value and flattens tuples and arrays into `values`
+    fn value(&mut self, values: &mut Vec<String>) -> Result<(), ModelError> {
+        match self.tokens.next() {
+            Some(Token::Punct(open @ ('(' | '['))) => {
+                let close = if open == '(' { ')' } else { ']' };
+                loop {
+                    if self.tokens.next_if_eq(&Token::Punct(close)).is_some() {
+                        break;
+                    }
+                    self.value(values)?;
+                    self.tokens.next_if_eq(&Token::Punct(','));
+                }
+            }
+            // Dictionaries and time samples are not supported
+            Some(Token::Punct('{')) => self.skip_rest('{', '}')?,
+            Some(Token::Word(word)) if word == "None" => {}
+            Some(
+                Token::Word(value) | Token::Str(value) | Token::Asset(value) | Token::Path(value),
+            ) => {
+                values.push(value);
+            }
+            _ => return Err(ModelError::ModelParsing("Expected a value".to_string())),
+        }
+        Ok(())
+    }
+
+    /// Reads attribute metadata, Only `interpolation` is kept
+    fn metadata(&mut self) -> Result<Option<String>, ModelError> {
+        let mut interpolation = None;
+        loop {
+            match self.tokens.next() {
+                Some(Token::Punct(')')) => break,
+                Some(Token::Word(key)) => {
+                    if self.tokens.next_if_eq(&Token::Punct('=')).is_some() {
+                        let mut values = Vec::new();
+                        self.value(&mut values)?;
+                        if key == "interpolation" {
+                            interpolation = values.into_iter().next();
+                        }
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    return Err(ModelError::ModelParsing(
+                        "Unexpected end of File".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(interpolation)
+    }
+}
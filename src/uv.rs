@@ -0,0 +1,106 @@
+use crate::Mesh;
+
+/// A coordinate axis, Used as the projection direction of [`Mesh::generate_planar_uvs`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    /// The position components which become U and V when projecting along the axis
+    const fn plane(self) -> [usize; 2] {
+        match self {
+            Self::X => [2, 1],
+            Self::Y => [0, 2],
+            Self::Z => [0, 1],
+        }
+    }
+
+    /// The axis the direction points along the most
+    fn dominant(direction: [f32; 3]) -> Self {
+        let [x, y, z] = direction.map(f32::abs);
+        if x >= y && x >= z {
+            Self::X
+        } else if y >= z {
+            Self::Y
+        } else {
+            Self::Z
+        }
+    }
+}
+
+impl Mesh {
+    /// Generates texture coordinates by projecting the Vertices along an axis, Overwriting existing ones
+    ///
+    /// A simple fallback for Meshes which have a textured Material but no UVs, Good enough for previews.
+    /// The bounding box is mapped onto 0..1, So the UVs span the whole texture. Projecting along `Axis::Z` maps X to U and Y to V,
+    /// `Axis::X` maps Z and Y and `Axis::Y` maps X and Z. Faces parallel to the axis get stretched texels
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::{Axis, Model3D};
+    ///
+    /// let mut model = Model3D::load("model.obj").expect("Failed to load");
+    /// for mesh in &mut model.meshes {
+    ///     if mesh.vertices.iter().any(|v| v.tex_coord.is_none()) {
+    ///         mesh.generate_planar_uvs(Axis::Y);
+    ///     }
+    /// }
+    /// ```
+    pub fn generate_planar_uvs(&mut self, axis: Axis) {
+        let Some((min, max)) = self.aabb() else {
+            return;
+        };
+        let [u, v] = axis.plane();
+        let scale = |i: usize, value: f32| {
+            let extent = max[i] - min[i];
+            if extent > 0.0 {
+                (value - min[i]) / extent
+            } else {
+                0.0
+            }
+        };
+        for vertex in &mut self.vertices {
+            let position = vertex.position;
+            vertex.tex_coord = Some([scale(u, position[u]), scale(v, position[v])]);
+        }
+    }
+
+    /// Generates texture coordinates by projecting every Vertex along the axis its normal points along the most, Overwriting existing ones
+    ///
+    /// Like a cube map around the Mesh, Which stretches less than a single planar projection on closed Meshes.
+    /// Vertex normals are used when present, Otherwise the normal of the first Triangle using the Vertex. Shared Vertices on edges
+    /// between differently oriented faces get only one projection, So split them first (e.g. with [`Mesh::compute_flat_normals`]) for clean seams.
+    /// All sides use the same scale, The longest side of the bounding box spans 0..1
+    pub fn generate_box_uvs(&mut self) {
+        let Some((min, max)) = self.aabb() else {
+            return;
+        };
+        let size = (0..3).map(|i| max[i] - min[i]).fold(0.0, f32::max);
+        let mut face_axes = vec![None; self.vertices.len()];
+        for ([a, b, c], normal) in self.triangles().zip(self.face_normals()) {
+            for vertex in [a, b, c] {
+                face_axes[vertex].get_or_insert_with(|| Axis::dominant(normal));
+            }
+        }
+        for (vertex, face_axis) in self.vertices.iter_mut().zip(face_axes) {
+            let axis = vertex
+                .normal
+                .map(Axis::dominant)
+                .or(face_axis)
+                .unwrap_or(Axis::Z);
+            let scale = |i: usize| {
+                if size > 0.0 {
+                    (vertex.position[i] - min[i]) / size
+                } else {
+                    0.0
+                }
+            };
+            let [u, v] = axis.plane();
+            vertex.tex_coord = Some([scale(u), scale(v)]);
+        }
+    }
+}
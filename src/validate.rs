@@ -0,0 +1,133 @@
+use crate::{math, Mesh, Model3D};
+
+/// A problem found by [`Model3D::validate`], `mesh` is the index into [`Model3D::meshes`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// The Mesh has no Vertices or nothing to draw
+    EmptyMesh { mesh: usize },
+    /// An index points past the Vertices
+    IndexOutOfRange { mesh: usize, index: usize },
+    /// A Morph Target attribute does not have one value per Vertex
+    MorphTargetLength {
+        mesh: usize,
+        target: usize,
+        len: usize,
+    },
+    /// `material_index` points past the Materials
+    MaterialOutOfRange { mesh: usize, material: usize },
+    /// A Vertex position contains NaN or infinity
+    NonFinitePosition { mesh: usize, vertex: usize },
+    /// A Vertex normal has zero (or non finite) length
+    ZeroLengthNormal { mesh: usize, vertex: usize },
+}
+
+impl Model3D {
+    /// Checks the Model for common problems which break renderers
+    ///
+    /// Nothing is modified and no error is returned, Every problem is reported as a [`ValidationWarning`].
+    /// An empty Vec means the Model is well formed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let model = Model3D::load("model.gltf").expect("Failed to load");
+    /// for warning in model.validate() {
+    ///     println!("{:?}", warning);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+        for (i, mesh) in self.meshes.iter().enumerate() {
+            if mesh.vertices.is_empty() || mesh.element_count() == 0 {
+                warnings.push(ValidationWarning::EmptyMesh { mesh: i });
+            }
+            if let Some(indices) = &mesh.indices {
+                warnings.extend(
+                    indices
+                        .iter()
+                        .map(|index| index as usize)
+                        .filter(|&index| index >= mesh.vertices.len())
+                        .map(|index| ValidationWarning::IndexOutOfRange { mesh: i, index }),
+                );
+            }
+            for (target, morph) in mesh.morph_targets.iter().enumerate() {
+                let lengths = [
+                    morph.positions.as_ref().map(Vec::len),
+                    morph.normals.as_ref().map(Vec::len),
+                    morph.tangents.as_ref().map(Vec::len),
+                ];
+                if let Some(len) = lengths
+                    .into_iter()
+                    .flatten()
+                    .find(|&len| len != mesh.vertices.len())
+                {
+                    warnings.push(ValidationWarning::MorphTargetLength {
+                        mesh: i,
+                        target,
+                        len,
+                    });
+                }
+            }
+            if let Some(material) = mesh
+                .material_index
+                .filter(|&material| material >= self.materials.len())
+            {
+                warnings.push(ValidationWarning::MaterialOutOfRange { mesh: i, material });
+            }
+            for (vertex, v) in mesh.vertices.iter().enumerate() {
+                if !v.position.iter().all(|p| p.is_finite()) {
+                    warnings.push(ValidationWarning::NonFinitePosition { mesh: i, vertex });
+                }
+                if v.normal.is_some_and(|normal| {
+                    let length = math::length(normal);
+                    !length.is_finite() || length == 0.0
+                }) {
+                    warnings.push(ValidationWarning::ZeroLengthNormal { mesh: i, vertex });
+                }
+            }
+        }
+        warnings
+    }
+}
+
+impl Mesh {
+    /// Replaces non finite values which would break GPU pipelines, Returns how many values were fixed
+    ///
+    /// NaN/infinite position and texture coordinate components become 0.0. Normals containing non finite values
+    /// are set to zero, Other normals which are not unit length are renormalized
+    pub fn sanitize(&mut self) -> usize {
+        fn zero_non_finite(values: &mut [f32]) -> usize {
+            let mut fixed = 0;
+            for value in values.iter_mut().filter(|value| !value.is_finite()) {
+                *value = 0.0;
+                fixed += 1;
+            }
+            fixed
+        }
+        let mut fixed = 0;
+        for vertex in &mut self.vertices {
+            let position = zero_non_finite(&mut vertex.position);
+            if position > 0 {
+                self.bounds = None;
+            }
+            fixed += position;
+            if let Some(tex_coord) = &mut vertex.tex_coord {
+                fixed += zero_non_finite(tex_coord);
+            }
+            if let Some(normal) = &mut vertex.normal {
+                let length = math::length(*normal);
+                if zero_non_finite(normal) > 0 {
+                    *normal = [0.0; 3];
+                    fixed += 1;
+                } else if length > 0.0 && (length - 1.0).abs() > 1e-4 {
+                    *normal = math::normalize(*normal);
+                    fixed += 1;
+                }
+            }
+        }
+        fixed
+    }
+}
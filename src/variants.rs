@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use crate::Model3D;
+
+/// The Materials of a Mesh for every variant of the Model, See [`Model3D::apply_variant`]
+///
+/// Only glTF supports variants (`KHR_materials_variants`), Used by product configurators to swap e.g. the color of a shoe
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaterialVariants {
+    /// The Material of the Mesh when no variant is selected
+    pub base: Option<usize>,
+    /// Material index of every variant name which uses another Material, Variants missing here use `base`
+    pub materials: HashMap<String, usize>,
+}
+
+impl Model3D {
+    /// Sets the `material_index` of every Mesh to the Material of the Given variant, See [`Model3D::variants`]
+    ///
+    /// Meshes without a mapping for the variant use their base Material, So unknown names select the base Materials of all Meshes.
+    /// Meshes without any variants stay untouched
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use modelz::Model3D;
+    ///
+    /// let mut model = Model3D::load("shoe.gltf").expect("Failed to load");
+    /// for variant in model.variants.clone() {
+    ///     model.apply_variant(&variant);
+    ///     // render the variant
+    /// }
+    /// ```
+    pub fn apply_variant(&mut self, name: &str) {
+        for mesh in &mut self.meshes {
+            if let Some(variants) = &mesh.material_variants {
+                mesh.material_index = variants.materials.get(name).copied().or(variants.base);
+            }
+        }
+    }
+}
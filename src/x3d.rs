@@ -0,0 +1,662 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::{options::LoadContext, Indices, Model3D, ModelError, RenderMode, Vertex};
+
+/// A VRML or X3D node, Its type, DEF name, field tokens and child nodes
+#[derive(Clone, Default)]
+struct Node {
+    kind: String,
+    name: Option<String>,
+    fields: Vec<(String, Vec<String>)>,
+    /// Nodes of all node valued fields, They are looked up by type so the field names do not matter
+    children: Vec<Self>,
+}
+
+impl Node {
+    fn field(&self, name: &str) -> Option<&[String]> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, values)| values.as_slice())
+    }
+
+    /// All numbers of a field, Empty if the field is missing
+    fn numbers<T: std::str::FromStr>(&self, name: &str) -> Result<Vec<T>, ModelError> {
+        self.field(name)
+            .unwrap_or_default()
+            .iter()
+            .map(|value| {
+                value.parse().map_err(|_| {
+                    ModelError::ModelParsing(format!("Invalid number {value} in field {name}"))
+                })
+            })
+            .collect()
+    }
+
+    fn vec3(&self, name: &str, default: [f32; 3]) -> Result<[f32; 3], ModelError> {
+        let values = self.numbers::<f32>(name)?;
+        if values.is_empty() {
+            return Ok(default);
+        }
+        values.get(..3).map_or_else(
+            || {
+                Err(ModelError::ModelParsing(format!(
+                    "Field {name} needs 3 numbers"
+                )))
+            },
+            |v| Ok([v[0], v[1], v[2]]),
+        )
+    }
+
+    fn float(&self, name: &str, default: f32) -> Result<f32, ModelError> {
+        Ok(self.numbers(name)?.first().copied().unwrap_or(default))
+    }
+
+    /// VRML writes `TRUE`/`FALSE`, X3D writes `true`/`false`
+    fn bool(&self, name: &str, default: bool) -> bool {
+        self.field(name)
+            .and_then(<[String]>::first)
+            .map_or(default, |value| value.eq_ignore_ascii_case("true"))
+    }
+
+    fn child(&self, kind: &str) -> Option<&Self> {
+        self.children.iter().find(|child| child.kind == kind)
+    }
+}
+
+/// Loads a VRML 2.0 (.wrl) or XML encoded X3D (.x3d) File
+///
+/// Every `Shape` with an `IndexedFaceSet` becomes a Mesh, Its `Appearance` becomes a Material.
+/// Polygons are triangulated as fans. `Transform`s are not applied and other geometry nodes are ignored
+pub fn load(path: &Path, options: &LoadContext) -> Result<Model3D, ModelError> {
+    let content = crate::source::read_to_string(path, options)?;
+    let nodes = if content.trim_start().starts_with('<') {
+        parse_xml(&content)?
+    } else {
+        parse_vrml(&content)?
+    };
+
+    let model_dir = path.parent().unwrap_or_else(|| Path::new("./"));
+    let mut model = Model3D::new(Vec::new(), Vec::new(), crate::ModelFormat::X3D);
+    let mut stack = nodes.iter().rev().collect::<Vec<_>>();
+    while let Some(node) = stack.pop() {
+        if node.kind == "Shape" {
+            load_shape(node, model_dir, options, &mut model)?;
+        } else {
+            stack.extend(node.children.iter().rev());
+        }
+    }
+    Ok(model)
+}
+
+fn load_shape(
+    shape: &Node,
+    model_dir: &Path,
+    options: &LoadContext,
+    model: &mut Model3D,
+) -> Result<(), ModelError> {
+    let Some(geometry) = shape.child("IndexedFaceSet") else {
+        return Ok(());
+    };
+    let mut mesh = load_face_set(geometry, options)?;
+    mesh.name = shape.name.clone().or_else(|| geometry.name.clone());
+    if let Some(appearance) = shape.child("Appearance") {
+        // Culling is a property of the geometry in VRML, `solid` is true by default
+        let double_sided = !geometry.bool("solid", true);
+        let material = load_material(appearance, double_sided, model_dir, options)?;
+        model.materials.push(material);
+        mesh.material_index = Some(model.materials.len() - 1);
+    }
+    model.meshes.push(mesh);
+    Ok(())
+}
+
+fn load_face_set(node: &Node, options: &LoadContext) -> Result<crate::Mesh, ModelError> {
+    let attribute = |kind: &str, field: &str| {
+        node.child(kind)
+            .map_or_else(|| Ok(Vec::new()), |child| child.numbers::<f32>(field))
+    };
+    let positions = attribute("Coordinate", "point")?;
+    let normals = attribute("Normal", "vector")?;
+    let tex_coords = attribute("TextureCoordinate", "point")?;
+    let coord_index = node.numbers::<i64>("coordIndex")?;
+    let normal_index = node.numbers::<i64>("normalIndex")?;
+    let tex_coord_index = node.numbers::<i64>("texCoordIndex")?;
+    let normal_per_vertex = node.bool("normalPerVertex", true);
+    let ccw = node.bool("ccw", true);
+    options.check_vertices(positions.len() / 3)?;
+    options.check_indices(coord_index.len())?;
+
+    let mut vertices = Vec::new();
+    let mut unique = HashMap::new();
+    let mut indices = Vec::new();
+    let mut face = Vec::new();
+    let mut face_number = 0;
+    // A missing -1 after the last polygon still ends it
+    for (corner, &position) in coord_index.iter().chain([&-1]).enumerate() {
+        if position < 0 {
+            for k in 1..face.len().saturating_sub(1) {
+                if ccw {
+                    indices.extend([face[0], face[k], face[k + 1]]);
+                } else {
+                    indices.extend([face[0], face[k + 1], face[k]]);
+                }
+            }
+            face.clear();
+            face_number += 1;
+            continue;
+        }
+        // Missing index lists fall back to coordIndex, Or the face number for per face normals
+        let normal = (!normals.is_empty()).then(|| {
+            if normal_per_vertex {
+                normal_index.get(corner).copied().unwrap_or(position)
+            } else {
+                normal_index
+                    .get(face_number)
+                    .copied()
+                    .unwrap_or_else(|| i64::try_from(face_number).unwrap_or(i64::MAX))
+            }
+        });
+        let tex_coord = (!tex_coords.is_empty())
+            .then(|| tex_coord_index.get(corner).copied().unwrap_or(position));
+
+        let key = (position, normal, tex_coord);
+        let index = if let Some(&index) = unique.get(&key) {
+            index
+        } else {
+            vertices.push(Vertex {
+                position: element(&positions, position, "Coordinate")?,
+                normal: normal.map(|i| element(&normals, i, "Normal")).transpose()?,
+                tex_coord: tex_coord
+                    .map(|i| element(&tex_coords, i, "TextureCoordinate"))
+                    .transpose()?,
+                ..Default::default()
+            });
+            let index = u32::try_from(vertices.len() - 1)
+                .map_err(|_| ModelError::ModelParsing("Too many Vertices".to_string()))?;
+            unique.insert(key, index);
+            index
+        };
+        face.push(index);
+    }
+
+    Ok(crate::Mesh::new(
+        vertices,
+        Some(Indices::U32(indices)),
+        RenderMode::Triangles,
+    ))
+}
+
+/// The `index`th group of `N` numbers
+fn element<const N: usize>(values: &[f32], index: i64, what: &str) -> Result<[f32; N], ModelError> {
+    usize::try_from(index)
+        .ok()
+        .and_then(|i| i.checked_mul(N))
+        .and_then(|start| values.get(start..start.checked_add(N)?))
+        .map_or_else(
+            || {
+                Err(ModelError::ModelParsing(format!(
+                    "{what} index {index} is out of range"
+                )))
+            },
+            |value| Ok(std::array::from_fn(|k| value[k])),
+        )
+}
+
+fn load_material(
+    appearance: &Node,
+    double_sided: bool,
+    model_dir: &Path,
+    options: &LoadContext,
+) -> Result<crate::Material, ModelError> {
+    let material = appearance.child("Material");
+    let (base_color, transparency) = match material {
+        Some(material) => (
+            material.vec3("diffuseColor", [0.8, 0.8, 0.8])?,
+            material.float("transparency", 0.0)?,
+        ),
+        None => ([1.0, 1.0, 1.0], 0.0),
+    };
+    let diffuse_texture = appearance
+        .child("ImageTexture")
+        .and_then(|texture| Some((texture, texture.field("url")?.first()?)))
+        .map(|(texture, url)| load_texture(texture, url, model_dir, options))
+        .transpose()?;
+
+    Ok(crate::Material {
+        diffuse_texture,
+        normal_texture: None,
+        alpha_mode: if transparency > 0.0 {
+            crate::AlphaMode::Blend
+        } else {
+            crate::AlphaMode::Opaque
+        },
+        alpha_cutoff: None,
+        double_sided,
+        base_color: Some([
+            base_color[0],
+            base_color[1],
+            base_color[2],
+            1.0 - transparency,
+        ]),
+        transmission_factor: None,
+        transmission_texture: None,
+        thickness_factor: None,
+        attenuation_color: None,
+        attenuation_distance: None,
+        clearcoat_factor: None,
+        clearcoat_roughness_factor: None,
+        clearcoat_texture: None,
+        clearcoat_normal_texture: None,
+        ior: None,
+        sheen_color_factor: None,
+        sheen_roughness_factor: None,
+        specular_factor: None,
+        glossiness_factor: None,
+        specular_glossiness_texture: None,
+        displacement_texture: None,
+        reflection_texture: None,
+        name: material
+            .and_then(|material| material.name.clone())
+            .or_else(|| appearance.name.clone()),
+        #[cfg(feature = "extras")]
+        extras: None,
+    })
+}
+
+fn load_texture(
+    texture: &Node,
+    url: &str,
+    model_dir: &Path,
+    options: &LoadContext,
+) -> Result<crate::Texture, ModelError> {
+    let image = options
+        .resolve_texture(url)?
+        .unwrap_or_else(|| crate::Image::Path {
+            path: model_dir.join(url),
+            mime_type: None,
+        });
+    let wrapping = |field| {
+        if texture.bool(field, true) {
+            crate::WrappingMode::Repeat
+        } else {
+            crate::WrappingMode::ClampToEdge
+        }
+    };
+    Ok(crate::Texture {
+        image,
+        sampler: crate::Sampler {
+            wrap_s: wrapping("repeatS"),
+            wrap_t: wrapping("repeatT"),
+            ..Default::default()
+        },
+        name: texture.name.clone(),
+        color_space: crate::ColorSpace::Srgb,
+        tex_coord_set: 0,
+    })
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    OpenList,
+    CloseList,
+    Word(String),
+    Str(String),
+}
+
+impl Token {
+    /// Numbers, Booleans and strings, Everything which is a field value and not a node
+    fn value(&self) -> Option<&str> {
+        match self {
+            Self::Str(value) => Some(value),
+            Self::Word(word)
+                if word == "TRUE" || word == "FALSE" || word.parse::<f64>().is_ok() =>
+            {
+                Some(word)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Splits VRML text (or an X3D attribute value) into tokens, Commas count as whitespace
+fn tokenize(text: &str) -> Result<Vec<Token>, ModelError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            '{' => tokens.push(Token::Open),
+            '}' => tokens.push(Token::Close),
+            '[' => tokens.push(Token::OpenList),
+            ']' => tokens.push(Token::CloseList),
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => value.extend(chars.next()),
+                        Some(c) => value.push(c),
+                        None => {
+                            return Err(ModelError::ModelParsing("Unterminated string".to_string()))
+                        }
+                    }
+                }
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_whitespace() || c == ',' => {}
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|&c| {
+                    !c.is_whitespace() && !matches!(c, ',' | '{' | '}' | '[' | ']' | '"' | '#')
+                }) {
+                    word.push(c);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses all top level nodes of a VRML 2.0 File
+fn parse_vrml(content: &str) -> Result<Vec<Node>, ModelError> {
+    if !content.starts_with("#VRML V2.0") {
+        return Err(ModelError::ModelParsing(
+            "Missing #VRML V2.0 header, Only VRML 2.0 is supported".to_string(),
+        ));
+    }
+    let mut parser = VrmlParser {
+        tokens: tokenize(content)?.into_iter().peekable(),
+        defs: HashMap::new(),
+    };
+    let mut nodes = Vec::new();
+    while let Some(token) = parser.tokens.next() {
+        let Token::Word(word) = token else {
+            return Err(ModelError::ModelParsing("Expected a node".to_string()));
+        };
+        if !parser.skip_statement(&word)? {
+            nodes.push(parser.node(word)?);
+        }
+    }
+    Ok(nodes)
+}
+
+struct VrmlParser {
+    tokens: std::iter::Peekable<std::vec::IntoIter<Token>>,
+    defs: HashMap<String, Node>,
+}
+
+impl VrmlParser {
+    fn word(&mut self) -> Result<String, ModelError> {
+        match self.tokens.next() {
+            Some(Token::Word(word)) => Ok(word),
+            _ => Err(ModelError::ModelParsing("Expected a name".to_string())),
+        }
+    }
+
+    fn expect(&mut self, expected: &Token) -> Result<(), ModelError> {
+        if self.tokens.next().as_ref() == Some(expected) {
+            Ok(())
+        } else {
+            Err(ModelError::ModelParsing("Unexpected token".to_string()))
+        }
+    }
+
+    /// Skips `ROUTE`s and prototype declarations, Returns `false` if `word` starts none of them
+    fn skip_statement(&mut self, word: &str) -> Result<bool, ModelError> {
+        match word {
+            "ROUTE" => {
+                // ROUTE node.field TO node.field
+                for _ in 0..3 {
+                    self.word()?;
+                }
+            }
+            "PROTO" | "EXTERNPROTO" => {
+                self.word()?;
+                self.skip_block(&Token::OpenList, &Token::CloseList)?;
+                if word == "PROTO" {
+                    self.skip_block(&Token::Open, &Token::Close)?;
+                } else if self.tokens.next_if_eq(&Token::OpenList).is_some() {
+                    self.skip_rest(&Token::OpenList, &Token::CloseList)?;
+                } else {
+                    self.tokens.next();
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn skip_block(&mut self, open: &Token, close: &Token) -> Result<(), ModelError> {
+        self.expect(open)?;
+        self.skip_rest(open, close)
+    }
+
+    fn skip_rest(&mut self, open: &Token, close: &Token) -> Result<(), ModelError> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.tokens.next() {
+                Some(token) if token == *open => depth += 1,
+                Some(token) if token == *close => depth -= 1,
+                Some(_) => {}
+                None => {
+                    return Err(ModelError::ModelParsing(
+                        "Unexpected end of File".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a node starting with `word`, Which is its type, `DEF` or `USE`
+    fn node(&mut self, word: String) -> Result<Node, ModelError> {
+        match word.as_str() {
+            "USE" => {
+                let name = self.word()?;
+                self.defs
+                    .get(&name)
+                    .cloned()
+                    .ok_or_else(|| ModelError::ModelParsing(format!("USE of unknown node {name}")))
+            }
+            "DEF" => {
+                let name = self.word()?;
+                let kind = self.word()?;
+                let mut node = self.node(kind)?;
+                node.name = Some(name.clone());
+                self.defs.insert(name, node.clone());
+                Ok(node)
+            }
+            _ => {
+                let mut node = Node {
+                    kind: word,
+                    ..Default::default()
+                };
+                self.expect(&Token::Open)?;
+                loop {
+                    match self.tokens.next() {
+                        Some(Token::Close) => break,
+                        Some(Token::Word(field)) => {
+                            if !self.skip_statement(&field)? {
+                                self.field(&mut node, field)?;
+                            }
+                        }
+                        _ => return Err(ModelError::ModelParsing("Expected a field".to_string())),
+                    }
+                }
+                Ok(node)
+            }
+        }
+    }
+
+    fn field(&mut self, node: &mut Node, field: String) -> Result<(), ModelError> {
+        let mut values = Vec::new();
+        match self.tokens.peek() {
+            Some(Token::OpenList) => {
+                self.tokens.next();
+                loop {
+                    match self.tokens.next() {
+                        Some(Token::CloseList) => break,
+                        Some(token) => {
+                            if let Some(value) = token.value() {
+                                values.push(value.to_string());
+                            } else if let Token::Word(word) = token {
+                                if !self.skip_statement(&word)? {
+                                    node.children.push(self.node(word)?);
+                                }
+                            }
+                        }
+                        None => {
+                            return Err(ModelError::ModelParsing(
+                                "Unexpected end of File".to_string(),
+                            ))
+                        }
+                    }
+                }
+            }
+            Some(Token::Str(_)) => values.extend(self.tokens.next().and_then(|t| match t {
+                Token::Str(value) => Some(value),
+                _ => None,
+            })),
+            Some(Token::Word(word)) if word == "NULL" => {
+                self.tokens.next();
+            }
+            Some(Token::Word(word)) if word == "TRUE" || word == "FALSE" => {
+                values.push(word.clone());
+                self.tokens.next();
+            }
+            Some(token) if token.value().is_some() => {
+                // Single values like SFVec3f are written without brackets
+                while let Some(Token::Word(word)) =
+                    self.tokens.next_if(|token| token.value().is_some())
+                {
+                    values.push(word);
+                }
+            }
+            Some(Token::Word(_)) => {
+                let word = self.word()?;
+                node.children.push(self.node(word)?);
+            }
+            _ => {
+                return Err(ModelError::ModelParsing(format!(
+                    "Missing value for {field}"
+                )))
+            }
+        }
+        node.fields.push((field, values));
+        Ok(())
+    }
+}
+
+/// Parses all top level elements of an XML encoded X3D File
+fn parse_xml(content: &str) -> Result<Vec<Node>, ModelError> {
+    let unexpected_end = || ModelError::ModelParsing("Unexpected end of File".to_string());
+    let mut defs: HashMap<String, Node> = HashMap::new();
+    let mut stack: Vec<(Node, Option<String>)> = Vec::new();
+    let mut nodes = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        // Comments, Processing instructions and the DOCTYPE are skipped
+        for (open, close) in [("<!--", "-->"), ("<?", "?>"), ("<!", ">")] {
+            if rest.starts_with(open) {
+                let end = rest.find(close).ok_or_else(unexpected_end)?;
+                rest = &rest[end + close.len()..];
+            }
+        }
+        if !rest.starts_with('<') {
+            continue;
+        }
+        let closing = rest.starts_with("</");
+        let end = tag_end(rest).ok_or_else(unexpected_end)?;
+        let tag = &rest[if closing { 2 } else { 1 }..end];
+        rest = &rest[end + 1..];
+
+        let self_closing = tag.ends_with('/');
+        if !closing {
+            let tag = tag.trim_end_matches('/');
+            let (kind, mut attributes) = tag
+                .split_once(|c: char| c.is_whitespace())
+                .unwrap_or((tag, ""));
+            let mut node = Node {
+                kind: kind.to_string(),
+                ..Default::default()
+            };
+            let mut use_name = None;
+            while let Some((name, value)) = attributes.split_once('=') {
+                let value = value.trim_start();
+                let quote = value.chars().next().ok_or_else(unexpected_end)?;
+                if quote != '"' && quote != '\'' {
+                    return Err(ModelError::ModelParsing(format!(
+                        "Attribute {} is not quoted",
+                        name.trim()
+                    )));
+                }
+                let value = &value[quote.len_utf8()..];
+                let end = value.find(quote).ok_or_else(unexpected_end)?;
+                let (name, text) = (name.trim(), unescape(&value[..end]));
+                attributes = &value[end + 1..];
+                match name {
+                    "DEF" => node.name = Some(text),
+                    "USE" => use_name = Some(text),
+                    _ => {
+                        let values = tokenize(&text)?
+                            .into_iter()
+                            .filter_map(|token| match token {
+                                Token::Word(value) | Token::Str(value) => Some(value),
+                                _ => None,
+                            })
+                            .collect();
+                        node.fields.push((name.to_string(), values));
+                    }
+                }
+            }
+            stack.push((node, use_name));
+        }
+        if closing || self_closing {
+            let (node, use_name) = stack
+                .pop()
+                .ok_or_else(|| ModelError::ModelParsing("Unexpected closing tag".to_string()))?;
+            let node = match use_name {
+                Some(name) => defs.get(&name).cloned().ok_or_else(|| {
+                    ModelError::ModelParsing(format!("USE of unknown node {name}"))
+                })?,
+                None => node,
+            };
+            if let Some(name) = &node.name {
+                defs.insert(name.clone(), node.clone());
+            }
+            match stack.last_mut() {
+                Some((parent, _)) => parent.children.push(node),
+                None => nodes.push(node),
+            }
+        }
+    }
+    Ok(nodes)
+}
+
+/// Position of the `>` ending the tag at the start of `text`, Ignoring any inside quoted attribute values
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    text.char_indices().find_map(|(i, c)| {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+        None
+    })
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
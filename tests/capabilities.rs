@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod capabilities {
+    use modelz::ModelFormat;
+
+    #[test]
+    fn capabilities() {
+        let stl = ModelFormat::STL.capabilities();
+        assert!(!stl.supports_materials);
+        assert!(!stl.supports_multiple_meshes);
+        assert!(stl.supports_normals);
+
+        let gltf = ModelFormat::GLTF.capabilities();
+        assert!(gltf.supports_animation);
+        assert!(gltf.supports_materials);
+
+        // Faces are split into one Mesh per `material_index`
+        let ply = ModelFormat::PLY.capabilities();
+        assert!(ply.supports_multiple_meshes);
+        assert!(!ply.supports_materials);
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(test)]
+mod dxf {
+    use modelz::{Model3D, RenderMode};
+
+    #[test]
+    fn load_dxf() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/faces.dxf");
+
+        let model = Model3D::load(model_path).expect("Failed to load dxf model");
+        // A quad and a triangle 3DFACE
+        let roof = model.mesh_by_name("Roof").expect("Roof layer not found");
+        assert_eq!(roof.triangles().count(), 3);
+        // A polyface quad
+        let floor = model.mesh_by_name("Floor").expect("Floor layer not found");
+        assert_eq!(floor.triangles().count(), 2);
+        assert!(floor.vertices.iter().all(|v| v.position[2] == 0.0));
+        let edges = model.mesh_by_name("Edges").expect("Edges layer not found");
+        assert_eq!(edges.mode, RenderMode::Lines);
+        assert_eq!(edges.vertices.len(), 2);
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(test)]
+mod features {
+    use modelz::{Model3D, ModelError};
+
+    #[cfg(not(feature = "gltf"))]
+    #[test]
+    fn gltf_disabled() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.glb");
+
+        let result = Model3D::load(model_path);
+        assert!(matches!(result, Err(ModelError::FeatureDisabled("gltf"))));
+    }
+
+    #[test]
+    fn unknown_extension() {
+        // Material Libraries are no Model Format, Which no feature could change
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.mtl");
+
+        let result = Model3D::load(model_path);
+        assert!(matches!(result, Err(ModelError::UnknowFormat)));
+    }
+}
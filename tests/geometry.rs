@@ -0,0 +1,714 @@
+#[cfg(test)]
+mod geometry {
+    use modelz::{Axis, CropMode, Indices, Mesh, Model3D, RenderMode, Vertex, VertexAttr};
+
+    fn load_cube() -> Model3D {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        Model3D::from_format(model_path, &modelz::ModelFormat::OBJ)
+            .expect("Failed to load obj model")
+    }
+
+    fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+        a.iter().zip(&b).map(|(a, b)| (a - b) * (a - b)).sum()
+    }
+
+    fn sphere(rings: u32, segments: u32) -> Mesh {
+        let mut vertices = Vec::new();
+        for ring in 0..=rings {
+            let theta = std::f32::consts::PI * ring as f32 / rings as f32;
+            for segment in 0..=segments {
+                let phi = std::f32::consts::TAU * segment as f32 / segments as f32;
+                vertices.push(Vertex {
+                    position: [
+                        theta.sin() * phi.cos(),
+                        theta.cos(),
+                        theta.sin() * phi.sin(),
+                    ],
+                    ..Default::default()
+                });
+            }
+        }
+        let mut indices = Vec::new();
+        for ring in 0..rings {
+            for segment in 0..segments {
+                let a = ring * (segments + 1) + segment;
+                let b = a + segments + 1;
+                indices.extend([a, b, a + 1, a + 1, b, b + 1]);
+            }
+        }
+        Mesh::new(vertices, Some(Indices::U32(indices)), RenderMode::Triangles)
+    }
+
+    #[test]
+    fn face_normals() {
+        let model = load_cube();
+        let normals = model.meshes[0].face_normals();
+        assert_eq!(normals.len(), 12);
+
+        let mut distinct: Vec<[f32; 3]> = Vec::new();
+        for normal in &normals {
+            // Every normal is axis aligned
+            assert_eq!(normal.iter().filter(|v| v.abs() == 1.0).count(), 1);
+            assert_eq!(normal.iter().filter(|v| **v == 0.0).count(), 2);
+            if !distinct.contains(normal) {
+                distinct.push(*normal);
+            }
+        }
+        assert_eq!(distinct.len(), 6);
+        for normal in &distinct {
+            assert_eq!(normals.iter().filter(|n| *n == normal).count(), 2);
+        }
+    }
+
+    #[test]
+    fn raycast() {
+        let model = load_cube();
+        let hit = model.meshes[0]
+            .raycast([0.5, 0.5, 5.0], [0.0, 0.0, -1.0])
+            .expect("Ray should hit the cube");
+        assert!((hit.distance - 4.0).abs() < 1e-5);
+        assert!((hit.point[2] - 1.0).abs() < 1e-5);
+        let tex_coord = hit.tex_coord.expect("Cube has texture coordinates");
+        assert!((tex_coord[0] - 0.5625).abs() < 1e-5);
+        assert!((tex_coord[1] - 0.8125).abs() < 1e-5);
+        let normal = hit.normal.unwrap();
+        assert!((normal[2] - 1.0).abs() < 1e-5);
+
+        assert!(model.meshes[0]
+            .raycast([5.0, 5.0, 5.0], [0.0, 0.0, -1.0])
+            .is_none());
+    }
+
+    #[test]
+    fn closest_point() {
+        let model = load_cube();
+        let closest = model.meshes[0]
+            .closest_point([3.0, 0.2, 0.3])
+            .expect("Cube has triangles");
+        let expected = [1.0, 0.2, 0.3];
+        for (a, b) in closest.point.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-5);
+        }
+        assert!((closest.distance - 2.0).abs() < 1e-5);
+        let normal = model.meshes[0].face_normals()[closest.triangle];
+        assert_eq!(normal, [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn bvh_matches_brute_force() {
+        let mesh = sphere(32, 64);
+        let triangle_count = mesh.triangles().count();
+        let bvh = mesh.build_bvh();
+
+        let mut queries = 0;
+        for i in 0..50 {
+            let angle = i as f32 * 0.37;
+            let origin = [3.0 * angle.cos(), (i as f32 * 0.1) - 2.5, 3.0 * angle.sin()];
+            let dir = [-origin[0], 0.3 - origin[1], -origin[2]];
+            let expected = mesh.raycast(origin, dir);
+            let actual = bvh.raycast(origin, dir);
+            // Rays hitting a shared edge may report either triangle, So only compare the distance
+            assert_eq!(expected.is_some(), actual.is_some());
+            if let (Some(expected), Some(actual)) = (expected, actual) {
+                assert!((expected.distance - actual.distance).abs() < 1e-5);
+            }
+
+            let expected = mesh.closest_point(origin).unwrap();
+            let actual = bvh.closest_point(origin).unwrap();
+            assert!((expected.distance - actual.distance).abs() < 1e-5);
+            queries += 2;
+        }
+        // Every query only tests a small part of all triangles
+        assert!(bvh.triangle_tests() < queries * triangle_count / 10);
+    }
+
+    #[test]
+    fn remove_unused_vertices() {
+        let mut model = load_cube();
+        let cube = &mut model.meshes[0];
+        let expected = cube
+            .triangles()
+            .map(|triangle| triangle.map(|i| cube.vertices[i].position))
+            .collect::<Vec<_>>();
+
+        // Orphans at the front and the back
+        let orphan = Vertex {
+            position: [9.0, 9.0, 9.0],
+            ..Default::default()
+        };
+        cube.vertices.insert(0, orphan.clone());
+        cube.vertices.push(orphan);
+        let shifted = cube.indices.as_ref().unwrap().iter().map(|i| i + 1);
+        cube.indices = Some(Indices::U32(shifted.collect()));
+
+        cube.remove_unused_vertices();
+        assert_eq!(cube.vertices.len(), 24);
+        assert!(cube.vertices.iter().all(|v| v.position != [9.0; 3]));
+        let triangles = cube
+            .triangles()
+            .map(|triangle| triangle.map(|i| cube.vertices[i].position))
+            .collect::<Vec<_>>();
+        assert_eq!(triangles, expected);
+    }
+
+    #[test]
+    fn crop_to_aabb() {
+        let area = |mesh: &Mesh| -> f32 {
+            mesh.triangles()
+                .map(|[a, b, c]| {
+                    let [a, b, c] = [a, b, c].map(|i| mesh.vertices[i].position);
+                    let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+                    let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+                    let cross = [
+                        ab[1] * ac[2] - ab[2] * ac[1],
+                        ab[2] * ac[0] - ab[0] * ac[2],
+                        ab[0] * ac[1] - ab[1] * ac[0],
+                    ];
+                    (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt() / 2.0
+                })
+                .sum()
+        };
+        // Keep the +X half space of the cube, Which spans -1..1 on every axis
+        let (min, max) = ([0.0, -2.0, -2.0], [2.0, 2.0, 2.0]);
+
+        let mut model = load_cube();
+        let cube = &mut model.meshes[0];
+        cube.crop_to_aabb(min, max);
+        // Only the two Triangles of the -X face are fully outside
+        assert_eq!(cube.triangles().count(), 10);
+        assert!(cube.vertices.iter().any(|v| v.position[0] < 0.0));
+
+        let mut model = load_cube();
+        let cube = &mut model.meshes[0];
+        cube.crop_to_aabb_with(min, max, CropMode::Clip);
+        assert!(cube.vertices.iter().all(|v| v.position[0] >= -1e-6));
+        assert!(cube.vertices.iter().all(|v| v.normal.is_some()));
+        // The +X face plus half of the four side faces
+        assert!((area(cube) - 12.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn bounding_sphere() {
+        let model = load_cube();
+        let (center, radius) = model.meshes[0].bounding_sphere().unwrap();
+        // The cube spans -1..1 on every axis
+        assert!(center.iter().all(|c| c.abs() < 1e-5));
+        assert!(radius >= 3.0_f32.sqrt() - 1e-5);
+        assert!(model.meshes[0].vertices.iter().all(|v| {
+            let d = [0, 1, 2].map(|k| v.position[k] - center[k]);
+            (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt() <= radius + 1e-5
+        }));
+        assert!(model.bounding_sphere().is_some());
+
+        let empty = Mesh::new(Vec::new(), None, RenderMode::Triangles);
+        assert!(empty.bounding_sphere().is_none());
+    }
+
+    #[test]
+    fn flip_normals() {
+        let mut model = load_cube();
+        let before = model.meshes[0].triangles().collect::<Vec<_>>();
+        let normals = model.meshes[0]
+            .vertices
+            .iter()
+            .map(|v| v.normal.unwrap())
+            .collect::<Vec<_>>();
+
+        model.flip_normals(false);
+        let cube = &model.meshes[0];
+        for (vertex, normal) in cube.vertices.iter().zip(&normals) {
+            assert_eq!(vertex.normal, Some(normal.map(|n| -n)));
+        }
+        assert_eq!(cube.triangles().collect::<Vec<_>>(), before);
+
+        model.flip_normals(true);
+        let cube = &model.meshes[0];
+        for (vertex, normal) in cube.vertices.iter().zip(&normals) {
+            assert_eq!(vertex.normal, Some(*normal));
+        }
+        let reversed = before
+            .iter()
+            .map(|[a, b, c]| [*a, *c, *b])
+            .collect::<Vec<_>>();
+        assert_eq!(cube.triangles().collect::<Vec<_>>(), reversed);
+    }
+
+    #[test]
+    fn aabb() {
+        let model = load_cube();
+        assert_eq!(
+            model.meshes[0].aabb(),
+            Some(([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0]))
+        );
+        assert_eq!(model.aabb(), model.meshes[0].aabb());
+        assert_eq!(model.meshes[0].triangle_count(), 12);
+
+        let empty = Mesh::new(Vec::new(), None, RenderMode::Triangles);
+        assert!(empty.aabb().is_none());
+    }
+
+    #[test]
+    fn centroid() {
+        let model = load_cube();
+        let (min, max) = model.meshes[0].aabb().expect("Empty Mesh");
+        let center: [f32; 3] = std::array::from_fn(|k| (min[k] + max[k]) * 0.5);
+        let centroid = model.meshes[0].centroid().expect("Empty Mesh");
+        assert!(distance(centroid, center) < 1e-5);
+        assert_eq!(model.centroid(), Some(centroid));
+
+        let empty = Mesh::new(Vec::new(), None, RenderMode::Triangles);
+        assert!(empty.centroid().is_none());
+    }
+
+    #[test]
+    fn convert_topology() {
+        let vertices = (0..5)
+            .map(|i| Vertex {
+                position: [i as f32, 0.0, 0.0],
+                ..Default::default()
+            })
+            .collect();
+        let mut mesh = Mesh::new(vertices, None, RenderMode::TriangleFan);
+        mesh.convert_topology(RenderMode::Triangles).unwrap();
+        assert_eq!(mesh.mode, RenderMode::Triangles);
+        assert_eq!(
+            mesh.indices.as_ref().unwrap().iter().collect::<Vec<_>>(),
+            [0, 1, 2, 0, 2, 3, 0, 3, 4]
+        );
+
+        let mut lines = Mesh::new(
+            Vec::new(),
+            Some(Indices::U16(vec![4, 5, 6])),
+            RenderMode::LineLoop,
+        );
+        lines.convert_topology(RenderMode::Lines).unwrap();
+        assert_eq!(
+            lines.indices.as_ref().unwrap().iter().collect::<Vec<_>>(),
+            [4, 5, 5, 6, 6, 4]
+        );
+        assert!(lines.convert_topology(RenderMode::Triangles).is_err());
+    }
+
+    #[test]
+    fn triangulated_copy() {
+        let vertices = (0..5)
+            .map(|i| Vertex {
+                position: [i as f32, (i % 2) as f32, 0.0],
+                ..Default::default()
+            })
+            .collect();
+        let mut strip = Mesh::new(vertices, None, RenderMode::TriangleStrip);
+        strip.material_index = Some(2);
+
+        let copy = strip.triangulated();
+        assert_eq!(strip.mode, RenderMode::TriangleStrip);
+        assert!(strip.indices.is_none());
+        assert_eq!(copy.mode, RenderMode::Triangles);
+        assert_eq!(copy.material_index, Some(2));
+        assert_eq!(copy.vertices.len(), strip.vertices.len());
+        assert_eq!(
+            copy.indices.as_ref().unwrap().iter().collect::<Vec<_>>(),
+            [0, 1, 2, 2, 1, 3, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn generate_uvs() {
+        let vertices = [[1.0, 2.0], [3.0, 2.0], [3.0, 6.0], [1.0, 6.0]]
+            .map(|[x, y]| Vertex {
+                position: [x, y, 5.0],
+                ..Default::default()
+            })
+            .to_vec();
+        let mut quad = Mesh::new(
+            vertices,
+            Some(Indices::U16(vec![0, 1, 2, 0, 2, 3])),
+            RenderMode::Triangles,
+        );
+        quad.generate_planar_uvs(Axis::Z);
+        let uvs = quad
+            .vertices
+            .iter()
+            .map(|vertex| vertex.tex_coord.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(uvs, [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+
+        let mut cube = load_cube();
+        cube.meshes[0].generate_box_uvs();
+        for vertex in &cube.meshes[0].vertices {
+            let uv = vertex.tex_coord.unwrap();
+            assert!(uv.iter().all(|value| (0.0..=1.0).contains(value)));
+        }
+    }
+
+    #[test]
+    fn split_for_u16() {
+        // 23334 Triangles without shared Vertices
+        let vertices = (0..70_002)
+            .map(|i| Vertex {
+                position: [i as f32, 0.0, 0.0],
+                ..Default::default()
+            })
+            .collect();
+        let mut mesh = Mesh::new(vertices, None, RenderMode::Triangles);
+        mesh.material_index = Some(1);
+
+        let chunks = mesh.split_for_u16();
+        assert_eq!(chunks.len(), 2);
+        let mut triangles = Vec::new();
+        for chunk in &chunks {
+            assert!(chunk.vertices.len() <= 65_536);
+            assert!(matches!(chunk.indices, Some(Indices::U16(_))));
+            assert_eq!(chunk.material_index, Some(1));
+            triangles.extend(
+                chunk
+                    .triangles()
+                    .map(|triangle| triangle.map(|i| chunk.vertices[i].position)),
+            );
+        }
+        let original = mesh
+            .triangles()
+            .map(|triangle| triangle.map(|i| mesh.vertices[i].position))
+            .collect::<Vec<_>>();
+        assert_eq!(triangles, original);
+    }
+
+    #[test]
+    fn voxel_downsample() {
+        // 10x10 points with a spacing of 0.1, Every 0.5 cell holds 5x5 of them
+        let vertices = (0..100)
+            .map(|i| {
+                let (x, y) = ((i % 10) as f32 * 0.1, (i / 10) as f32 * 0.1);
+                Vertex {
+                    position: [x, y, 0.0],
+                    color: Some([x, y, 0.0, 1.0]),
+                    normal: Some([0.0, 0.0, 2.0]),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let mut mesh = Mesh::new(vertices, None, RenderMode::Points);
+        mesh.voxel_downsample(0.5);
+        assert_eq!(mesh.vertices.len(), 4);
+
+        let first = &mesh.vertices[0];
+        assert!((first.position[0] - 0.2).abs() < 1e-5);
+        assert!((first.position[1] - 0.2).abs() < 1e-5);
+        let color = first.color.unwrap();
+        assert!((color[0] - 0.2).abs() < 1e-5 && (color[3] - 1.0).abs() < 1e-5);
+        assert_eq!(first.normal, Some([0.0, 0.0, 1.0]));
+
+        let mut triangles = load_cube();
+        let count = triangles.meshes[0].vertices.len();
+        triangles.meshes[0].voxel_downsample(0.5);
+        assert_eq!(triangles.meshes[0].vertices.len(), count);
+    }
+
+    #[test]
+    fn estimate_point_normals() {
+        // A slightly irregular grid in a tilted plane with the normal (0, 0.6, 0.8)
+        let vertices = (0..400)
+            .map(|i| {
+                let (u, v) = (
+                    (i % 20) as f32 * 0.1,
+                    (i / 20) as f32 * 0.1 + (i % 3) as f32 * 0.01,
+                );
+                Vertex {
+                    position: [u, 0.8 * v, -0.6 * v],
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let mut mesh = Mesh::new(vertices, None, RenderMode::Points);
+        mesh.estimate_point_normals(8);
+        for vertex in &mesh.vertices {
+            let normal = vertex.normal.unwrap();
+            assert!(normal[0].abs() < 1e-3, "{normal:?}");
+            assert!((normal[1] - 0.6).abs() < 1e-3, "{normal:?}");
+            assert!((normal[2] - 0.8).abs() < 1e-3, "{normal:?}");
+        }
+    }
+
+    #[test]
+    fn kdtree() {
+        let model = load_cube();
+        let mesh = &model.meshes[0];
+        let tree = mesh.build_kdtree();
+        assert_eq!(tree.len(), mesh.vertices.len());
+
+        // The cube corners lie at +-1, The point is closest to the corner (1, 1, 1)
+        let point = [0.9, 1.2, 0.8];
+        let nearest = tree.nearest(point).unwrap();
+        let closest = mesh
+            .vertices
+            .iter()
+            .map(|v| v.position)
+            .min_by(|a, b| distance(*a, point).total_cmp(&distance(*b, point)))
+            .unwrap();
+        assert_eq!(mesh.vertices[nearest].position, closest);
+
+        let k_nearest = tree.k_nearest(point, 3);
+        assert_eq!(k_nearest.len(), 3);
+        assert_eq!(mesh.vertices[k_nearest[0]].position, closest);
+        assert!(
+            distance(mesh.vertices[k_nearest[1]].position, point)
+                <= distance(mesh.vertices[k_nearest[2]].position, point)
+        );
+
+        let within = tree.within_radius(closest, 0.001);
+        assert!(within.iter().all(|&i| mesh.vertices[i].position == closest));
+        assert!(within.contains(&nearest));
+        assert_eq!(tree.within_radius(point, 0.1), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn memory_usage() {
+        let model = load_cube();
+        let mesh = &model.meshes[0];
+        let usage = model.memory_usage();
+        assert_eq!(
+            usage.vertices,
+            mesh.vertices.len() * std::mem::size_of::<Vertex>()
+        );
+        assert_eq!(
+            usage.indices,
+            mesh.indices.as_ref().map_or(0, |i| i.len() * 4)
+        );
+        assert_eq!(
+            usage.total(),
+            usage.vertices + usage.indices + usage.textures
+        );
+    }
+
+    #[test]
+    fn content_hash() {
+        let first = load_cube();
+        let mut second = load_cube();
+        assert_eq!(first.content_hash(), second.content_hash());
+        assert_eq!(
+            first.meshes[0].content_hash(),
+            second.meshes[0].content_hash()
+        );
+
+        // Names are ignored, Geometry is not
+        second.meshes[0].name = Some("Renamed".to_string());
+        assert_eq!(first.content_hash(), second.content_hash());
+        second.meshes[0].vertices[0].position[0] += 1.0;
+        assert_ne!(first.content_hash(), second.content_hash());
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn packed_f16() {
+        use modelz::half::f16;
+
+        let model = load_cube();
+        let mesh = &model.meshes[0];
+        let packed = mesh.to_packed_f16();
+        // Position, Normal and texture coordinate
+        assert_eq!(packed.len(), mesh.vertices.len() * 8);
+        for (values, vertex) in packed.chunks_exact(8).zip(&mesh.vertices) {
+            for (value, expected) in values[..3].iter().zip(vertex.position) {
+                assert!((value.to_f32() - expected).abs() <= expected.abs() / 2048.0);
+            }
+            let normal = vertex.normal.unwrap();
+            let tex_coord = vertex.tex_coord.unwrap();
+            for (value, expected) in values[3..].iter().zip(normal.iter().chain(&tex_coord)) {
+                assert!((value.to_f32() - expected).abs() <= 1.0 / 2048.0);
+            }
+        }
+
+        // Exactly representable values survive, Too large ones become infinite
+        let mesh = Mesh::new(
+            vec![Vertex {
+                position: [0.1, 65504.0, 1e6],
+                color: None,
+                tex_coord: Some([0.5, -2.0]),
+                normal: None,
+                tangent: None,
+            }],
+            None,
+            RenderMode::Points,
+        );
+        let packed = mesh.to_packed_f16();
+        let bits = packed
+            .iter()
+            .map(|value| value.to_bits())
+            .collect::<Vec<_>>();
+        assert_eq!(bits, [0x2e66, 0x7bff, 0x7c00, 0x3800, 0xc000]);
+        assert_eq!(packed[2], f16::INFINITY);
+    }
+
+    #[test]
+    fn adjacency_indices() {
+        let model = load_cube();
+        let mesh = &model.meshes[0];
+        let adjacency = mesh.to_adjacency_indices();
+        assert_eq!(adjacency.len(), mesh.triangle_count() * 6);
+
+        // The cube is closed, So every edge has a real neighbour instead of the duplicated opposite Vertex
+        let position = |i: u32| mesh.vertices[i as usize].position;
+        for triangle in adjacency.chunks(6) {
+            for corner in 0..3 {
+                let opposite = triangle[(corner * 2 + 4) % 6];
+                assert_ne!(position(triangle[corner * 2 + 1]), position(opposite));
+            }
+        }
+
+        // A single Triangle only has boundary edges
+        let mut triangle = Mesh::new(mesh.vertices[..3].to_vec(), None, RenderMode::Triangles);
+        assert_eq!(triangle.to_adjacency_indices(), vec![0, 2, 1, 0, 2, 1]);
+        triangle.mode = RenderMode::Points;
+        assert!(triangle.to_adjacency_indices().is_empty());
+    }
+
+    #[test]
+    fn weld_positions_keep_seams() {
+        let mut model = load_cube();
+        let mesh = &mut model.meshes[0];
+        // A tiny gap on one corner gets closed
+        mesh.vertices[0].position[0] += 1e-6;
+        let triangles = mesh.triangle_count();
+        let groups = mesh.weld_positions_keep_seams(1e-4);
+
+        // Every face keeps its own 4 corners, The normals and UVs differ between faces
+        assert_eq!(mesh.vertices.len(), 24);
+        assert_eq!(mesh.triangle_count(), triangles);
+        assert_eq!(groups.len(), mesh.vertices.len());
+
+        // The 8 cube corners connect the 3 faces meeting at each of them
+        for group in 0..8 {
+            let members = (0..groups.len())
+                .filter(|&i| groups[i] == group)
+                .collect::<Vec<_>>();
+            assert_eq!(members.len(), 3);
+            let position = mesh.vertices[members[0]].position;
+            assert!(members
+                .iter()
+                .all(|&i| mesh.vertices[i].position == position));
+            assert_ne!(
+                mesh.vertices[members[0]].normal,
+                mesh.vertices[members[1]].normal
+            );
+        }
+        assert!(groups.iter().all(|&group| group < 8));
+    }
+
+    #[test]
+    fn drop_attribute() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/face_colors.ply");
+        let mut model = Model3D::load(model_path).expect("Failed to load ply model");
+        let mesh = &mut model.meshes[0];
+        assert!(mesh.available_attributes().contains(&VertexAttr::Color));
+
+        mesh.drop_attribute(VertexAttr::Color);
+        assert!(mesh.vertices.iter().all(|v| v.color.is_none()));
+        assert!(!mesh.available_attributes().contains(&VertexAttr::Color));
+    }
+
+    #[test]
+    fn to_soa() {
+        let model = load_cube();
+        let mesh = &model.meshes[0];
+        let soa = mesh.to_soa();
+        assert_eq!(soa.positions.len(), mesh.vertices.len());
+        let normals = soa.normals.expect("Cube has normals");
+        assert_eq!(normals.len(), mesh.vertices.len());
+        assert_eq!(Some(normals[5]), mesh.vertices[5].normal);
+        assert!(soa.colors.is_none());
+    }
+
+    #[test]
+    fn compute_normals_with_angle() {
+        let model = load_cube();
+        let mut cube = Mesh::new(
+            model.meshes[0]
+                .vertices
+                .iter()
+                .map(|v| Vertex {
+                    position: v.position,
+                    ..Default::default()
+                })
+                .collect(),
+            model.meshes[0].indices.clone(),
+            RenderMode::Triangles,
+        );
+        cube.join_identical_vertices();
+        assert_eq!(cube.vertices.len(), 8);
+
+        // The 90° edges of the cube are sharper than the threshold, So every side gets its own Vertices
+        let mut hard = cube.triangulated();
+        hard.compute_normals_with_angle(30.0);
+        assert_eq!(hard.vertices.len(), 24);
+        assert_eq!(hard.triangle_count(), 12);
+        let normals = hard.face_normals();
+        for ([a, b, c], face) in hard.triangles().zip(normals) {
+            for i in [a, b, c] {
+                let normal = hard.vertices[i].normal.expect("Missing normal");
+                assert!(distance(normal, face) < 1e-6);
+            }
+        }
+
+        cube.compute_normals_with_angle(120.0);
+        assert_eq!(cube.vertices.len(), 8);
+    }
+
+    #[test]
+    fn fill_missing_normals() {
+        let mut smooth = sphere(8, 12);
+        smooth.compute_smooth_normals();
+
+        // Every second Vertex keeps a marker normal, The others lose theirs
+        let mut mesh = sphere(8, 12);
+        for (i, vertex) in mesh.vertices.iter_mut().enumerate() {
+            vertex.normal = (i % 2 == 0).then_some([0.0, 0.0, -1.0]);
+        }
+        mesh.fill_missing_normals();
+        for (i, (vertex, expected)) in mesh.vertices.iter().zip(&smooth.vertices).enumerate() {
+            if i % 2 == 0 {
+                assert_eq!(vertex.normal, Some([0.0, 0.0, -1.0]));
+            } else {
+                assert_eq!(vertex.normal, expected.normal);
+            }
+        }
+    }
+
+    #[test]
+    fn draw_calls() {
+        let model = load_cube();
+        let draws = model.draw_calls();
+        assert_eq!(draws.len(), model.meshes.len());
+        for (draw, mesh) in draws.iter().zip(&model.meshes) {
+            assert_eq!(draw.vertices.len(), mesh.vertices.len());
+            assert_eq!(draw.indices, mesh.indices.as_ref());
+            assert_eq!(draw.mode, mesh.mode);
+            let material = draw.material.expect("Material not resolved");
+            assert_eq!(
+                material.name,
+                model.materials[mesh.material_index.unwrap()].name
+            );
+        }
+    }
+
+    #[test]
+    fn color_u8() {
+        let mut vertex = Vertex {
+            color: Some([1.0, 0.5, 0.0, 1.0]),
+            ..Default::default()
+        };
+        assert_eq!(vertex.color_u8(), Some([255, 128, 0, 255]));
+
+        // Out of range values are clamped
+        vertex.color = Some([2.0, -1.0, 0.25, 1.0]);
+        assert_eq!(vertex.color_u8(), Some([255, 0, 64, 255]));
+
+        vertex.set_color_u8([255, 0, 51, 255]);
+        assert_eq!(vertex.color, Some([1.0, 0.0, 0.2, 1.0]));
+        assert_eq!(Vertex::default().color_u8(), None);
+
+        let model = load_cube();
+        let mut material = model.materials.into_iter().next().unwrap();
+        material.set_base_color_u8([255, 128, 0, 255]);
+        assert_eq!(material.base_color_u8(), Some([255, 128, 0, 255]));
+    }
+}
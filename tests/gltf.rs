@@ -1,6 +1,11 @@
 #[cfg(test)]
 mod gltf {
-    use modelz::Model3D;
+    use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
+
+    use modelz::{
+        ColorSpace, Image, LoadOptions, MagFilter, MinFilter, Model3D, ModelError, PostProcess,
+        RenderMode, Sampler, TextureTimeout,
+    };
 
     #[test]
     fn load_gltf() {
@@ -18,4 +23,914 @@ mod gltf {
             println!("{}", material.name.unwrap_or("Unknown".to_string()))
         }
     }
+
+    #[test]
+    fn load_camera() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/camera.gltf");
+
+        let model = Model3D::from_format(model_path, &modelz::ModelFormat::GLTF)
+            .expect("Failed to load gltf model");
+        assert_eq!(model.cameras.len(), 1);
+        let camera = &model.cameras[0];
+        assert_eq!(camera.name.as_deref(), Some("MainCamera"));
+        match camera.projection {
+            modelz::Projection::Perspective {
+                yfov, aspect_ratio, ..
+            } => {
+                assert_eq!(yfov, 0.8);
+                assert_eq!(aspect_ratio, Some(1.5));
+            }
+            modelz::Projection::Orthographic { .. } => panic!("Expected perspective camera"),
+        }
+        // The parent node translation is applied
+        assert_eq!(camera.position(), [0.0, 2.0, 5.0]);
+    }
+
+    #[test]
+    fn load_point_light() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/light.gltf");
+
+        let model = Model3D::from_format(model_path, &modelz::ModelFormat::GLTF)
+            .expect("Failed to load gltf model");
+        assert_eq!(model.lights.len(), 1);
+        let light = &model.lights[0];
+        assert_eq!(light.kind, modelz::LightKind::Point);
+        assert_eq!(light.color, [1.0, 0.5, 0.25]);
+        assert_eq!(light.intensity, 40.0);
+        assert_eq!(light.range, Some(10.0));
+        assert_eq!(light.position(), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn load_morph_targets() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/morph.gltf");
+
+        let model = Model3D::from_format(model_path, &modelz::ModelFormat::GLTF)
+            .expect("Failed to load gltf model");
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.morph_targets.len(), 2);
+        assert_eq!(mesh.morph_weights, vec![0.5, 0.0]);
+        for target in &mesh.morph_targets {
+            let positions = target.positions.as_ref().expect("Missing position deltas");
+            assert_eq!(positions.len(), mesh.vertices.len());
+        }
+        let normals = mesh.morph_targets[0].normals.as_ref().unwrap();
+        assert_eq!(normals.len(), mesh.vertices.len());
+        assert!(mesh.morph_targets[1].normals.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "extras")]
+    fn load_extras() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/extras.gltf");
+
+        let model = Model3D::from_format(model_path, &modelz::ModelFormat::GLTF)
+            .expect("Failed to load gltf model");
+        let extras = model.meshes[0]
+            .extras
+            .as_ref()
+            .expect("Missing mesh extras");
+        assert_eq!(extras["collision"], true);
+        assert_eq!(extras["layer"], 3);
+        let extras = model.materials[0].extras.as_ref().unwrap();
+        assert_eq!(extras["surface"], "metal");
+    }
+
+    #[test]
+    #[cfg(feature = "extras")]
+    fn hidden_nodes() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/hidden.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.meshes.len(), 4);
+
+        let options = LoadOptions {
+            respect_hidden_extras: true,
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        // The Wheel is only referenced by a child of the hidden node
+        assert_eq!(model.meshes.len(), 2);
+        assert!(model
+            .meshes
+            .iter()
+            .all(|mesh| mesh.name.as_deref() == Some("Body")));
+        assert_eq!(model.mesh_instances.len(), 2);
+    }
+
+    #[test]
+    fn retain_source() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/multi_material.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert!(model.gltf_document().is_none());
+
+        let options = LoadOptions {
+            retain_source: true,
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        let document = model.gltf_document().expect("Document was not retained");
+        assert_eq!(document.meshes().len(), 2);
+        // Every primitive became one Mesh
+        let primitives: usize = document.meshes().map(|mesh| mesh.primitives().len()).sum();
+        assert_eq!(primitives, model.meshes.len());
+    }
+
+    #[test]
+    fn passthrough_source_bytes() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/multi_material.gltf");
+        let copy_path = std::env::temp_dir().join("modelz_passthrough.gltf");
+
+        let options = LoadOptions {
+            retain_source_bytes: true,
+            ..Default::default()
+        };
+        let mut model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        let bytes = model.unmodified_source().expect("Source was not retained");
+        std::fs::write(&copy_path, bytes).expect("Failed to write copy");
+        let original = std::fs::read(model_path).unwrap();
+        assert_eq!(std::fs::read(&copy_path).unwrap(), original);
+        let _ = std::fs::remove_file(&copy_path);
+
+        // Modified geometry or Materials can not be passed through anymore
+        model.meshes[0].vertices[0].position[0] += 1.0;
+        assert!(model.unmodified_source().is_none());
+        model.meshes[0].vertices[0].position[0] -= 1.0;
+        assert!(model.unmodified_source().is_some());
+        model.materials[0].name = Some("Renamed".to_string());
+        assert!(model.unmodified_source().is_none());
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert!(model.unmodified_source().is_none());
+    }
+
+    #[test]
+    fn authored_tangents() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/tangents.gltf");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let expected = [
+            [1.0, 0.0, 0.0, -1.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [0.6, 0.8, 0.0, -1.0],
+        ];
+        let tangents = |model: &Model3D| {
+            model.meshes[0]
+                .vertices
+                .iter()
+                .map(|vertex| vertex.tangent.unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tangents(&model), expected);
+        // Authored tangents are not regenerated
+        model.post_process(PostProcess::CALC_TANGENTS);
+        assert_eq!(tangents(&model), expected);
+    }
+
+    #[test]
+    fn material_variants() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/variants.gltf");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.variants, ["Midnight", "Beach"]);
+        let materials = |model: &Model3D| {
+            model
+                .meshes
+                .iter()
+                .map(|mesh| mesh.material_index)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(materials(&model), [Some(0), Some(1), Some(0), None]);
+
+        model.apply_variant("Midnight");
+        assert_eq!(materials(&model), [Some(1), Some(1), Some(0), None]);
+        model.apply_variant("Beach");
+        assert_eq!(materials(&model), [Some(2), Some(1), Some(0), None]);
+        // Unknown variants select the base Materials
+        model.apply_variant("Unknown");
+        assert_eq!(materials(&model), [Some(0), Some(1), Some(0), None]);
+    }
+
+    #[test]
+    fn triangulate_strips() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/strip.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.meshes[0].mode, RenderMode::TriangleStrip);
+
+        let options = LoadOptions {
+            triangulate: true,
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.mode, RenderMode::Triangles);
+        // 5 strip Vertices make 3 Triangles
+        assert_eq!(mesh.triangle_count(), 3);
+        assert_eq!(
+            mesh.indices.as_ref().unwrap().iter().collect::<Vec<_>>(),
+            [0, 1, 2, 2, 1, 3, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn meshes_by_material() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/multi_material.gltf");
+
+        let model = Model3D::from_format(model_path, &modelz::ModelFormat::GLTF)
+            .expect("Failed to load gltf model");
+        let groups = model.meshes_by_material();
+        assert_eq!(groups.len(), 3);
+
+        let red = model.material_by_name("Red").unwrap();
+        assert_eq!(red.base_color, Some([1.0, 0.0, 0.0, 1.0]));
+        let names = |index| {
+            groups[&index]
+                .iter()
+                .map(|mesh| mesh.name.as_deref().unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(Some(0)), vec!["Body", "Wheel"]);
+        assert_eq!(names(Some(1)), vec!["Body"]);
+        assert_eq!(names(None), vec!["Wheel"]);
+    }
+
+    #[test]
+    fn from_bytes() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.glb");
+        let data = std::fs::read(model_path).unwrap();
+
+        let options = LoadOptions {
+            retain_source_bytes: true,
+            ..Default::default()
+        };
+        let model = Model3D::from_bytes_with_options(&data, &modelz::ModelFormat::GLTF, &options)
+            .expect("Failed to load gltf model");
+        let file = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.content_hash(), file.content_hash());
+        assert_eq!(model.unmodified_source(), Some(&data[..]));
+
+        // External Buffers are never read
+        let data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/encoded_uri.gltf"
+        ))
+        .unwrap();
+        let result = Model3D::from_bytes(&data, &modelz::ModelFormat::GLTF);
+        assert!(matches!(result, Err(ModelError::ModelParsing(_))));
+    }
+
+    #[test]
+    fn missing_position_accessor() {
+        // The POSITION accessor points past the accessors, Which the glTF validation itself does not check
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/missing_accessor.gltf");
+
+        let result = Model3D::load(model_path);
+        assert!(matches!(result, Err(ModelError::ModelParsing(_))));
+    }
+
+    #[test]
+    fn unreadable_position_accessor() {
+        // The POSITION accessor declares 3 Vertices, But runs past the end of its bufferView
+        let model_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/unreadable_accessor.gltf"
+        );
+
+        let result = Model3D::load(model_path);
+        assert!(matches!(result, Err(ModelError::ModelParsing(_))));
+    }
+
+    #[test]
+    fn vertex_limit() {
+        // Declares a billion Vertices backed by a 36 byte buffer
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/bomb.gltf");
+
+        let options = LoadOptions {
+            max_vertices: Some(1_000_000),
+            ..Default::default()
+        };
+        let result = Model3D::load_with_options(model_path, &options);
+        assert!(matches!(result, Err(ModelError::ModelParsing(_))));
+
+        let model = Model3D::load_with_options(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.glb"),
+            &options,
+        );
+        assert!(model.is_ok());
+    }
+
+    #[test]
+    fn normalized_vertex_colors() {
+        // First primitive uses VEC4 unsigned byte colors, The second VEC3 unsigned short colors
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vertex_colors.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let colors = |mesh: &modelz::Mesh| {
+            mesh.vertices
+                .iter()
+                .map(|v| v.color.expect("Vertex has no color"))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            colors(&model.meshes[0]),
+            [
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 128.0 / 255.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0, 0.0]
+            ]
+        );
+        assert_eq!(
+            colors(&model.meshes[1]),
+            [
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 32768.0 / 65535.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0, 1.0]
+            ]
+        );
+
+        // OBJ colors have no alpha either, So they are opaque
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/color.obj");
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert_eq!(
+            colors(&model.meshes[0]),
+            [
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0, 1.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn texture_color_space() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/textured.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let material = model.material_by_name("Brick").expect("Material not found");
+        let diffuse = material.diffuse_texture.as_ref().unwrap();
+        assert_eq!(diffuse.name.as_deref(), Some("Albedo"));
+        assert_eq!(diffuse.color_space, ColorSpace::Srgb);
+        let normal = material.normal_texture.as_ref().unwrap();
+        assert_eq!(normal.name.as_deref(), Some("Normal"));
+        assert_eq!(normal.color_space, ColorSpace::Linear);
+    }
+
+    #[test]
+    fn transmission_and_volume() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/transmission.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let material = model.material_by_name("Glass").expect("Material not found");
+        assert_eq!(material.transmission_factor, Some(0.8));
+        let texture = material.transmission_texture.as_ref().unwrap();
+        assert_eq!(texture.color_space, ColorSpace::Linear);
+        assert_eq!(material.thickness_factor, Some(0.5));
+        assert_eq!(material.attenuation_color, Some([0.9, 0.5, 0.25]));
+        assert_eq!(material.attenuation_distance, Some(2.0));
+
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/textured.gltf");
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let material = model.material_by_name("Brick").expect("Material not found");
+        assert!(material.transmission_factor.is_none());
+        assert!(material.thickness_factor.is_none());
+    }
+
+    #[test]
+    fn clearcoat() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/clearcoat.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let material = model
+            .material_by_name("CarPaint")
+            .expect("Material not found");
+        assert_eq!(material.clearcoat_factor, Some(1.0));
+        assert_eq!(material.clearcoat_roughness_factor, Some(0.25));
+        let texture = material.clearcoat_texture.as_ref().unwrap();
+        assert_eq!(texture.name.as_deref(), Some("Clearcoat"));
+        let normal = material.clearcoat_normal_texture.as_ref().unwrap();
+        assert_eq!(normal.name.as_deref(), Some("ClearcoatNormal"));
+
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert!(model.materials[0].clearcoat_factor.is_none());
+        assert!(model.materials[0].clearcoat_texture.is_none());
+    }
+
+    #[test]
+    fn ior_and_sheen() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/sheen.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let velvet = model
+            .material_by_name("Velvet")
+            .expect("Material not found");
+        assert_eq!(velvet.ior, Some(1.33));
+        assert_eq!(velvet.sheen_color_factor, Some([0.9, 0.5, 1.0]));
+        assert_eq!(velvet.sheen_roughness_factor, Some(0.75));
+
+        // Extension without properties uses the defaults
+        let glass = model.material_by_name("Glass").expect("Material not found");
+        assert_eq!(glass.ior, Some(1.5));
+        assert!(glass.sheen_color_factor.is_none());
+
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert!(model.materials[0].ior.is_none());
+        assert!(model.materials[0].sheen_roughness_factor.is_none());
+    }
+
+    #[test]
+    fn instances() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/instanced.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.meshes.len(), 1);
+        let instances = model.instances().collect::<Vec<_>>();
+        assert_eq!(instances.len(), 2);
+        assert!(instances
+            .iter()
+            .all(|(mesh, _)| mesh.name.as_deref() == Some("Tree")));
+        // The second node inherits the translation of its parent
+        assert_eq!(instances[0].1[12..15], [2.0, 0.0, 0.0]);
+        assert_eq!(instances[1].1[12..15], [0.0, 3.0, 1.0]);
+
+        // Formats without a scene graph place every Mesh once at the origin
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert_eq!(model.instances().count(), model.meshes.len());
+        assert!(model
+            .instances()
+            .all(|(_, transform)| transform[12..15] == [0.0; 3]));
+    }
+
+    #[test]
+    fn deterministic_order() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/multi_material.gltf");
+
+        // Bit patterns, So even NaNs have to match
+        let bits = |model: &Model3D| {
+            model
+                .meshes
+                .iter()
+                .map(|mesh| {
+                    let vertices = mesh
+                        .vertices
+                        .iter()
+                        .flat_map(|v| {
+                            v.position
+                                .into_iter()
+                                .chain(v.normal.into_iter().flatten())
+                                .chain(v.tex_coord.into_iter().flatten())
+                                .chain(v.color.into_iter().flatten())
+                                .chain(v.tangent.into_iter().flatten())
+                        })
+                        .map(f32::to_bits)
+                        .collect::<Vec<_>>();
+                    (mesh.material_index, vertices, mesh.indices.clone())
+                })
+                .collect::<Vec<_>>()
+        };
+        let first = Model3D::load(model_path).expect("Failed to load gltf model");
+        for _ in 0..4 {
+            let again = Model3D::load(model_path).expect("Failed to load gltf model");
+            assert_eq!(bits(&first), bits(&again));
+            let names = |model: &Model3D| {
+                model
+                    .materials
+                    .iter()
+                    .map(|m| m.name.clone())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(names(&first), names(&again));
+        }
+        // Primitives of the first Mesh come first
+        assert_eq!(first.meshes.len(), 4);
+        assert_eq!(first.meshes[0].name, first.meshes[1].name);
+    }
+
+    #[test]
+    fn no_default_scene() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/scenes.gltf");
+
+        // Both scenes reference the Tree, It is still loaded once
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let names = model
+            .meshes
+            .iter()
+            .map(|mesh| mesh.name.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Tree", "Lamp"]);
+
+        // Only the first scene is placed, The Lamp only exists in the second one
+        let instances = model
+            .instances()
+            .map(|(mesh, _)| mesh.name.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(instances, ["Tree"]);
+    }
+
+    #[test]
+    fn empty_primitives() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/empty_primitive.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.meshes.len(), 1);
+        assert_eq!(model.meshes[0].vertices.len(), 3);
+        // The node of the empty Mesh places nothing
+        assert_eq!(model.instances().count(), 1);
+
+        let options = LoadOptions {
+            keep_empty_primitives: true,
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        let counts = model
+            .meshes
+            .iter()
+            .map(|mesh| mesh.vertices.len())
+            .collect::<Vec<_>>();
+        assert_eq!(counts, [0, 3, 0]);
+        assert_eq!(model.instances().count(), 3);
+    }
+
+    #[test]
+    fn basisu_textures() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/basisu.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        // Without a fallback the KTX2 Image is used and flagged
+        let compressed = model
+            .material_by_name("Compressed")
+            .and_then(|material| material.diffuse_texture.as_ref())
+            .expect("Texture not found");
+        assert!(compressed.image.is_gpu_compressed());
+        #[cfg(feature = "image")]
+        assert!(matches!(
+            compressed.to_rgba8(),
+            Err(ModelError::MaterialLoad(_))
+        ));
+
+        // The fallback can be decoded, So it is preferred
+        let fallback = model
+            .material_by_name("Fallback")
+            .and_then(|material| material.diffuse_texture.as_ref())
+            .expect("Texture not found");
+        assert!(!fallback.image.is_gpu_compressed());
+        assert!(
+            matches!(&fallback.image, Image::Path { path, .. } if path.ends_with("checker_256x128.png"))
+        );
+    }
+
+    #[test]
+    fn texture_resolver() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resolver.gltf");
+        let png = include_bytes!("checker_256x128.png").to_vec();
+
+        let options = LoadOptions {
+            texture_resolver: Some(Box::new(move |uri| {
+                (uri == "virtual/albedo.png").then(|| png.clone())
+            })),
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        let material = model.material_by_name("Brick").expect("Material not found");
+        let diffuse = material.diffuse_texture.as_ref().unwrap();
+        let Image::Memory { data, mime_type } = &diffuse.image else {
+            panic!("Resolved texture is not in memory");
+        };
+        assert!(data.starts_with(b"\x89PNG"));
+        assert_eq!(mime_type.as_deref(), Some("image/png"));
+        // Unknown URIs fall back to the File path
+        let normal = material.normal_texture.as_ref().unwrap();
+        assert!(matches!(normal.image, Image::Path { .. }));
+    }
+
+    #[test]
+    fn nested_load_in_resolver() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resolver.gltf");
+        let stl_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/mixed_winding.stl");
+
+        // The resolver does not have to be Send, And its own loads keep their warnings
+        let nested_warnings = Rc::new(RefCell::new(Vec::new()));
+        let collected = Rc::clone(&nested_warnings);
+        let options = LoadOptions {
+            texture_resolver: Some(Box::new(move |_| {
+                let model = Model3D::load(stl_path).expect("Failed to load stl model");
+                collected.borrow_mut().push(model.warnings.len());
+                None
+            })),
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        assert!(model.warnings.is_empty());
+        assert_eq!(*nested_warnings.borrow(), [1, 1]);
+    }
+
+    #[test]
+    fn texture_timeout() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/resolver.gltf");
+
+        let options = LoadOptions {
+            texture_timeout: Some(TextureTimeout {
+                duration: Duration::from_millis(50),
+                resolver: Arc::new(|uri| {
+                    if uri == "virtual/albedo.png" {
+                        std::thread::sleep(Duration::from_secs(2));
+                    }
+                    None
+                }),
+            }),
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        assert_eq!(model.warnings.len(), 1);
+        assert!(matches!(
+            &model.warnings[0],
+            ModelError::MaterialLoad(message) if message.contains("timed out")
+        ));
+        // The slow texture falls back to its File path
+        let material = model.material_by_name("Brick").expect("Material not found");
+        let diffuse = material.diffuse_texture.as_ref().unwrap();
+        assert!(matches!(diffuse.image, Image::Path { .. }));
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert!(model.warnings.is_empty());
+    }
+
+    #[test]
+    fn extract_textures_to_dir() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/embedded_jpeg.gltf");
+        let dir = std::env::temp_dir().join("modelz_extract_textures");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let Some(Image::Memory { data, .. }) = model.materials[0]
+            .diffuse_texture
+            .as_ref()
+            .map(|texture| &texture.image)
+        else {
+            panic!("Texture is not embedded");
+        };
+        let data = data.clone();
+        model.extract_textures_to_dir(&dir).unwrap();
+
+        let texture = model.materials[0].diffuse_texture.as_ref().unwrap();
+        let Image::Path { path, mime_type } = &texture.image else {
+            panic!("Texture was not extracted");
+        };
+        assert!(path.starts_with(&dir));
+        assert_eq!(path.extension().unwrap(), "jpg");
+        assert_eq!(mime_type.as_deref(), Some("image/jpeg"));
+        assert_eq!(std::fs::read(path).unwrap(), data);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn decode_embedded_jpeg() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/embedded_jpeg.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let texture = model.materials[0].diffuse_texture.as_ref().unwrap();
+        let (pixels, width, height) = texture.to_rgba8().expect("Failed to decode texture");
+        assert_eq!((width, height), (8, 4));
+        assert_eq!(pixels.len(), width as usize * height as usize * 4);
+
+        // Without a MIME type the embedded Image is sniffed
+        let copy_path = std::env::temp_dir().join("modelz_embedded_no_mime.gltf");
+        let text = std::fs::read_to_string(model_path).unwrap();
+        std::fs::write(
+            &copy_path,
+            text.replace(r#""mimeType": "image/jpeg""#, r#""name": "Checker""#),
+        )
+        .unwrap();
+        let model = Model3D::load(&copy_path).expect("Failed to load gltf model");
+        let _ = std::fs::remove_file(&copy_path);
+        let texture = model.materials[0].diffuse_texture.as_ref().unwrap();
+        assert!(
+            matches!(&texture.image, Image::Memory { mime_type: Some(mime_type), .. } if mime_type == "image/jpeg")
+        );
+    }
+
+    #[test]
+    fn mesh_filter() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/multi_material.gltf");
+
+        let options = LoadOptions {
+            mesh_filter: Some(vec!["Wheel".to_string()]),
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load gltf model");
+        assert!(!model.meshes.is_empty());
+        assert!(model
+            .meshes
+            .iter()
+            .all(|mesh| mesh.name.as_deref() == Some("Wheel")));
+        // Materials are still loaded so Material indices stay valid
+        assert_eq!(model.materials.len(), 2);
+    }
+
+    #[test]
+    fn inferred_mime_type() {
+        // The images only have an URI, No mimeType
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/textured.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let texture = model.materials[0].diffuse_texture.as_ref().unwrap();
+        let modelz::Image::Path { mime_type, .. } = &texture.image else {
+            panic!("Expected an Image path");
+        };
+        assert_eq!(mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn interleaved_attributes() {
+        // Position, normal and texture coordinate share one buffer view with a 32 byte stride
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/interleaved.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let vertices = &model.meshes[0].vertices;
+        assert_eq!(vertices.len(), 3);
+        let expected = [
+            ([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0]),
+            ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0]),
+            ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0]),
+        ];
+        for (vertex, (position, normal, tex_coord)) in vertices.iter().zip(expected) {
+            assert_eq!(vertex.position, position);
+            assert_eq!(vertex.normal, Some(normal));
+            assert_eq!(vertex.tex_coord, Some(tex_coord));
+        }
+    }
+
+    #[test]
+    fn merge_primitives_by_material() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/split_primitives.gltf");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.meshes.len(), 4);
+        let triangles = |model: &Model3D| {
+            model
+                .meshes
+                .iter()
+                .map(|m| m.triangle_count())
+                .sum::<usize>()
+        };
+        let before = triangles(&model);
+        let instances = model.instances().count();
+        model.merge_primitives_by_material();
+
+        // The two red Body primitives become one, The red Wheel stays its own Mesh
+        assert_eq!(model.meshes.len(), 3);
+        assert_eq!(triangles(&model), before);
+        let body = &model.meshes[0];
+        assert_eq!(body.name.as_deref(), Some("Body"));
+        assert_eq!(body.material_index, Some(0));
+        assert_eq!(body.vertices.len(), 6);
+        assert!(body.indices.is_some());
+        assert_eq!(model.meshes[2].name.as_deref(), Some("Wheel"));
+
+        // Both Wheel nodes still place the Wheel, The merged Body is placed once
+        assert_eq!(model.instances().count(), instances - 1);
+        let wheels = model
+            .instances()
+            .filter(|(mesh, _)| mesh.name.as_deref() == Some("Wheel"))
+            .map(|(_, transform)| transform[12])
+            .collect::<Vec<_>>();
+        assert_eq!(wheels, vec![5.0, -5.0]);
+        assert_eq!(body.source_mesh, Some(0));
+        assert_eq!(model.meshes[2].source_mesh, Some(1));
+    }
+
+    #[test]
+    fn merge_primitives_of_unnamed_meshes() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/unnamed_meshes.gltf");
+
+        // Both Meshes have no name, The same Material and are placed by identity nodes, But are different source Meshes
+        let mut model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.meshes.len(), 2);
+        model.merge_primitives_by_material();
+        assert_eq!(model.meshes.len(), 2);
+        assert_eq!(model.instances().count(), 2);
+        let sources = model
+            .meshes
+            .iter()
+            .map(|mesh| mesh.source_mesh)
+            .collect::<Vec<_>>();
+        assert_eq!(sources, vec![Some(0), Some(1)]);
+
+        // Appended Meshes never share a source Mesh with the existing ones
+        model.append(Model3D::load(model_path).expect("Failed to load gltf model"));
+        model.merge_primitives_by_material();
+        assert_eq!(model.meshes.len(), 4);
+        assert_eq!(model.meshes[3].source_mesh, Some(3));
+    }
+
+    #[test]
+    fn declared_bounds() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.glb");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let mesh = &mut model.meshes[0];
+        let declared = mesh.bounds.expect("POSITION accessor has no min/max");
+        assert_eq!(mesh.aabb(), Some(declared));
+
+        // Without the declared bounds the Vertices are scanned
+        mesh.bounds = None;
+        assert_eq!(mesh.aabb(), Some(declared));
+
+        // Formats without declared bounds always scan
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert!(model.meshes.iter().all(|mesh| mesh.bounds.is_none()));
+    }
+
+    #[test]
+    fn tex_coord_sets() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/uv_sets.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let material = &model.materials[0];
+        assert_eq!(material.diffuse_uv_set(), Some(1));
+        assert_eq!(material.normal_texture.as_ref().unwrap().tex_coord_set, 0);
+
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert!(model
+            .materials
+            .iter()
+            .all(|material| material.diffuse_uv_set().unwrap_or(0) == 0));
+    }
+
+    #[test]
+    fn encoded_uris() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/encoded_uri.gltf");
+
+        // The buffer lives at `../tests/spaced%20buffer.bin`
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert_eq!(model.meshes[0].vertices.len(), 3);
+        let texture = model.materials[0].diffuse_texture.as_ref().unwrap();
+        let Image::Path { path, mime_type } = &texture.image else {
+            panic!("Texture is not a path");
+        };
+        assert!(path.ends_with("brick albedo.png"));
+        assert_eq!(mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn sampler_defaults() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/textured.gltf");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let texture = model.materials[0].diffuse_texture.as_mut().unwrap();
+        // The Texture has no sampler, So the filters stay unspecified
+        assert_eq!(texture.sampler.mag_filter, None);
+        assert_eq!(texture.sampler.min_filter, None);
+
+        let sampler = std::mem::take(&mut texture.sampler).with_gltf_defaults();
+        assert_eq!(sampler.mag_filter, Some(MagFilter::Linear));
+        assert_eq!(sampler.min_filter, Some(MinFilter::LinearMipmapLinear));
+
+        // Filters the File specifies are kept
+        let sampler = Sampler {
+            mag_filter: Some(MagFilter::Nearest),
+            ..Default::default()
+        }
+        .with_gltf_defaults();
+        assert_eq!(sampler.mag_filter, Some(MagFilter::Nearest));
+        assert_eq!(sampler.min_filter, Some(MinFilter::LinearMipmapLinear));
+    }
+
+    #[test]
+    fn specular_glossiness() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/spec_gloss.gltf");
+
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        let material = model
+            .material_by_name("Leather")
+            .expect("Material not found");
+        // The diffuse color replaces the default white base color
+        assert_eq!(material.base_color, Some([0.5, 0.25, 0.1, 1.0]));
+        let diffuse = material.diffuse_texture.as_ref().unwrap();
+        assert_eq!(diffuse.name.as_deref(), Some("Diffuse"));
+        assert_eq!(material.specular_factor, Some([0.2, 0.2, 0.2]));
+        assert_eq!(material.glossiness_factor, Some(0.4));
+        let texture = material.specular_glossiness_texture.as_ref().unwrap();
+        assert_eq!(texture.name.as_deref(), Some("SpecularGlossiness"));
+
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/clearcoat.gltf");
+        let model = Model3D::load(model_path).expect("Failed to load gltf model");
+        assert!(model.materials[0].specular_factor.is_none());
+        assert!(model.materials[0].glossiness_factor.is_none());
+    }
 }
@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod malformed {
+    use std::path::PathBuf;
+
+    use modelz::{LoadOptions, Model3D, ModelError};
+
+    /// Deterministic xorshift, So failures can be reproduced
+    fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state.to_le_bytes()[0]
+            })
+            .collect()
+    }
+
+    fn write(name: &str, data: &[u8]) -> PathBuf {
+        let dir = std::env::temp_dir().join("modelz_malformed");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn random_bytes_are_errors() {
+        let extensions = [
+            "gltf", "glb", "obj", "stl", "ply", "dxf", "wrl", "x3d", "usda", "usdz",
+        ];
+        for extension in extensions {
+            for seed in 0..16 {
+                // Starts with a byte which is not valid UTF-8 or any magic word
+                let mut data = random_bytes(seed, 512);
+                data[0] = 0xff;
+                let path = write(&format!("random_{seed}.{extension}"), &data);
+                let result = Model3D::load(&path);
+                // OBJ skips unknown statements, So random bytes are just a Model without Vertices
+                if extension == "obj" {
+                    assert!(result.map_or(true, |model| model
+                        .meshes
+                        .iter()
+                        .all(|mesh| mesh.vertices.is_empty())));
+                } else {
+                    assert!(
+                        result.is_err(),
+                        "Random bytes loaded as {extension} (seed {seed})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn corrupted_files_never_panic() {
+        let fixtures = [
+            "cube.glb",
+            "multi_material.gltf",
+            "cube.obj",
+            "lines.obj",
+            "cube.stl",
+            "cube.ply",
+            "face_colors.ply",
+            "double_be.ply",
+            "faces.dxf",
+            "quad.wrl",
+            "quad.x3d",
+            "cube.usda",
+            "cube.usdz",
+        ];
+        for fixture in fixtures {
+            let original = std::fs::read(
+                PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                    .join("tests")
+                    .join(fixture),
+            )
+            .unwrap();
+            let mut cases = Vec::new();
+            // Truncated at various points
+            for cut in [1, 4, 12, 20, 40, 84, 100, 200, 400] {
+                cases.push(original[..original.len().saturating_sub(cut)].to_vec());
+                cases.push(original[..cut.min(original.len())].to_vec());
+            }
+            // Random bytes overwritten
+            for seed in 0..64 {
+                let mut data = original.clone();
+                let noise = random_bytes(seed, 8);
+                for pair in noise.chunks(2) {
+                    let position = usize::from(pair[0]) * data.len() / 256;
+                    data[position] = pair[1];
+                }
+                cases.push(data);
+            }
+            let extension = fixture.rsplit('.').next().unwrap();
+            if extension == "ply" {
+                // Counts and versions beyond u64
+                let text = String::from_utf8_lossy(&original);
+                cases.push(
+                    text.replace("element vertex", "element vertex 99999999999999999999")
+                        .into_bytes(),
+                );
+                cases.push(text.replace("1.0", "99999999999999999999.0").into_bytes());
+            }
+            for (i, data) in cases.iter().enumerate() {
+                let path = write(&format!("corrupted_{i}.{extension}"), data);
+                // Only panics matter, Some corruptions still form valid Files
+                let _ = Model3D::load(&path);
+            }
+        }
+    }
+
+    #[test]
+    fn broken_references_are_errors() {
+        // Fixture, Text to replace and its replacement
+        let cases = [
+            (
+                "embedded_jpeg.gltf",
+                r#""byteLength": 647"#,
+                r#""byteLength": 4096"#,
+            ),
+            // An Image without data
+            (
+                "resolver.gltf",
+                r#""uri": "brick_normal.png""#,
+                r#""name": "brick_normal.png""#,
+            ),
+        ];
+        for (i, (fixture, from, to)) in cases.into_iter().enumerate() {
+            let original = std::fs::read_to_string(
+                PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                    .join("tests")
+                    .join(fixture),
+            )
+            .unwrap();
+            assert!(original.contains(from), "{fixture} does not contain {from}");
+            let extension = fixture.rsplit('.').next().unwrap();
+            let path = write(
+                &format!("broken_{i}.{extension}"),
+                original.replace(from, to).as_bytes(),
+            );
+            let result = Model3D::load(&path);
+            assert!(
+                matches!(result, Err(ModelError::ModelParsing(_))),
+                "{fixture} with {to} did not fail to parse"
+            );
+        }
+    }
+
+    #[test]
+    fn mixed_obj_corners() {
+        // Faces mixing `v//vn`, `v/vt` and `v/vt/vn` corners give fewer texture coordinates or normals than positions
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/mixed_corners.obj");
+
+        for obj_single_index in [true, false] {
+            let options = LoadOptions {
+                obj_single_index,
+                ..Default::default()
+            };
+            let model =
+                Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+            assert_eq!(model.meshes.len(), 2);
+            assert!(model.meshes.iter().all(|mesh| mesh.vertices.len() == 3));
+        }
+    }
+
+    #[test]
+    fn ply_stream_header() {
+        let header = b"ply\nformat ascii 1.0\nelement vertex 99999999999999999999999\nproperty float x\nend_header\n";
+        let result = modelz::ply::stream(&header[..], |_| {});
+        assert!(matches!(result, Err(ModelError::ModelParsing(_))));
+
+        // The checked header is still parsed
+        let cube =
+            std::fs::File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.ply")).unwrap();
+        let mut count = 0;
+        modelz::ply::stream(cube, |_| count += 1).expect("Failed to stream ply model");
+        assert_eq!(count, 14);
+    }
+
+    #[cfg(feature = "fuzzing")]
+    #[test]
+    fn fuzz_load() {
+        // 10 Formats, Tags past them wrap around
+        for tag in 0..20 {
+            for data in [&[][..], &random_bytes(u64::from(tag), 256)] {
+                let _ = modelz::fuzz_load(tag, data);
+            }
+        }
+        // Small inputs which get past the header of each Format, Every prefix of them is loaded too
+        let glb = [
+            &b"glTF\x02\0\0\0\x20\0\0\0\xff\xff\0\0JSON"[..],
+            &[b' '; 12],
+        ]
+        .concat();
+        let stl = [&[0; 80][..], &u32::MAX.to_le_bytes(), &[0; 12]].concat();
+        let seeds: [(u8, &[u8]); 12] = [
+            (0, b"v 1 2 3\nvt 0 0\nf 1/1 2/9 -7\nl 1 5\np 0\n"),
+            (
+                1,
+                br#"{"asset":{"version":"2.0"},"meshes":[{"primitives":[{"attributes":{"POSITION":7},"indices":3}]}]}"#,
+            ),
+            (2, &glb),
+            (3, b"solid s\nfacet normal 0 0 1\nouter loop\nvertex 0 0\n"),
+            (3, &stl),
+            (
+                4,
+                b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n1\n2\n4 0 1 7 9\n",
+            ),
+            (5, b"0\nSECTION\n2\nENTITIES\n0\n3DFACE\n10\n1\n20\n"),
+            (
+                6,
+                b"#VRML V2.0 utf8\nShape { geometry IndexedFaceSet { coordIndex [0 1 5 -1] coord Coordinate { point [0 0 0, 1 0 0] } } }",
+            ),
+            (
+                7,
+                br#"<X3D><Scene><Shape><IndexedFaceSet coordIndex="0 1 5 -1"><Coordinate point="0 0 0 1"/></IndexedFaceSet></Shape></Scene></X3D>"#,
+            ),
+            (7, br#"<X3D a=""#),
+            (
+                8,
+                br#"#usda 1.0
+def Mesh "m" { int[] faceVertexCounts = [4] int[] faceVertexIndices = [0, 1, 9] point3f[] points = [(0, 0, 0)] }"#,
+            ),
+            (9, b"PK\x03\x04\x14\0\0\0\0\0"),
+        ];
+        for (tag, seed) in seeds {
+            for end in 0..=seed.len() {
+                let _ = modelz::fuzz_load(tag, &seed[..end]);
+            }
+        }
+
+        let cube = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.stl")).unwrap();
+        assert!(modelz::fuzz_load(3, &cube).is_ok());
+        // Inputs are loaded from memory, So they can not make the loader read other Files
+        let mtl = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.mtl");
+        let result = modelz::fuzz_load(0, format!("mtllib {mtl}\nv 0 0 0\n").as_bytes());
+        assert!(matches!(result, Err(ModelError::MaterialLoad(_))));
+    }
+}
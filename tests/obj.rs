@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod obj {
-    use modelz::Model3D;
+    use modelz::{Image, LoadOptions, Model3D, PolygonMesh, RawObjMesh, RenderMode};
 
     #[test]
     fn load_obj() {
@@ -21,4 +21,328 @@ mod obj {
             println!("{}", material.name.unwrap_or("Unknown".to_string()))
         }
     }
+
+    #[test]
+    fn mesh_by_name() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+
+        let mut model = Model3D::from_format(model_path, &modelz::ModelFormat::OBJ)
+            .expect("Failed to load obj model");
+        let cube = model.mesh_by_name("Cube").expect("Cube mesh not found");
+        assert!(!cube.vertices.is_empty());
+        assert!(model.mesh_by_name("Missing").is_none());
+
+        assert!(model.material_by_name("Material").is_some());
+        let material = model
+            .material_by_name_mut("Material")
+            .expect("Material not found");
+        material.double_sided = true;
+        assert!(model.material_by_name("Material").unwrap().double_sided);
+        assert!(model.mesh_by_name_mut("Cube").is_some());
+    }
+
+    #[test]
+    fn single_index() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        let cube = model.mesh_by_name("Cube").expect("Cube mesh not found");
+        assert_eq!(cube.vertices.len(), 24);
+        assert_eq!(cube.indices.as_ref().map(|i| i.len()), Some(36));
+
+        let options = LoadOptions {
+            obj_single_index: false,
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+        let cube = model.mesh_by_name("Cube").expect("Cube mesh not found");
+        assert_eq!(cube.vertices.len(), 36);
+        assert!(cube.indices.is_none());
+    }
+
+    #[test]
+    fn lines_and_points() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/lines.obj");
+
+        // Lines and points are skipped by default
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert_eq!(model.meshes.len(), 1);
+        assert_eq!(model.meshes[0].mode, RenderMode::Triangles);
+
+        let options = LoadOptions {
+            obj_triangulate: false,
+            obj_ignore_points: false,
+            obj_ignore_lines: false,
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+        let modes = model
+            .meshes
+            .iter()
+            .map(|mesh| mesh.mode)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modes,
+            [RenderMode::Points, RenderMode::Lines, RenderMode::Triangles]
+        );
+        let lines = &model.meshes[1];
+        assert_eq!(lines.indices.as_ref().map(|i| i.len()), Some(10));
+        assert_eq!(lines.vertices.len(), 4);
+        assert_eq!(model.meshes[0].vertices[0].position, [2.0, 0.0, 0.0]);
+        assert_eq!(model.meshes[2].triangle_count(), 1);
+        assert!(model
+            .meshes
+            .iter()
+            .all(|mesh| mesh.name.as_deref() == Some("Wire")));
+
+        // Every corner has its own Vertex without a single index
+        let options = LoadOptions {
+            obj_single_index: false,
+            ..options
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+        assert_eq!(model.meshes[1].vertices.len(), 10);
+        assert!(model.meshes[1].indices.is_none());
+    }
+
+    #[test]
+    fn raw_uv_seam() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/seam.obj");
+
+        let meshes = RawObjMesh::load(model_path).expect("Failed to load obj model");
+        let seam = &meshes[0];
+        assert_eq!(seam.positions.len(), 4);
+        assert_eq!(seam.tex_coords.len(), 6);
+        assert_eq!(seam.faces.len(), 2);
+
+        // Position 0 and 2 are shared by both faces, But with distinct texture coordinates
+        let [first, second] = [&seam.faces[0], &seam.faces[1]];
+        assert_eq!(first[0].position, second[0].position);
+        assert_eq!(first[2].position, second[1].position);
+        assert_eq!(first[0].tex_coord, Some(0));
+        assert_eq!(second[0].tex_coord, Some(3));
+        assert_eq!(first[2].tex_coord, Some(2));
+        assert_eq!(second[1].tex_coord, Some(4));
+        assert!(seam.faces.iter().flatten().all(|i| i.normal == Some(0)));
+    }
+
+    #[test]
+    fn unicode_path() {
+        let dir = std::env::temp_dir().join("modelz_ünïcödé");
+        std::fs::create_dir_all(&dir).unwrap();
+        let obj = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj"))
+            .unwrap()
+            .replace("mtllib cube.mtl", "mtllib würfel.mtl")
+            .replace("usemtl Material", "usemtl Matériau");
+        std::fs::write(dir.join("würfel.obj"), obj).unwrap();
+        std::fs::write(
+            dir.join("würfel.mtl"),
+            "newmtl Matériau\nKd 0.8 0.8 0.8\nmap_Kd textür_é.png\n",
+        )
+        .unwrap();
+
+        let model = Model3D::load(dir.join("würfel.obj")).expect("Failed to load obj model");
+        let material = model
+            .material_by_name("Matériau")
+            .expect("Material not found");
+        let texture = material.diffuse_texture.as_ref().unwrap();
+        assert_eq!(texture.name.as_deref(), Some("textür_é.png"));
+        assert!(
+            matches!(&texture.image, modelz::Image::Path { path, .. } if path.ends_with("textür_é.png"))
+        );
+    }
+
+    #[test]
+    fn mtl_override() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+
+        let options = LoadOptions {
+            mtl_override: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/override.mtl").into()),
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+        let material = model
+            .material_by_name("Material")
+            .expect("Material not found");
+        assert_eq!(material.base_color, Some([1.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn displacement_and_reflection_maps() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+
+        let options = LoadOptions {
+            mtl_override: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/maps.mtl").into()),
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+        let material = model
+            .material_by_name("Material")
+            .expect("Material not found");
+        for texture in [&material.displacement_texture, &material.reflection_texture] {
+            let texture = texture.as_ref().expect("Missing texture");
+            let Image::Path { path, .. } = &texture.image else {
+                panic!("Texture is not a path");
+            };
+            assert!(path.ends_with("checker_256x128.png"));
+            assert!(path.exists());
+        }
+
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert!(model.materials[0].displacement_texture.is_none());
+        assert!(model.materials[0].reflection_texture.is_none());
+    }
+
+    #[test]
+    fn out_of_range_index() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/out_of_range.obj");
+
+        let result = Model3D::load(model_path);
+        assert!(matches!(result, Err(modelz::ModelError::ModelParsing(_))));
+    }
+
+    #[test]
+    fn from_bytes() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let data = std::fs::read(model_path).unwrap();
+
+        // The MTL File next to the OBJ File is never read
+        let result = Model3D::from_bytes(&data, &modelz::ModelFormat::OBJ);
+        assert!(matches!(result, Err(modelz::ModelError::MaterialLoad(_))));
+
+        let options = LoadOptions {
+            mtl_override: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.mtl").into()),
+            ..Default::default()
+        };
+        let model = Model3D::from_bytes_with_options(&data, &modelz::ModelFormat::OBJ, &options)
+            .expect("Failed to load obj model");
+        let file = Model3D::load(model_path).expect("Failed to load obj model");
+        assert_eq!(model.meshes.len(), file.meshes.len());
+        assert_eq!(model.materials.len(), file.materials.len());
+        assert_eq!(model.content_hash(), file.content_hash());
+    }
+
+    #[test]
+    fn append_models() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load obj model");
+        let other = Model3D::load(model_path).expect("Failed to load obj model");
+        assert_eq!(model.materials.len(), 1);
+        let meshes = model.meshes.len();
+        model.append(other);
+
+        assert!(matches!(model.format, modelz::ModelFormat::OBJ));
+        assert_eq!(model.materials.len(), 2);
+        assert_eq!(model.meshes.len(), meshes * 2);
+        assert!(model.meshes[..meshes]
+            .iter()
+            .all(|mesh| mesh.material_index == Some(0)));
+        assert!(model.meshes[meshes..]
+            .iter()
+            .all(|mesh| mesh.material_index == Some(1)));
+        assert_eq!(model.instances().count(), meshes * 2);
+    }
+
+    #[test]
+    fn remove_degenerate_triangles() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/degenerate.obj");
+
+        let model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert_eq!(model.meshes[0].triangle_count(), 3);
+        assert!(model.warnings.is_empty());
+
+        let options = LoadOptions {
+            remove_degenerate_triangles: Some(1e-6),
+            ..Default::default()
+        };
+        let model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.triangle_count(), 2);
+        assert_eq!(mesh.vertices.len(), 4);
+        assert!(mesh.vertices.iter().all(|v| v.position[0] <= 1.0));
+        assert_eq!(model.warnings.len(), 1);
+    }
+
+    #[test]
+    fn preserve_quads() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+
+        for obj_single_index in [true, false] {
+            let options = LoadOptions {
+                obj_single_index,
+                ..Default::default()
+            };
+            let meshes =
+                PolygonMesh::load_obj(model_path, &options).expect("Failed to load obj model");
+            let cube = &meshes[0];
+            assert_eq!(cube.face_sizes.len(), 6);
+            assert!(cube.face_sizes.iter().all(|&size| size == 4));
+            assert_eq!(cube.indices.len(), 24);
+            assert!(cube
+                .indices
+                .iter()
+                .all(|&i| (i as usize) < cube.vertices.len()));
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn preserve_quads_gzip_compressed() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj.gz");
+
+        let meshes = PolygonMesh::load_obj(model_path, &LoadOptions::default())
+            .expect("Failed to load compressed obj model");
+        assert_eq!(meshes[0].face_sizes, [4; 6]);
+        // The MTL File next to the compressed File is still found
+        assert_eq!(meshes[0].material_index, Some(0));
+    }
+
+    #[test]
+    fn retain_meshes() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/lines.obj");
+
+        let options = LoadOptions {
+            obj_triangulate: false,
+            obj_ignore_points: false,
+            obj_ignore_lines: false,
+            ..Default::default()
+        };
+        let mut model =
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model");
+        model.retain_meshes(|mesh| mesh.mode == RenderMode::Triangles);
+        assert_eq!(model.meshes.len(), 1);
+        assert_eq!(model.meshes[0].mode, RenderMode::Triangles);
+
+        // The cube uses the only Material, Without it nothing does
+        let cube_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let mut model = Model3D::load(cube_path).expect("Failed to load obj model");
+        model.append(
+            Model3D::load_with_options(model_path, &options).expect("Failed to load obj model"),
+        );
+        model.retain_meshes(|mesh| mesh.mode != RenderMode::Lines);
+        assert!(model
+            .meshes
+            .iter()
+            .all(|mesh| mesh.mode != RenderMode::Lines));
+        assert_eq!(model.instances().count(), model.meshes.len());
+        model.remove_unused_materials();
+        assert_eq!(model.materials.len(), 1);
+
+        model.retain_meshes(|mesh| mesh.material_index.is_none());
+        model.remove_unused_materials();
+        assert!(model.materials.is_empty());
+        assert_eq!(model.meshes.len(), 2);
+
+        // Out of range indices are left for validate to report
+        model.meshes[0].material_index = Some(3);
+        model.remove_unused_materials();
+        assert_eq!(model.meshes[0].material_index, Some(3));
+    }
 }
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod ply {
-    use modelz::Model3D;
+    use modelz::{Model3D, RenderMode};
 
     #[test]
     fn load_ply() {
@@ -14,4 +14,182 @@ mod ply {
             }
         }
     }
+
+    #[test]
+    fn out_of_range_index() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/out_of_range.ply");
+
+        let result = Model3D::from_format(model_path, &modelz::ModelFormat::PLY);
+        assert!(matches!(result, Err(modelz::ModelError::ModelParsing(_))));
+    }
+
+    #[test]
+    fn face_colors_and_materials() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/face_colors.ply");
+
+        let model = Model3D::load(model_path).expect("Failed to load ply model");
+        assert_eq!(model.meshes.len(), 2);
+        let first = &model.meshes[0];
+        assert_eq!(first.material_index, Some(0));
+        let colors = first
+            .vertices
+            .iter()
+            .map(|v| v.color.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(colors[..3], [[1.0, 0.0, 0.0, 1.0]; 3]);
+        assert_eq!(colors[3..], [[0.0, 0.0, 1.0, 1.0]; 3]);
+
+        let second = &model.meshes[1];
+        assert_eq!(second.material_index, Some(1));
+        assert_eq!(second.vertices.len(), 3);
+        let names = model
+            .materials
+            .iter()
+            .map(|material| material.name.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(names, [Some("material_0"), Some("material_1")]);
+        assert!(model.validate().is_empty());
+        assert!(second
+            .vertices
+            .iter()
+            .all(|v| v.color == Some([0.0, 1.0, 0.0, 1.0])));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzip_compressed() {
+        let plain_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.ply");
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.ply.gz");
+
+        let plain = Model3D::load(plain_path).expect("Failed to load ply model");
+        let model = Model3D::load(model_path).expect("Failed to load compressed ply model");
+        assert_eq!(model.meshes.len(), plain.meshes.len());
+        assert_eq!(
+            model.meshes[0].vertices.len(),
+            plain.meshes[0].vertices.len()
+        );
+        assert_eq!(
+            model.meshes[0].vertices[0].position,
+            plain.meshes[0].vertices[0].position
+        );
+        // Sniffing looks at the decompressed content too
+        assert!(Model3D::load_sniff(model_path).is_ok());
+
+        // The retained source is the compressed File the loader read
+        let options = modelz::LoadOptions {
+            retain_source_bytes: true,
+            ..Default::default()
+        };
+        let model = Model3D::load_with_options(model_path, &options)
+            .expect("Failed to load compressed ply model");
+        assert_eq!(
+            model.unmodified_source(),
+            Some(std::fs::read(model_path).unwrap().as_slice())
+        );
+        assert_eq!(
+            model.meshes[0].vertices.len(),
+            plain.meshes[0].vertices.len()
+        );
+    }
+
+    #[test]
+    fn quads_become_triangle_fans() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.ply");
+
+        // Every face of the cube is a quad
+        let model = Model3D::load(model_path).expect("Failed to load ply model");
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.triangle_count(), 12);
+        assert_eq!(mesh.vertices.len(), 36);
+
+        let mut file_vertices = Vec::new();
+        let file = std::fs::File::open(model_path).unwrap();
+        modelz::ply::stream(file, |vertex| file_vertices.push(vertex.position)).unwrap();
+        let original = mesh.original_indices.as_ref().unwrap();
+        assert_eq!(original.len(), mesh.vertices.len());
+        for (vertex, &index) in mesh.vertices.iter().zip(original) {
+            assert_eq!(vertex.position, file_vertices[index as usize]);
+        }
+        // Both Triangles of a quad share its first and third corner
+        assert_eq!(original[0], original[3]);
+        assert_eq!(original[2], original[4]);
+    }
+
+    #[test]
+    fn stream_vertices() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.ply");
+
+        let mut streamed = Vec::new();
+        let file = std::fs::File::open(model_path).unwrap();
+        modelz::ply::stream(file, |vertex| streamed.push(vertex.clone())).unwrap();
+        assert_eq!(streamed.len(), 14);
+
+        // The full load expands the faces, Every Vertex it produces comes from the stream
+        let model = Model3D::load(model_path).expect("Failed to load ply model");
+        for vertex in &model.meshes[0].vertices {
+            assert!(streamed
+                .iter()
+                .any(|streamed| streamed.position == vertex.position
+                    && streamed.tex_coord == vertex.tex_coord));
+        }
+    }
+
+    #[test]
+    fn double_precision_big_endian() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/double_be.ply");
+
+        let model = Model3D::load(model_path).expect("Failed to load ply model");
+        assert_eq!(model.meshes.len(), 1);
+        let positions = model.meshes[0]
+            .vertices
+            .iter()
+            .map(|v| v.position)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![[0.5, 0.0, 0.0], [1.25, 0.0, 0.0], [0.0, 2.5, -3.0]]
+        );
+    }
+
+    #[test]
+    fn point_cloud() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/points.ply");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load ply model");
+        assert_eq!(model.meshes.len(), 1);
+        let mesh = &mut model.meshes[0];
+        assert_eq!(mesh.mode, RenderMode::Points);
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.vertices[3].position, [0.0, 0.0, 1.0]);
+        assert_eq!(mesh.original_indices, Some(vec![0, 1, 2, 3]));
+
+        // The point cloud utilities work on it directly
+        mesh.voxel_downsample(10.0);
+        assert_eq!(mesh.vertices.len(), 1);
+    }
+
+    #[test]
+    fn original_indices() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.ply");
+        let mut source = Vec::new();
+        modelz::ply::stream(std::fs::File::open(model_path).unwrap(), |vertex| {
+            source.push(vertex.position);
+        })
+        .expect("Failed to stream ply model");
+
+        let mut model = Model3D::load(model_path).expect("Failed to load ply model");
+        let mesh = &mut model.meshes[0];
+        let round_trips = |mesh: &modelz::Mesh| {
+            let original = mesh.original_indices.as_ref().expect("Missing mapping");
+            assert_eq!(original.len(), mesh.vertices.len());
+            for (vertex, &index) in mesh.vertices.iter().zip(original) {
+                assert_eq!(vertex.position, source[index as usize]);
+            }
+        };
+        round_trips(mesh);
+        // Welding merges the corners of each source Vertex again
+        mesh.join_identical_vertices();
+        round_trips(mesh);
+        assert!(mesh.vertices.len() <= source.len());
+    }
 }
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod postprocess {
+    use modelz::{Indices, Mesh, Model3D, ModelFormat, PostProcess, RenderMode, Vertex};
+
+    fn textured_quad() -> Mesh {
+        let corners = [
+            ([0.0, 0.0, 0.0], [0.0, 0.0]),
+            ([1.0, 0.0, 0.0], [1.0, 0.0]),
+            ([1.0, 1.0, 0.0], [1.0, 1.0]),
+            ([0.0, 1.0, 0.0], [0.0, 1.0]),
+        ];
+        let vertices = corners
+            .into_iter()
+            .map(|(position, tex_coord)| Vertex {
+                position,
+                tex_coord: Some(tex_coord),
+                ..Default::default()
+            })
+            .collect();
+        Mesh::new(
+            vertices,
+            Some(Indices::U16(vec![0, 1, 2, 0, 2, 3])),
+            RenderMode::Triangles,
+        )
+    }
+
+    #[test]
+    fn smooth_normals_and_tangents() {
+        let mut model = Model3D::new(vec![textured_quad()], vec![], ModelFormat::OBJ);
+
+        model.post_process(PostProcess::GENERATE_SMOOTH_NORMALS | PostProcess::CALC_TANGENTS);
+
+        for vertex in &model.meshes[0].vertices {
+            assert_eq!(vertex.normal, Some([0.0, 0.0, 1.0]));
+            assert_eq!(vertex.tangent, Some([1.0, 0.0, 0.0, 1.0]));
+        }
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(test)]
+mod send_sync {
+    use modelz::{Material, Mesh, MeshBvh, Model3D, Texture};
+
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    // Fails to compile if any type stops being Send + Sync
+    const _: () = {
+        assert_send_sync::<Model3D>();
+        assert_send_sync::<Mesh>();
+        assert_send_sync::<Material>();
+        assert_send_sync::<Texture>();
+        assert_send_sync::<MeshBvh<'static>>();
+    };
+
+    #[test]
+    fn load_on_worker_thread() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+
+        let model = std::thread::spawn(move || Model3D::load(model_path))
+            .join()
+            .unwrap()
+            .expect("Failed to load obj model");
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert!(!model.meshes.is_empty()));
+            scope.spawn(|| assert!(!model.materials.is_empty()));
+        });
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod sniff {
+    use std::path::PathBuf;
+
+    use modelz::{Model3D, ModelFormat};
+
+    /// Copies a fixture into a temporary directory under a new name
+    fn copy_as(fixture: &str, name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("modelz_sniff");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::copy(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("tests")
+                .join(fixture),
+            &path,
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn load_without_extension() {
+        let cases = [
+            ("cube.glb", "glb_model"),
+            ("morph.gltf", "gltf_model.json"),
+            ("cube.stl", "stl_model"),
+            ("cube.ply", "ply_model.bin"),
+            ("cube.obj", "obj_model.txt"),
+        ];
+        // The OBJ file references its MTL by name
+        copy_as("cube.mtl", "cube.mtl");
+        for (fixture, name) in cases {
+            let path = copy_as(fixture, name);
+            assert!(Model3D::load(&path).is_err(), "{name} should not load");
+            let model = Model3D::load_sniff(&path).expect("Failed to sniff the format");
+            assert!(!model.meshes.is_empty());
+            let expected = match fixture.rsplit('.').next().unwrap() {
+                "glb" | "gltf" => ModelFormat::GLTF,
+                "stl" => ModelFormat::STL,
+                "ply" => ModelFormat::PLY,
+                _ => ModelFormat::OBJ,
+            };
+            assert_eq!(
+                std::mem::discriminant(&model.format),
+                std::mem::discriminant(&expected)
+            );
+        }
+    }
+
+    #[test]
+    fn fall_back_to_extension() {
+        // Empty file, Nothing to sniff so the extension decides
+        let dir = std::env::temp_dir().join("modelz_sniff");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("empty.obj");
+        std::fs::write(&path, "").unwrap();
+        let model = Model3D::load_sniff(&path).expect("Failed to load");
+        assert!(matches!(model.format, ModelFormat::OBJ));
+    }
+}
@@ -1,12 +1,12 @@
 #[cfg(test)]
 mod stl {
-    use modelz::Model3D;
+    use modelz::{LoadOptions, Model3D, StlWinding};
 
     #[test]
     fn load_stl() {
         let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.stl");
 
-        let model = Model3D::from_format(model_path, modelz::ModelFormat::STL)
+        let model = Model3D::from_format(model_path, &modelz::ModelFormat::STL)
             .expect("Failed to load stl model");
         for mesh in model.meshes {
             for vert in mesh.vertices {
@@ -14,4 +14,54 @@ mod stl {
             }
         }
     }
+
+    #[test]
+    fn corrupt_file() {
+        // STL has no indices in the File which could point at missing Vertices, So the only corruption is missing data.
+        // Declares 4 Triangles but only contains 1
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/truncated.stl");
+
+        let result = Model3D::from_format(model_path, &modelz::ModelFormat::STL);
+        assert!(matches!(result, Err(modelz::ModelError::ModelParsing(_))));
+    }
+
+    #[test]
+    fn binary_with_solid_header() {
+        // Binary STL whose header starts with the ASCII magic word "solid"
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/solid_header.stl");
+
+        let model = Model3D::from_format(model_path, &modelz::ModelFormat::STL)
+            .expect("Failed to load stl model");
+        assert_eq!(model.meshes[0].triangles().count(), 12);
+    }
+
+    #[test]
+    fn mixed_winding() {
+        // Both faces wind towards +Z, The second one stores a normal pointing to -Z
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/mixed_winding.stl");
+        let load = |stl_winding| {
+            let options = LoadOptions {
+                stl_winding,
+                ..Default::default()
+            };
+            Model3D::load_with_options(model_path, &options).expect("Failed to load stl model")
+        };
+
+        let model = load(StlWinding::Keep);
+        assert_eq!(model.warnings.len(), 1);
+        assert_eq!(model.meshes[0].vertices[3].normal, Some([0.0, 0.0, -1.0]));
+
+        let model = load(StlWinding::RecomputeNormals);
+        assert_eq!(model.warnings.len(), 1);
+        let mut normals = model.meshes[0].vertices.iter().map(|v| v.normal);
+        assert!(normals.all(|n| n == Some([0.0, 0.0, 1.0])));
+
+        let model = load(StlWinding::FlipWinding);
+        assert_eq!(model.warnings.len(), 1);
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.vertices[3].normal, Some([0.0, 0.0, -1.0]));
+        let face_normals = mesh.face_normals();
+        assert_eq!(face_normals[0], [0.0, 0.0, 1.0]);
+        assert_eq!(face_normals[1], [0.0, 0.0, -1.0]);
+    }
 }
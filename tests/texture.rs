@@ -0,0 +1,98 @@
+#[cfg(all(test, feature = "image"))]
+mod texture {
+    use modelz::{ColorSpace, Image, MinFilter, ModelLoader, Sampler, Texture};
+
+    fn checker() -> Texture {
+        Texture {
+            image: Image::Path {
+                path: concat!(env!("CARGO_MANIFEST_DIR"), "/tests/checker_256x128.png").into(),
+                mime_type: None,
+            },
+            sampler: Sampler::default(),
+            name: None,
+            color_space: ColorSpace::Srgb,
+            tex_coord_set: 0,
+        }
+    }
+
+    #[test]
+    fn mip_level_count() {
+        let mut texture = checker();
+        assert_eq!(texture.mip_level_count().unwrap(), 9);
+
+        texture.sampler.min_filter = Some(MinFilter::Linear);
+        assert_eq!(texture.mip_level_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn to_rgba32f() {
+        let mut texture = checker();
+        texture.image = Image::Path {
+            path: concat!(env!("CARGO_MANIFEST_DIR"), "/tests/sky_2x2.hdr").into(),
+            mime_type: None,
+        };
+        texture.color_space = ColorSpace::Linear;
+
+        let (pixels, width, height) = texture.to_rgba32f().unwrap();
+        assert_eq!((width, height), (2, 2));
+        let close = |pixel: usize, expected: [f32; 3]| {
+            pixels[pixel * 4..pixel * 4 + 3]
+                .iter()
+                .zip(expected)
+                .all(|(value, expected)| (value - expected).abs() <= expected * 0.02 + 0.01)
+        };
+        assert!(close(0, [4.0, 2.0, 1.0]), "{pixels:?}");
+        assert!(close(1, [0.5, 0.5, 0.5]), "{pixels:?}");
+        assert!(close(3, [8.0, 0.0, 0.0]), "{pixels:?}");
+        assert!(pixels.chunks(4).all(|pixel| pixel[3] == 1.0));
+
+        // 8 bit decoding clamps the HDR values
+        let (pixels, ..) = texture.to_rgba8().unwrap();
+        assert_eq!(pixels[..4], [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn exr_to_rgba32f() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/sky_2x2.exr");
+        let mut texture = checker();
+        texture.color_space = ColorSpace::Linear;
+        texture.image = Image::Path {
+            path: path.into(),
+            mime_type: None,
+        };
+        let (pixels, width, height) = texture.to_rgba32f().unwrap();
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(pixels[..4], [4.0, 2.0, 1.0, 1.0]);
+        assert_eq!(pixels[12..], [8.0, 0.0, 0.0, 1.0]);
+
+        // Embedded EXR Images use the mime type glTF and the texture resolver assign
+        let data = std::fs::read(path).unwrap();
+        texture.image = Image::Memory {
+            data,
+            mime_type: Some("image/x-exr".to_string()),
+        };
+        let (memory_pixels, ..) = texture.to_rgba32f().unwrap();
+        assert_eq!(memory_pixels, pixels);
+    }
+
+    #[test]
+    fn shared_texture_cache() {
+        let mut loader = ModelLoader::default();
+        let first = loader
+            .load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/shared_a.obj"))
+            .expect("Failed to load obj model");
+        let second = loader
+            .load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/shared_b.obj"))
+            .expect("Failed to load obj model");
+
+        let a = loader
+            .decode(first.materials[0].diffuse_texture.as_ref().unwrap())
+            .unwrap();
+        let b = loader
+            .decode(second.materials[0].diffuse_texture.as_ref().unwrap())
+            .unwrap();
+        assert!(std::sync::Arc::ptr_eq(&a, &b));
+        assert_eq!(loader.image_count(), 1);
+        assert_eq!((a.width, a.height), (256, 128));
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod usd {
+    use modelz::{Image, Model3D, WrappingMode};
+
+    fn check_cube(model: &Model3D) {
+        assert_eq!(model.meshes.len(), 1);
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.name.as_deref(), Some("Cube"));
+        // faceVarying normals give every face its own 4 Vertices
+        assert_eq!(mesh.vertices.len(), 24);
+        assert_eq!(mesh.indices.as_ref().unwrap().len(), 36);
+        assert_eq!(mesh.vertices[0].normal, Some([0.0, 0.0, 1.0]));
+        assert_eq!(mesh.vertices[2].tex_coord, Some([1.0, 1.0]));
+
+        let material = &model.materials[mesh.material_index.unwrap()];
+        assert_eq!(material.name.as_deref(), Some("Brick"));
+        assert_eq!(material.base_color, Some([0.8, 0.4, 0.2, 1.0]));
+        let texture = material.diffuse_texture.as_ref().unwrap();
+        assert_eq!(texture.sampler.wrap_s, WrappingMode::ClampToEdge);
+    }
+
+    #[test]
+    fn load_usda() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.usda");
+
+        let model = Model3D::load(model_path).expect("Failed to load usda model");
+        check_cube(&model);
+        let texture = model.materials[0].diffuse_texture.as_ref().unwrap();
+        assert!(
+            matches!(&texture.image, Image::Path { path, .. } if path.ends_with("textures/albedo.png"))
+        );
+    }
+
+    #[test]
+    fn load_usdz() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.usdz");
+
+        let model = Model3D::load(model_path).expect("Failed to load usdz model");
+        check_cube(&model);
+        let texture = model.materials[0].diffuse_texture.as_ref().unwrap();
+        let Image::Memory { data, mime_type } = &texture.image else {
+            panic!("Packaged texture is not in memory");
+        };
+        assert!(data.starts_with(b"\x89PNG"));
+        assert_eq!(mime_type.as_deref(), Some("image/png"));
+    }
+
+    #[test]
+    fn binary_usd_is_rejected() {
+        // USDZ package whose root layer is binary usdc
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/binary.usdz");
+        let result = Model3D::load(model_path);
+        assert!(matches!(result, Err(modelz::ModelError::ModelParsing(_))));
+
+        let dir = std::env::temp_dir().join("modelz_usd");
+        std::fs::create_dir_all(&dir).unwrap();
+        let usdc = dir.join("model.usdc");
+        std::fs::write(&usdc, b"PXR-USDC").unwrap();
+        assert!(matches!(
+            Model3D::load(&usdc),
+            Err(modelz::ModelError::UnknowFormat)
+        ));
+
+        // Index which overflows when multiplied by the component count
+        let usda = dir.join("overflow.usda");
+        std::fs::write(
+            &usda,
+            "#usda 1.0\ndef Mesh \"Tri\" {\n    point3f[] points = [(0, 0, 0), (1, 0, 0), (0, 1, 0)]\n    \
+             int[] faceVertexCounts = [3]\n    int[] faceVertexIndices = [0, 1, 6148914691236517853]\n}\n",
+        )
+        .unwrap();
+        let result = Model3D::load(&usda);
+        assert!(matches!(result, Err(modelz::ModelError::ModelParsing(_))));
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod validate {
+    use modelz::{Indices, Mesh, Model3D, RenderMode, ValidationWarning, Vertex};
+
+    #[test]
+    fn broken_model() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/cube.obj");
+        let mut model = Model3D::load(model_path).expect("Failed to load obj model");
+        assert!(model.validate().is_empty());
+
+        let vertex = |position: [f32; 3], normal: [f32; 3]| Vertex {
+            position,
+            normal: Some(normal),
+            ..Default::default()
+        };
+        let mut broken = Mesh::new(
+            vec![
+                vertex([0.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+                vertex([f32::NAN, 0.0, 0.0], [0.0, 0.0, 1.0]),
+                vertex([0.0, 1.0, 0.0], [0.0, 0.0, 0.0]),
+            ],
+            Some(Indices::U16(vec![0, 1, 5])),
+            RenderMode::Triangles,
+        );
+        broken.material_index = Some(7);
+        model.meshes.push(broken);
+        model
+            .meshes
+            .push(Mesh::new(Vec::new(), None, RenderMode::Triangles));
+
+        assert_eq!(
+            model.validate(),
+            [
+                ValidationWarning::IndexOutOfRange { mesh: 1, index: 5 },
+                ValidationWarning::MaterialOutOfRange {
+                    mesh: 1,
+                    material: 7
+                },
+                ValidationWarning::NonFinitePosition { mesh: 1, vertex: 1 },
+                ValidationWarning::ZeroLengthNormal { mesh: 1, vertex: 2 },
+                ValidationWarning::EmptyMesh { mesh: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitize() {
+        let mut mesh = Mesh::new(
+            vec![
+                Vertex {
+                    position: [f32::NAN, 1.0, f32::INFINITY],
+                    normal: Some([0.0, 2.0, 0.0]),
+                    ..Default::default()
+                },
+                Vertex {
+                    position: [0.0, 1.0, 0.0],
+                    normal: Some([f32::NAN, 0.0, 1.0]),
+                    ..Default::default()
+                },
+                Vertex {
+                    position: [1.0, 0.0, 0.0],
+                    normal: Some([0.0, 0.0, 1.0]),
+                    ..Default::default()
+                },
+            ],
+            None,
+            RenderMode::Triangles,
+        );
+        // Two position components, One normal to renormalize and one to zero
+        assert_eq!(mesh.sanitize(), 4);
+        assert_eq!(mesh.vertices[0].position, [0.0, 1.0, 0.0]);
+        assert_eq!(mesh.vertices[0].normal, Some([0.0, 1.0, 0.0]));
+        assert_eq!(mesh.vertices[1].normal, Some([0.0; 3]));
+        assert_eq!(mesh.sanitize(), 0);
+    }
+}
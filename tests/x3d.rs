@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod x3d {
+    use modelz::{AlphaMode, Model3D, WrappingMode};
+
+    fn check_quad(model: &Model3D) {
+        assert_eq!(model.meshes.len(), 2);
+        let mesh = &model.meshes[0];
+        assert_eq!(mesh.name.as_deref(), Some("Quad"));
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(
+            mesh.indices.as_ref().unwrap().iter().collect::<Vec<_>>(),
+            [0, 1, 2, 0, 2, 3]
+        );
+        assert_eq!(mesh.vertices[2].position, [1.0, 1.0, 0.0]);
+        assert_eq!(mesh.vertices[2].tex_coord, Some([1.0, 1.0]));
+
+        let material = &model.materials[mesh.material_index.unwrap()];
+        assert_eq!(material.name.as_deref(), Some("Red"));
+        assert_eq!(material.base_color, Some([1.0, 0.0, 0.0, 0.5]));
+        assert_eq!(material.alpha_mode, AlphaMode::Blend);
+        assert!(material.double_sided);
+        let texture = material.diffuse_texture.as_ref().unwrap();
+        assert_eq!(texture.sampler.wrap_s, WrappingMode::ClampToEdge);
+        assert_eq!(texture.sampler.wrap_t, WrappingMode::Repeat);
+    }
+
+    #[test]
+    fn load_vrml() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/quad.wrl");
+
+        let model = Model3D::load(model_path).expect("Failed to load vrml model");
+        check_quad(&model);
+    }
+
+    #[test]
+    fn load_x3d() {
+        let model_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/quad.x3d");
+
+        let model = Model3D::load(model_path).expect("Failed to load x3d model");
+        check_quad(&model);
+    }
+
+    #[test]
+    fn malformed_x3d() {
+        let dir = std::env::temp_dir().join("modelz_x3d");
+        std::fs::create_dir_all(&dir).unwrap();
+        let cases = [
+            // Attribute value without quotes, Starting with a multi byte character
+            "<X3D a=\u{e9}x/>".to_string(),
+            // Index which overflows when multiplied by the component count
+            format!(
+                "<X3D><Scene><Shape><IndexedFaceSet coordIndex=\"0 1 {} -1\">\
+                 <Coordinate point=\"0 0 0, 1 0 0, 1 1 0\"/></IndexedFaceSet></Shape></Scene></X3D>",
+                usize::MAX / 3 + 1
+            ),
+        ];
+        for (i, case) in cases.iter().enumerate() {
+            let path = dir.join(format!("malformed_{i}.x3d"));
+            std::fs::write(&path, case).unwrap();
+            let result = Model3D::load(&path);
+            assert!(
+                matches!(result, Err(modelz::ModelError::ModelParsing(_))),
+                "Case {i} did not fail"
+            );
+        }
+    }
+}